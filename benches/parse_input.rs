@@ -0,0 +1,48 @@
+// The input parsers alone, not a render: the month grid parses every open task title for
+// effort estimates on every frame. Run with `cargo bench`.
+use calpersonal::parse_input;
+use chrono::NaiveDate;
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+// Roughly a busy month: 42 cells, each checking every task
+fn titles() -> Vec<String> {
+    (0..200)
+        .map(|i| match i % 4 {
+            0 => format!("Write report ~{}h", i % 5 + 1),
+            1 => format!("Review PR #{i} ~45m"),
+            2 => format!("Call back {i}"),
+            _ => format!("Plan sprint ~1h30m notes: item {i}"),
+        })
+        .collect()
+}
+
+fn parsers(c: &mut Criterion) {
+    let titles = titles();
+    c.bench_function("effort for 42 cells", |b| {
+        b.iter(|| {
+            for _ in 0..42 {
+                for title in &titles {
+                    black_box(parse_input::parse_effort(black_box(title)));
+                }
+            }
+        })
+    });
+
+    let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    let inputs = [
+        "10:00 - 10:30 Standup",
+        "3/4 9:00 - 17:00 Offsite",
+        "2026/3/5 - 2026/3/7 Conference",
+        "Lunch with Sam",
+    ];
+    c.bench_function("parse_time_range", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(parse_input::parse_time_range(black_box(input), today));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, parsers);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+use crate::platform;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{OpenOptions, create_dir_all, read_to_string};
+use std::io::Write;
+
+const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub at: DateTime<Local>,
+    pub action: String,
+    pub target: String, // calendar or tasklist id, then item id when there is one
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub result: String, // "ok" or the API error
+}
+
+impl AuditEntry {
+    // Best human-readable name found in either payload
+    pub fn title(&self) -> String {
+        [&self.after, &self.before]
+            .into_iter()
+            .flatten()
+            .find_map(|v| {
+                v.get("summary")
+                    .or_else(|| v.get("title"))
+                    .and_then(|s| s.as_str())
+            })
+            .unwrap_or("")
+            .to_string()
+    }
+}
+
+pub fn payload<T: Serialize>(item: &T) -> Option<Value> {
+    serde_json::to_value(item).ok()
+}
+
+// Appends one line per mutation; failures to write never block the change itself
+pub fn record<E: std::fmt::Display>(
+    action: &str,
+    target: &str,
+    before: Option<Value>,
+    after: Option<Value>,
+    result: &Result<(), E>,
+) {
+    match result {
+        Ok(_) => tracing::info!(target, "{action}"),
+        Err(e) => tracing::warn!(target, "{action} failed: {e}"),
+    }
+    let entry = AuditEntry {
+        at: Local::now(),
+        action: action.to_string(),
+        target: target.to_string(),
+        before,
+        after,
+        result: match result {
+            Ok(_) => "ok".to_string(),
+            Err(e) => e.to_string(),
+        },
+    };
+    let path = platform::cache_dir().join(AUDIT_LOG_FILE);
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    let Ok(line) = serde_json::to_string(&entry) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+// Newest first
+pub fn load_recent(limit: usize) -> Vec<AuditEntry> {
+    let path = platform::cache_dir().join(AUDIT_LOG_FILE);
+    let Ok(data) = read_to_string(path) else {
+        return Vec::new();
+    };
+    data.lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,138 @@
+use ratatui::text::Span;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Ltr,
+    Rtl,
+    Number,
+    Neutral,
+}
+
+fn is_rtl(ch: char) -> bool {
+    matches!(ch as u32,
+        0x0590..=0x08FF      // Hebrew, Arabic, Syriac, Thaana, NKo
+        | 0xFB1D..=0xFDFF    // Hebrew and Arabic presentation forms A
+        | 0xFE70..=0xFEFF    // Arabic presentation forms B
+        | 0x10800..=0x10FFF
+        | 0x1E800..=0x1EFFF)
+}
+
+fn class(ch: char) -> Class {
+    if is_rtl(ch) {
+        Class::Rtl
+    } else if ch.is_ascii_digit() {
+        Class::Number
+    } else if ch.is_alphabetic() {
+        Class::Ltr
+    } else {
+        Class::Neutral
+    }
+}
+
+// Paragraph direction follows the first strong character, like most editors
+pub fn is_rtl_text(text: &str) -> bool {
+    text.chars()
+        .map(class)
+        .find(|c| matches!(c, Class::Ltr | Class::Rtl))
+        == Some(Class::Rtl)
+}
+
+// Embedding level per character: a cut-down UAX #9 with no explicit embeddings
+fn levels(chars: &[char]) -> Vec<u8> {
+    let classes: Vec<Class> = chars.iter().map(|c| class(*c)).collect();
+    let base = match classes
+        .iter()
+        .find(|c| matches!(c, Class::Ltr | Class::Rtl))
+    {
+        Some(Class::Rtl) => 1,
+        _ => 0,
+    };
+
+    // Numbers after right-to-left text act as RTL for neutrals but keep their own digit order
+    let mut rtl_context = base == 1;
+    let dirs: Vec<Option<bool>> = classes
+        .iter()
+        .map(|c| match c {
+            Class::Rtl => {
+                rtl_context = true;
+                Some(true)
+            }
+            Class::Ltr => {
+                rtl_context = false;
+                Some(false)
+            }
+            Class::Number => Some(rtl_context),
+            Class::Neutral => None,
+        })
+        .collect();
+
+    let mut levels = vec![base; chars.len()];
+    for i in 0..chars.len() {
+        let rtl = match dirs[i] {
+            Some(rtl) => rtl,
+            None => {
+                // Neutrals take the direction of their neighbours when both sides agree
+                let before = dirs[..i].iter().rev().find_map(|d| *d);
+                let after = dirs[i + 1..].iter().find_map(|d| *d);
+                match (before, after) {
+                    (Some(b), Some(a)) if b == a => b,
+                    _ => base == 1,
+                }
+            }
+        };
+        levels[i] = match (rtl, classes[i]) {
+            (true, Class::Number) => 2,
+            (true, _) => 1,
+            (false, _) if base == 1 => 2,
+            (false, _) => 0,
+        };
+    }
+    levels
+}
+
+// Logical indices in the order they should be drawn left to right
+pub fn visual_order(text: &str) -> Vec<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let levels = levels(&chars);
+    let mut order: Vec<usize> = (0..chars.len()).collect();
+
+    let max = levels.iter().copied().max().unwrap_or(0);
+    for level in (1..=max).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < order.len() && levels[order[i]] >= level {
+                i += 1;
+            }
+            order[start..i].reverse();
+        }
+    }
+    order
+}
+
+pub fn display(text: &str) -> String {
+    if !text.chars().any(is_rtl) {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    visual_order(text).into_iter().map(|i| chars[i]).collect()
+}
+
+// Cuts in logical order first so an RTL title loses its end, not its beginning
+pub fn truncate_display(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut kept = String::new();
+    for ch in text.chars() {
+        let w = Span::raw(ch.to_string()).width();
+        if used + w > width {
+            break;
+        }
+        used += w;
+        kept.push(ch);
+    }
+    display(&kept)
+}
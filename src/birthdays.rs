@@ -0,0 +1,195 @@
+use crate::calendar_auth;
+use crate::error::{Error, Result};
+use crate::merge::EventsCache;
+use crate::{net, platform};
+use chrono::{Datelike, Days, NaiveDate};
+use google_calendar3::{CalendarHub, api};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::time::Duration;
+
+// Birthdays stand in for a calendar with this id; never cached with Google's events or sent back
+pub const CALENDAR: &str = "contacts:birthdays";
+pub const BIRTHDAYS_REFRESH: Duration = Duration::from_secs(24 * 60 * 60);
+const CACHE_FILE: &str = "calendar_cache/birthdays.json";
+const CONNECTIONS_URL: &str = "https://people.googleapis.com/v1/people/me/connections";
+
+pub fn is_birthday(calendar_id: &str) -> bool {
+    calendar_id == CALENDAR
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Birthday {
+    pub contact: String, // People API resource name, e.g. people/c123
+    pub name: String,
+    pub month: u32,
+    pub day: u32,
+    pub year: Option<i32>, // left out by many contacts
+}
+
+impl Birthday {
+    // Feb 29 falls on Feb 28 in other years
+    pub fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, self.month, self.day)
+            .or_else(|| NaiveDate::from_ymd_opt(year, self.month, self.day - 1))
+    }
+
+    // The next one on or after `today`
+    pub fn next(&self, today: NaiveDate) -> Option<NaiveDate> {
+        self.in_year(today.year())
+            .filter(|date| *date >= today)
+            .or_else(|| self.in_year(today.year() + 1))
+    }
+
+    pub fn age_on(&self, date: NaiveDate) -> Option<i32> {
+        self.year.map(|year| date.year() - year)
+    }
+
+    pub fn title(&self, date: NaiveDate) -> String {
+        match self.age_on(date) {
+            Some(age) => format!("🎂 {} ({age})", self.name),
+            None => format!("🎂 {}", self.name),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connections {
+    #[serde(default)]
+    connections: Vec<Person>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Person {
+    resource_name: String,
+    #[serde(default)]
+    names: Vec<Name>,
+    #[serde(default)]
+    birthdays: Vec<PersonBirthday>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Name {
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PersonBirthday {
+    date: Option<PartialDate>,
+}
+
+#[derive(Deserialize)]
+struct PartialDate {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+}
+
+impl Person {
+    fn birthday(self) -> Option<Birthday> {
+        let date = self.birthdays.into_iter().find_map(|b| b.date)?;
+        let name = self.names.into_iter().find_map(|n| n.display_name)?;
+        Some(Birthday {
+            contact: self.resource_name,
+            name,
+            month: date.month.filter(|m| *m > 0)?,
+            day: date.day.filter(|d| *d > 0)?,
+            // The People API sends 0 for "no year" in some clients' data
+            year: date.year.filter(|y| *y > 0),
+        })
+    }
+}
+
+// Every contact with a birthday, through the calendar sign-in (which asked for the
+// contacts scope when birthdays are on)
+pub async fn fetch<C>(hub: &CalendarHub<C>, timeout: Duration) -> Result<Vec<Birthday>> {
+    let scopes = calendar_auth::scopes(true);
+    let token = hub
+        .auth
+        .get_token(&scopes)
+        .await
+        .map_err(|e| Error::token(e.to_string()))?
+        .ok_or_else(|| Error::Auth("no token for the contacts scope".to_string()))?;
+    let client = net::http_client().timeout(timeout).build()?;
+    let mut birthdays = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut request = client
+            .get(CONNECTIONS_URL)
+            .bearer_auth(&token)
+            .query(&[("personFields", "names,birthdays"), ("pageSize", "1000")]);
+        if let Some(page) = &page_token {
+            request = request.query(&[("pageToken", page)]);
+        }
+        let page: Connections = request.send().await?.error_for_status()?.json().await?;
+        birthdays.extend(page.connections.into_iter().filter_map(Person::birthday));
+        match page.next_page_token {
+            Some(next) => page_token = Some(next),
+            None => break,
+        }
+    }
+    Ok(birthdays)
+}
+
+pub fn load_cached() -> Vec<Birthday> {
+    match read_to_string(platform::cache_dir().join(CACHE_FILE)) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn save_cached(birthdays: &[Birthday]) {
+    let path = platform::cache_dir().join(CACHE_FILE);
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(birthdays) {
+        let _ = write(path, json);
+    }
+}
+
+// All-day events for last year, this year and next, to lay over the events cache
+pub fn events(birthdays: &[Birthday], today: NaiveDate) -> EventsCache {
+    let mut cache = EventsCache::new();
+    for birthday in birthdays {
+        let contact = birthday.contact.trim_start_matches("people/");
+        for year in today.year() - 1..=today.year() + 1 {
+            let Some(date) = birthday.in_year(year) else {
+                continue;
+            };
+            let day = |date: NaiveDate| api::EventDateTime {
+                date: Some(date),
+                ..Default::default()
+            };
+            let event = api::Event {
+                id: Some(format!("birthday_{contact}_{year}")),
+                summary: Some(birthday.title(date)),
+                status: Some("confirmed".to_string()),
+                event_type: Some("birthday".to_string()),
+                transparency: Some("transparent".to_string()),
+                start: Some(day(date)),
+                end: Some(day(date + Days::new(1))),
+                ..Default::default()
+            };
+            cache
+                .entry(date)
+                .or_default()
+                .push((event, CALENDAR.to_string()));
+        }
+    }
+    cache
+}
+
+// (next date, birthday) soonest first
+pub fn upcoming(birthdays: &[Birthday], today: NaiveDate) -> Vec<(NaiveDate, &Birthday)> {
+    let mut upcoming: Vec<(NaiveDate, &Birthday)> = birthdays
+        .iter()
+        .filter_map(|b| Some((b.next(today)?, b)))
+        .collect();
+    upcoming.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    upcoming
+}
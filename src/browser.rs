@@ -0,0 +1,21 @@
+use std::process::{Command, Stdio};
+
+// URL openers tried in order
+const OPEN_COMMANDS: [(&str, &[&str]); 4] = [
+    ("xdg-open", &[]),
+    ("open", &[]),
+    ("wslview", &[]),
+    ("cmd.exe", &["/c", "start", ""]),
+];
+
+pub fn open(url: &str) -> bool {
+    OPEN_COMMANDS.iter().any(|(program, args)| {
+        Command::new(program)
+            .args(*args)
+            .arg(url)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
@@ -1,44 +1,131 @@
-use dirs::home_dir;
-use google_calendar3::{CalendarHub, yup_oauth2};
-use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
-use hyper_util::{client::legacy::Client, client::legacy::connect, rt::TokioExecutor};
-use std::error::Error;
-
-pub async fn get_calendar_hub()
--> Result<CalendarHub<HttpsConnector<connect::HttpConnector>>, Box<dyn Error>> {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(".config/calpersonal/clientsecret.json");
-
-    let token_path = home_dir()
-        .expect("Could not find home directory")
-        .join(".cache/calpersonal/calendar_tokens/tokencache.json");
-
-    let secret: yup_oauth2::ApplicationSecret = yup_oauth2::read_application_secret(secret_path)
-        .await
-        .map_err(|e| format!("clientsecret.json not found: {}", e))?;
-
-    // 2. Authenticator (opens browser first time, reuses tokencache.json)
-    let auth = yup_oauth2::InstalledFlowAuthenticator::builder(
-        secret,
-        yup_oauth2::InstalledFlowReturnMethod::HTTPRedirect,
-    )
-    .persist_tokens_to_disk(token_path)
-    .build()
-    .await
-    .map_err(|e| format!("Failed to create authenticator: {}", e))?;
-
-    let scopes = &["https://www.googleapis.com/auth/calendar"];
-    auth.token(scopes)
-        .await
-        .map_err(|e| format!("Failed to get token: {}", e))?;
-
-    let https = HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| format!("Failed to load native roots: {}", e))?
-        .https_or_http()
-        .enable_http1()
-        .build();
+use crate::config::{Access, AuthConfig, NetworkConfig};
+use crate::error::{Error, Result};
+use crate::net;
+use crate::platform;
+use google_calendar3::{CalendarHub, api::Scope, yup_oauth2};
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const CALENDAR_SCOPE: &str = "https://www.googleapis.com/auth/calendar";
+const EVENTS_SCOPE: &str = "https://www.googleapis.com/auth/calendar.events";
+const READONLY_SCOPE: &str = "https://www.googleapis.com/auth/calendar.readonly";
+// Asked for as well when contact birthdays are on
+const CONTACTS_SCOPE: &str = "https://www.googleapis.com/auth/contacts.readonly";
+
+// [auth] access, set once at startup; calendar and task calls alike ask within it
+static ACCESS: OnceLock<Access> = OnceLock::new();
+
+pub fn set_access(access: Access) {
+    let _ = ACCESS.set(access);
+}
+
+pub fn access() -> Access {
+    ACCESS.get().copied().unwrap_or_default()
+}
+
+pub fn scopes(contacts: bool) -> Vec<&'static str> {
+    let mut scopes = match access() {
+        Access::Full => vec![CALENDAR_SCOPE],
+        Access::Events => vec![EVENTS_SCOPE, READONLY_SCOPE],
+        Access::ReadOnly => vec![READONLY_SCOPE],
+    };
+    if contacts {
+        scopes.push(CONTACTS_SCOPE);
+    }
+    scopes
+}
+
+// Every call names one of these: a scope the stored token doesn't cover would start
+// the browser sign-in again in the middle of a session
+pub fn read_scope() -> Scope {
+    match access() {
+        Access::Full => Scope::Full,
+        Access::Events | Access::ReadOnly => Scope::Readonly,
+    }
+}
+
+pub fn write_scope() -> Scope {
+    match access() {
+        Access::Full => Scope::Full,
+        Access::Events | Access::ReadOnly => Scope::Event,
+    }
+}
+
+fn token_path() -> PathBuf {
+    platform::cache_dir().join("calendar_tokens/tokencache.json")
+}
+
+fn service_token_path() -> PathBuf {
+    platform::cache_dir().join("calendar_tokens/service_account.json")
+}
+
+// Drops the stored token so the next sign-in goes through the browser again
+pub fn forget_token() {
+    let _ = std::fs::remove_file(token_path());
+    let _ = std::fs::remove_file(service_token_path());
+}
+
+// Whether a hub can be had without the browser sign-in, for runs with no one to click through it
+pub fn signed_in(auth_config: &AuthConfig) -> bool {
+    auth_config.service_account.is_some() || token_path().exists()
+}
+
+pub async fn get_calendar_hub(
+    network: NetworkConfig,
+    client_secret: Option<String>,
+    auth_config: AuthConfig,
+    contacts: bool,
+) -> Result<CalendarHub<net::Connector>> {
+    // Sign-in and refreshes go through the proxy and CA bundle like every other call
+    let token_client = net::token_client(&network).map_err(|e| Error::Network(e.to_string()))?;
+    let auth = match &auth_config.service_account {
+        Some(key_path) => {
+            tracing::info!(api = "calendar", "signing in with a service account");
+            let key = yup_oauth2::read_service_account_key(platform::expand_home(key_path))
+                .await
+                .map_err(|e| Error::Auth(format!("service account key {key_path}: {e}")))?;
+            let builder =
+                yup_oauth2::ServiceAccountAuthenticator::builder(key).hyper_client(token_client);
+            let builder = match &auth_config.subject {
+                Some(subject) => builder.subject(subject.clone()),
+                None => builder,
+            };
+            builder
+                .persist_tokens_to_disk(service_token_path())
+                .build()
+                .await?
+        }
+        None => {
+            let secret_path = platform::config_dir().join("clientsecret.json");
+
+            tracing::info!(api = "calendar", "signing in");
+            // Decrypted at startup when configured; otherwise the plain file
+            let secret: yup_oauth2::ApplicationSecret = match client_secret {
+                Some(json) => yup_oauth2::parse_application_secret(json)
+                    .map_err(|e| Error::Auth(format!("invalid client secret: {e}")))?,
+                None => yup_oauth2::read_application_secret(secret_path)
+                    .await
+                    .map_err(|e| Error::Auth(format!("clientsecret.json not found: {e}")))?,
+            };
+
+            // 2. Authenticator (opens browser first time, reuses tokencache.json)
+            yup_oauth2::InstalledFlowAuthenticator::builder(
+                secret,
+                yup_oauth2::InstalledFlowReturnMethod::HTTPRedirect,
+            )
+            .hyper_client(token_client)
+            .persist_tokens_to_disk(token_path())
+            .build()
+            .await?
+        }
+    };
+
+    // Token requests go out through token_client, so each is timed like an API call; only the
+    // wait for the browser on first sign-in is not
+    auth.token(&scopes(contacts)).await?;
+
+    let https = net::build_connector(&network).map_err(|e| Error::Network(e.to_string()))?;
 
     let client = Client::builder(TokioExecutor::new()).build(https);
     // 6. Create and return the hub (generics inferred)
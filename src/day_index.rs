@@ -0,0 +1,92 @@
+use crate::merge::{EventsCache, LocalChange};
+use crate::reminders::event_start;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+// Position of an event in its day's list in events_cache, with what it sorts by
+#[derive(Clone, Copy)]
+pub struct EventRef {
+    pub index: usize,
+    all_day: bool,
+    start: Option<DateTime<FixedOffset>>,
+}
+
+// Sorted view over events_cache, so the grid and week views walk days in order and list events
+// by start time without hashing each date or cloning events. Rebuilt after a sync, patched a
+// day at a time for local edits.
+#[derive(Default)]
+pub struct DayIndex {
+    days: BTreeMap<NaiveDate, Vec<EventRef>>,
+    ids: HashMap<String, NaiveDate>, // event id → the day it is listed under
+}
+
+impl DayIndex {
+    pub fn build(cache: &EventsCache, app_tz: FixedOffset) -> DayIndex {
+        let mut index = DayIndex::default();
+        for date in cache.keys() {
+            index.update_day(cache, *date, app_tz);
+        }
+        index
+    }
+
+    pub fn update_day(&mut self, cache: &EventsCache, date: NaiveDate, app_tz: FixedOffset) {
+        self.ids.retain(|_, listed| *listed != date);
+        let Some(events) = cache.get(&date).filter(|events| !events.is_empty()) else {
+            self.days.remove(&date);
+            return;
+        };
+        let mut refs: Vec<EventRef> = events
+            .iter()
+            .enumerate()
+            .map(|(index, (event, _))| {
+                if let Some(id) = &event.id {
+                    self.ids.insert(id.clone(), date);
+                }
+                EventRef {
+                    index,
+                    all_day: event.start.as_ref().is_some_and(|s| s.date.is_some()),
+                    start: event_start(event, app_tz),
+                }
+            })
+            .collect();
+        // All-day events first, then by start; the sort is stable so ties keep fetch order
+        refs.sort_by_key(|r| (!r.all_day, r.start));
+        self.days.insert(date, refs);
+    }
+
+    // Days a local change adds to or removes from: where the event was listed and where it
+    // starts now
+    pub fn touched_by(&self, change: &LocalChange, app_tz: FixedOffset) -> Vec<NaiveDate> {
+        let (id, event) = match change {
+            LocalChange::Create(event) => (None, Some(event)),
+            LocalChange::Update(event) => (event.id.as_deref(), Some(event)),
+            LocalChange::Delete(id) => (Some(id.as_str()), None),
+        };
+        id.and_then(|id| self.ids.get(id).copied())
+            .into_iter()
+            .chain(
+                event
+                    .and_then(|event| event_start(event, app_tz))
+                    .map(|start| start.date_naive()),
+            )
+            .collect()
+    }
+
+    pub fn date_of(&self, id: &str) -> Option<NaiveDate> {
+        self.ids.get(id).copied()
+    }
+
+    pub fn day(&self, date: NaiveDate) -> &[EventRef] {
+        self.days.get(&date).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn range(
+        &self,
+        dates: RangeInclusive<NaiveDate>,
+    ) -> impl Iterator<Item = (NaiveDate, &[EventRef])> {
+        self.days
+            .range(dates)
+            .map(|(date, refs)| (*date, refs.as_slice()))
+    }
+}
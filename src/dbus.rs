@@ -0,0 +1,126 @@
+// A session-bus service for desktop widgets and scripts, compiled in with `--features dbus`:
+//   org.calpersonal.Calendar at /org/calpersonal/Calendar, interface org.calpersonal.Calendar1
+//   NextEvent() -> (title, start as RFC 3339), empty strings when nothing is coming up
+//   Counts() -> (events today, tasks due, tasks open)
+//   AddEvent(text) parses `text` like the input line and creates the event
+// The UI publishes a fresh snapshot every tick and picks up queued commands the same way.
+
+#[derive(Default, Clone)]
+#[cfg_attr(not(feature = "dbus"), allow(dead_code))]
+pub struct Snapshot {
+    pub next_event: Option<(String, String)>,
+    pub events_today: u32,
+    pub tasks_due: u32,
+    pub tasks_open: u32,
+}
+
+#[cfg_attr(not(feature = "dbus"), allow(dead_code))]
+pub enum Command {
+    AddEvent(String),
+}
+
+#[cfg(feature = "dbus")]
+mod service {
+    use super::{Command, Snapshot};
+    use std::sync::{Arc, Mutex};
+
+    const NAME: &str = "org.calpersonal.Calendar";
+    const PATH: &str = "/org/calpersonal/Calendar";
+
+    struct Calendar {
+        snapshot: Arc<Mutex<Snapshot>>,
+        commands: Arc<Mutex<Vec<Command>>>,
+    }
+
+    #[zbus::interface(name = "org.calpersonal.Calendar1")]
+    impl Calendar {
+        fn next_event(&self) -> (String, String) {
+            self.snapshot
+                .lock()
+                .unwrap()
+                .next_event
+                .clone()
+                .unwrap_or_default()
+        }
+
+        fn counts(&self) -> (u32, u32, u32) {
+            let snapshot = self.snapshot.lock().unwrap();
+            (
+                snapshot.events_today,
+                snapshot.tasks_due,
+                snapshot.tasks_open,
+            )
+        }
+
+        fn add_event(&self, text: String) {
+            self.commands.lock().unwrap().push(Command::AddEvent(text));
+            crate::wakeup::wake();
+        }
+    }
+
+    pub struct Service {
+        snapshot: Arc<Mutex<Snapshot>>,
+        commands: Arc<Mutex<Vec<Command>>>,
+    }
+
+    impl Service {
+        // No session bus, or another instance holding the name, only gets logged
+        pub fn start() -> Service {
+            let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+            let commands = Arc::new(Mutex::new(Vec::new()));
+            let calendar = Calendar {
+                snapshot: snapshot.clone(),
+                commands: commands.clone(),
+            };
+            tokio::spawn(async move {
+                let connection = async {
+                    zbus::connection::Builder::session()?
+                        .name(NAME)?
+                        .serve_at(PATH, calendar)?
+                        .build()
+                        .await
+                }
+                .await;
+                match connection {
+                    // Held for as long as the app runs
+                    Ok(connection) => {
+                        tracing::info!("dbus service at {NAME}");
+                        std::future::pending::<()>().await;
+                        drop(connection);
+                    }
+                    Err(e) => tracing::warn!("dbus: {e}"),
+                }
+            });
+            Service { snapshot, commands }
+        }
+
+        pub fn publish(&self, snapshot: Snapshot) {
+            *self.snapshot.lock().unwrap() = snapshot;
+        }
+
+        pub fn take_commands(&self) -> Vec<Command> {
+            std::mem::take(&mut *self.commands.lock().unwrap())
+        }
+    }
+}
+
+#[cfg(not(feature = "dbus"))]
+mod service {
+    use super::{Command, Snapshot};
+
+    pub struct Service;
+
+    impl Service {
+        pub fn start() -> Service {
+            Service
+        }
+
+        pub fn publish(&self, _snapshot: Snapshot) {}
+
+        pub fn take_commands(&self) -> Vec<Command> {
+            Vec::new()
+        }
+    }
+}
+
+pub use service::Service;
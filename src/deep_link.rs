@@ -0,0 +1,18 @@
+// calpersonal://event/<id> links; an OS URL handler can run `calpersonal open %u`
+const EVENT_PREFIX: &str = "calpersonal://event/";
+
+pub fn event_link(id: &str) -> String {
+    format!("{EVENT_PREFIX}{}", urlencoding::encode(id))
+}
+
+// The event id from `open <link or id>`, or from a bare link as the only argument
+pub fn from_args(args: &[String]) -> Option<String> {
+    let target = match args.get(1).map(String::as_str) {
+        Some("open") => args.get(2)?,
+        Some(arg) if arg.starts_with(EVENT_PREFIX) => &args[1],
+        _ => return None,
+    };
+    let id = target.strip_prefix(EVENT_PREFIX).unwrap_or(target);
+    let id = urlencoding::decode(id.trim_end_matches('/')).ok()?;
+    (!id.is_empty()).then(|| id.into_owned())
+}
@@ -0,0 +1,344 @@
+use crate::memos::{self, Memo};
+use crate::weather::{
+    AirQuality, CachedWeather, DailyTemp, DailyWeather, HourlyWeather, OneCallResponse, Weather,
+    WeatherData,
+};
+use crate::{file_writing, platform, weather};
+use chrono::{DateTime, Days, FixedOffset, Local, NaiveDate, TimeZone};
+use google_calendar3::api;
+use google_tasks1::api::Task;
+use std::collections::HashMap;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// --demo: made-up calendars, events, tasks and weather around today in a throwaway directory,
+// so every view has something to show without signing in, going online or touching real data
+const CALENDARS: [(&str, &str, &str); 3] = [
+    ("demo-personal", "Personal", "#7986cb"),
+    ("demo-work", "Work", "#33b679"),
+    ("demo-family", "Family", "#f6bf26"),
+];
+const TASK_LIST: &str = "demo-tasks";
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+// Colleagues on the work calendar's meetings, so the meeting report has people to count
+const COLLEAGUES: [(&str, &str); 3] = [
+    ("Alex Kim", "alex@example.com"),
+    ("Sam Rivera", "sam@example.com"),
+    ("Jo Park", "jo@example.com"),
+];
+
+// Days from today, calendar, start (hour, minute), minutes, title, location
+type Timed = (i64, usize, (u32, u32), i64, &'static str, &'static str);
+const TIMED: [Timed; 14] = [
+    (0, 1, (10, 0), 60, "Design review", "Room 4B"),
+    (0, 0, (12, 30), 60, "Lunch with Sam", "Corner Bistro"),
+    (0, 1, (15, 0), 30, "1:1 with Alex", ""),
+    (1, 1, (9, 0), 90, "Sprint planning", ""),
+    (1, 0, (18, 30), 60, "Climbing", "Boulder Hall"),
+    (2, 2, (8, 0), 30, "School run", ""),
+    (
+        2,
+        1,
+        (14, 0),
+        60,
+        "Customer call",
+        "https://meet.google.com/abc-defg-hij",
+    ),
+    (3, 0, (19, 0), 120, "Dinner party", "Jo's place"),
+    (-1, 1, (11, 0), 45, "Retro", ""),
+    (-2, 0, (7, 30), 60, "Morning run", "Riverside park"),
+    (-3, 2, (17, 0), 60, "Piano lesson", ""),
+    (5, 0, (10, 0), 60, "Dentist", "Smile Dental"),
+    (8, 1, (13, 0), 120, "Quarterly review", "Main auditorium"),
+    (12, 2, (16, 0), 90, "Parents' evening", "School hall"),
+];
+
+// Days from today, days covered, calendar, title
+const ALL_DAY: [(i64, i64, usize, &str); 3] = [
+    (6, 3, 0, "Trip to the coast"),
+    (4, 1, 2, "Grandma's birthday"),
+    (-6, 1, 1, "Company offsite"),
+];
+
+// Days from today (None: no due date), done, title
+const TASKS: [(Option<i64>, bool, &str); 7] = [
+    (Some(0), false, "Send slides ~30m"),
+    (Some(0), true, "Book train tickets"),
+    (Some(1), false, "Review PR #42 ~45m"),
+    (Some(3), false, "Buy birthday present"),
+    (Some(-1), false, "Renew passport"),
+    (None, false, "Plan the garden"),
+    (None, false, "Read the new RFC ~1h"),
+];
+
+fn on(today: NaiveDate, days: i64) -> NaiveDate {
+    today + chrono::Duration::days(days)
+}
+
+fn calendars() -> HashMap<String, api::CalendarListEntry> {
+    CALENDARS
+        .iter()
+        .map(|(id, name, color)| {
+            let entry = api::CalendarListEntry {
+                id: Some(id.to_string()),
+                summary: Some(name.to_string()),
+                background_color: Some(color.to_string()),
+                access_role: Some("owner".to_string()),
+                primary: Some(*id == "demo-personal"),
+                ..Default::default()
+            };
+            (id.to_string(), entry)
+        })
+        .collect()
+}
+
+fn event(
+    id: String,
+    title: &str,
+    start: api::EventDateTime,
+    end: api::EventDateTime,
+) -> api::Event {
+    api::Event {
+        id: Some(id),
+        summary: Some(title.to_string()),
+        status: Some("confirmed".to_string()),
+        start: Some(start),
+        end: Some(end),
+        ..Default::default()
+    }
+}
+
+// Left unanswered, so the invitations view has one; the others show a maybe and a no
+const INVITATION: &str = "Quarterly review";
+const TENTATIVE: &str = "Customer call";
+const DECLINED: &str = "Retro";
+
+// You, with your answer, and the first `count` colleagues
+fn guests(count: usize, response: &str) -> Option<Vec<api::EventAttendee>> {
+    let you = api::EventAttendee {
+        email: Some("you@example.com".to_string()),
+        self_: Some(true),
+        response_status: Some(response.to_string()),
+        ..Default::default()
+    };
+    let colleagues = COLLEAGUES
+        .iter()
+        .take(count)
+        .map(|(name, email)| api::EventAttendee {
+            display_name: Some(name.to_string()),
+            email: Some(email.to_string()),
+            response_status: Some("accepted".to_string()),
+            ..Default::default()
+        });
+    Some(std::iter::once(you).chain(colleagues).collect())
+}
+
+fn events(today: NaiveDate, tz: FixedOffset) -> HashMap<NaiveDate, Vec<(api::Event, String)>> {
+    let at = |date: NaiveDate, (hour, minute): (u32, u32)| -> DateTime<FixedOffset> {
+        tz.from_local_datetime(&date.and_hms_opt(hour, minute, 0).unwrap())
+            .unwrap()
+    };
+    let timed = |start: DateTime<FixedOffset>| api::EventDateTime {
+        date_time: Some(start.to_utc()),
+        ..Default::default()
+    };
+    let mut cache: HashMap<NaiveDate, Vec<(api::Event, String)>> = HashMap::new();
+    for (i, (days, calendar, start, minutes, title, location)) in TIMED.iter().enumerate() {
+        let date = on(today, *days);
+        let start = at(date, *start);
+        let mut ev = event(
+            format!("demo{i}"),
+            title,
+            timed(start),
+            timed(start + chrono::Duration::minutes(*minutes)),
+        );
+        if !location.is_empty() {
+            ev.location = Some(location.to_string());
+        }
+        if *calendar == 1 {
+            let response = match *title {
+                INVITATION => "needsAction",
+                TENTATIVE => "tentative",
+                DECLINED => "declined",
+                _ => "accepted",
+            };
+            ev.attendees = guests(1 + i % COLLEAGUES.len(), response);
+            // Sent by the first colleague on the list
+            let (name, email) = COLLEAGUES[0];
+            ev.organizer = Some(api::EventOrganizer {
+                display_name: Some(name.to_string()),
+                email: Some(email.to_string()),
+                ..Default::default()
+            });
+        }
+        let calendar = CALENDARS[*calendar].0.to_string();
+        cache.entry(date).or_default().push((ev, calendar));
+    }
+    // A weekly standup a month either side, so recurring views have a series
+    for week in -4..=4 {
+        let date = on(today, week * 7);
+        let start = at(date, (9, 30));
+        let mut ev = event(
+            format!("demostandup_{week}"),
+            "Team standup",
+            timed(start),
+            timed(start + chrono::Duration::minutes(15)),
+        );
+        ev.recurring_event_id = Some("demostandup".to_string());
+        ev.attendees = guests(COLLEAGUES.len(), "accepted");
+        let calendar = CALENDARS[1].0.to_string();
+        cache.entry(date).or_default().push((ev, calendar));
+    }
+    for (i, (days, covered, calendar, title)) in ALL_DAY.iter().enumerate() {
+        let date = on(today, *days);
+        let day = |date: NaiveDate| api::EventDateTime {
+            date: Some(date),
+            ..Default::default()
+        };
+        let ev = event(
+            format!("demoallday{i}"),
+            title,
+            day(date),
+            day(on(date, *covered)),
+        );
+        let calendar = CALENDARS[*calendar].0.to_string();
+        cache.entry(date).or_default().push((ev, calendar));
+    }
+    // Listed in start order within a day, like a fetch
+    for day in cache.values_mut() {
+        day.sort_by_key(|(ev, _)| ev.start.as_ref().and_then(|s| s.date_time));
+    }
+    cache
+}
+
+fn tasks(today: NaiveDate) -> Vec<(Task, String)> {
+    TASKS
+        .iter()
+        .enumerate()
+        .map(|(i, (days, done, title))| {
+            let task = Task {
+                id: Some(format!("demotask{i}")),
+                title: Some(title.to_string()),
+                due: days.map(|d| format!("{}T00:00:00.000Z", on(today, d))),
+                status: Some(if *done { "completed" } else { "needsAction" }.to_string()),
+                completed: done.then(|| Local::now().to_utc().to_rfc3339()),
+                ..Default::default()
+            };
+            (task, TASK_LIST.to_string())
+        })
+        .collect()
+}
+
+fn sky(main: &str, icon: &str) -> Vec<Weather> {
+    vec![Weather {
+        main: main.to_string(),
+        icon: icon.to_string(),
+    }]
+}
+
+// Mild and changeable, with two wet days
+fn weather(today: NaiveDate, tz: FixedOffset) -> CachedWeather {
+    let noon = |date: NaiveDate| {
+        tz.from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .timestamp()
+    };
+    let daily = (0..8)
+        .map(|d| {
+            let wet = d == 2 || d == 3;
+            DailyWeather {
+                dt: noon(on(today, d)),
+                temp: DailyTemp {
+                    max: 19.0 + (d % 3) as f64,
+                    min: 11.0 + (d % 2) as f64,
+                },
+                humidity: if wet { 82.0 } else { 60.0 },
+                wind_speed: 3.5,
+                pressure: 1014.0,
+                uvi: if wet { 2.0 } else { 5.0 },
+                rain: wet.then_some(6.5),
+                snow: None,
+                weather: if wet {
+                    sky("Rain", "10d")
+                } else {
+                    sky("Clouds", "02d")
+                },
+                pop: if wet { 0.8 } else { 0.1 },
+            }
+        })
+        .collect();
+    let now = Local::now().timestamp() / 3600 * 3600;
+    let hourly = (0..48)
+        .map(|h| HourlyWeather {
+            dt: now + h * 3600,
+            temp: 15.0 + 4.0 * ((h as f64 - 3.0) * std::f64::consts::PI / 12.0).sin(),
+            pop: if (30..40).contains(&h) { 0.7 } else { 0.1 },
+        })
+        .collect();
+    CachedWeather {
+        fetched_at: Local::now(),
+        response: OneCallResponse {
+            lat: 51.5,
+            lon: -0.12,
+            current: WeatherData {
+                temp: 17.0,
+                feels_like: 16.0,
+                humidity: 64.0,
+                wind_speed: 3.1,
+                pressure: 1015.0,
+                uvi: 4.0,
+                clouds: 40.0,
+                rain: None,
+                snow: None,
+                weather: sky("Clouds", "02d"),
+            },
+            daily,
+            hourly,
+            alerts: None,
+        },
+        air: Some(AirQuality {
+            us_aqi: 38.0,
+            pm2_5: 6.2,
+        }),
+    }
+}
+
+// Removes this run's directory on the way out
+pub fn cleanup() {
+    if let Some(root) = ROOT.get() {
+        let _ = remove_dir_all(root);
+    }
+}
+
+// Points every path at a fresh temp directory and fills its caches. The directory is private
+// and unique to this run, so two demos never clear each other's data.
+pub fn prepare() {
+    let root = tempfile::Builder::new()
+        .prefix("calpersonal-demo-")
+        .tempdir()
+        .expect("Could not create a demo directory")
+        .keep();
+    let _ = ROOT.set(root.clone());
+    platform::set_root(root);
+    let cache = platform::cache_dir();
+    let _ = create_dir_all(cache.join("calendar_cache"));
+    let _ = create_dir_all(cache.join("task_cache"));
+
+    let today = Local::now().date_naive();
+    let tz = *Local::now().offset();
+    file_writing::save_calendars_cache(&calendars());
+    file_writing::save_events_cache(&events(today, tz));
+    file_writing::save_tasks_cache(&tasks(today));
+    weather::save_cached(&weather(today, tz));
+    memos::save(&[
+        Memo {
+            created_at: Local::now(),
+            text: "Call the plumber about the leak".to_string(),
+        },
+        Memo {
+            created_at: Local::now() - Days::new(1),
+            text: "Gift ideas: book, plant, concert tickets".to_string(),
+        },
+    ]);
+}
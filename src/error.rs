@@ -0,0 +1,143 @@
+use crate::StatusColor;
+use google_calendar3::{common, yup_oauth2};
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Set when a request was rejected for its credentials; the UI signs in again on its next tick
+static REAUTH_NEEDED: AtomicBool = AtomicBool::new(false);
+
+pub fn take_reauth() -> bool {
+    REAUTH_NEEDED.swap(false, Ordering::Relaxed)
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Auth(String),
+    Revoked(String), // the token endpoint answered invalid_grant: only a new sign-in helps
+    Network(String),
+    Api { status: u16, reason: String },
+    Parse(String),
+    Io(io::Error),
+    Cache(String),
+    Config(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // A failed token fetch, which is usually the network being down and keeps the stored
+    // token; only invalid_grant means the refresh token itself is gone
+    pub fn token(reason: String) -> Error {
+        if reason.contains("invalid_grant") {
+            Error::Revoked(reason)
+        } else {
+            Error::Auth(reason)
+        }
+    }
+
+    // 401s and invalid_grant mean the stored token was revoked or expired beyond refresh
+    pub fn needs_reauth(&self) -> bool {
+        matches!(self, Error::Revoked(_) | Error::Api { status: 401, .. })
+    }
+
+    // The status line for a failed background action; flags re-auth when that is the fix
+    pub fn status(self) -> (String, StatusColor) {
+        if self.needs_reauth() {
+            REAUTH_NEEDED.store(true, Ordering::Relaxed);
+        }
+        (format!("Failed: {self}"), StatusColor::Red)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Auth(reason) => write!(f, "sign-in failed: {reason}"),
+            Error::Revoked(reason) => write!(f, "sign-in expired: {reason}"),
+            Error::Network(reason) => write!(f, "network error: {reason}"),
+            Error::Api { status, reason } => write!(f, "{status} {reason}"),
+            Error::Parse(reason) => write!(f, "unexpected response: {reason}"),
+            Error::Io(e) => e.fmt(f),
+            Error::Cache(reason) => f.write_str(reason),
+            Error::Config(reason) => write!(f, "check the config: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<yup_oauth2::Error> for Error {
+    fn from(e: yup_oauth2::Error) -> Self {
+        Error::token(e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        match e.status() {
+            Some(status) => Error::Api {
+                status: status.as_u16(),
+                reason: status.canonical_reason().unwrap_or_default().to_string(),
+            },
+            None if e.is_decode() => Error::Parse(e.to_string()),
+            None => Error::Network(e.to_string()),
+        }
+    }
+}
+
+// hyper only says "client error (Connect)"; the innermost cause says what went wrong,
+// e.g. net's "Timed out after 15s"
+fn root_cause(e: &dyn std::error::Error) -> String {
+    let mut cause = e;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
+// Google's error body: {"error": {"code": 404, "message": "Not Found"}}
+fn api_error(body: &serde_json::Value) -> Error {
+    let error = &body["error"];
+    Error::Api {
+        status: error["code"].as_u64().unwrap_or_default() as u16,
+        reason: error["message"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| body.to_string()),
+    }
+}
+
+impl From<common::Error> for Error {
+    fn from(e: common::Error) -> Self {
+        match e {
+            common::Error::HttpError(e) => Error::Network(root_cause(&e)),
+            common::Error::Io(e) => Error::Io(e),
+            common::Error::BadRequest(body) => api_error(&body),
+            common::Error::Failure(response) => Error::Api {
+                status: response.status().as_u16(),
+                reason: response
+                    .status()
+                    .canonical_reason()
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            common::Error::MissingAPIKey => Error::Auth("no API key".to_string()),
+            common::Error::MissingToken(e) => Error::token(e.to_string()),
+            common::Error::JsonDecodeError(_, e) => Error::Parse(e.to_string()),
+            e => Error::Network(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use crate::config::{Config, DisplayConfig};
+use crate::merge::EventsCache;
+use crate::{birthdays, ics};
+use chrono::FixedOffset;
+use google_calendar3::api;
+use ratatui::style::{Color, Modifier};
+use std::collections::HashMap;
+
+// Event types requested from the API; workingLocation is only returned when asked for, and
+// asking for any turns the list into an allow-list, so every other type is named too
+pub const EVENT_TYPES: [&str; 6] = [
+    "birthday",
+    "default",
+    "focusTime",
+    "outOfOffice",
+    "workingLocation",
+    "fromGmail",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EventKind {
+    Normal,
+    OutOfOffice,
+    FocusTime,
+    WorkingLocation,
+}
+
+impl EventKind {
+    pub fn of(event: &api::Event) -> EventKind {
+        match event.event_type.as_deref() {
+            Some("outOfOffice") => EventKind::OutOfOffice,
+            Some("focusTime") => EventKind::FocusTime,
+            Some("workingLocation") => EventKind::WorkingLocation,
+            _ => EventKind::Normal,
+        }
+    }
+
+    // Background-span events are drawn as shading instead of regular entries
+    pub fn is_background(self) -> bool {
+        !matches!(self, EventKind::Normal)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventKind::Normal => "",
+            EventKind::OutOfOffice => "OOO",
+            EventKind::FocusTime => "Focus",
+            EventKind::WorkingLocation => "Working",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            EventKind::Normal => Color::Reset,
+            EventKind::OutOfOffice => Color::Magenta,
+            EventKind::FocusTime => Color::Cyan,
+            EventKind::WorkingLocation => Color::Gray,
+        }
+    }
+
+    pub fn api_name(self) -> Option<&'static str> {
+        match self {
+            EventKind::Normal => None,
+            EventKind::OutOfOffice => Some("outOfOffice"),
+            EventKind::FocusTime => Some("focusTime"),
+            EventKind::WorkingLocation => Some("workingLocation"),
+        }
+    }
+
+    // Fills in the type-specific properties Google requires when inserting
+    pub fn apply_to(self, event: &mut api::Event) {
+        event.event_type = self.api_name().map(|s| s.to_string());
+        match self {
+            EventKind::OutOfOffice => {
+                event.transparency = Some("opaque".to_string());
+                event.out_of_office_properties = Some(api::EventOutOfOfficeProperties {
+                    auto_decline_mode: Some("declineOnlyNewConflictingInvitations".to_string()),
+                    decline_message: None,
+                });
+            }
+            EventKind::FocusTime => {
+                event.transparency = Some("opaque".to_string());
+                event.focus_time_properties = Some(api::EventFocusTimeProperties {
+                    auto_decline_mode: Some("declineNone".to_string()),
+                    chat_status: Some("doNotDisturb".to_string()),
+                    decline_message: None,
+                });
+            }
+            EventKind::WorkingLocation | EventKind::Normal => {}
+        }
+    }
+}
+
+// Short label for a workingLocation event ("Home", "Office", or the custom label)
+pub fn working_location_label(event: &api::Event) -> Option<String> {
+    let properties = event.working_location_properties.as_ref()?;
+    match properties.type_.as_deref() {
+        Some("homeOffice") => Some("Home".to_string()),
+        Some("officeLocation") => Some(
+            properties
+                .office_location
+                .as_ref()
+                .and_then(|o| o.label.clone())
+                .unwrap_or("Office".to_string()),
+        ),
+        Some("customLocation") => properties
+            .custom_location
+            .as_ref()
+            .and_then(|c| c.label.clone())
+            .or(Some("Custom".to_string())),
+        _ => None,
+    }
+}
+
+// Builds the properties for `home`, `office` or any custom location text
+pub fn working_location_properties(input: &str) -> api::EventWorkingLocationProperties {
+    match input.to_lowercase().as_str() {
+        "home" => api::EventWorkingLocationProperties {
+            type_: Some("homeOffice".to_string()),
+            home_office: Some(serde_json::json!({})),
+            ..Default::default()
+        },
+        "office" => api::EventWorkingLocationProperties {
+            type_: Some("officeLocation".to_string()),
+            office_location: Some(Default::default()),
+            ..Default::default()
+        },
+        _ => api::EventWorkingLocationProperties {
+            type_: Some("customLocation".to_string()),
+            custom_location: Some(api::EventWorkingLocationPropertiesCustomLocation {
+                label: Some(input.to_string()),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+// Days an all-day event covers (Google's end date is exclusive); 1 for timed events
+pub fn all_day_span(event: &api::Event) -> i64 {
+    let start = event.start.as_ref().and_then(|s| s.date);
+    let end = event.end.as_ref().and_then(|e| e.date);
+    match (start, end) {
+        (Some(start), Some(end)) => (end - start).num_days().max(1),
+        _ => 1,
+    }
+}
+
+pub fn meet_link(event: &api::Event) -> Option<String> {
+    event.hangout_link.clone().or_else(|| {
+        event
+            .conference_data
+            .as_ref()?
+            .entry_points
+            .as_ref()?
+            .iter()
+            .find(|e| e.entry_point_type.as_deref() == Some("video"))?
+            .uri
+            .clone()
+    })
+}
+
+// Plain-text rundown of an event for pasting into chat
+pub fn summary_text(event: &api::Event, app_tz: FixedOffset, display: &DisplayConfig) -> String {
+    let title = event.summary.as_deref().unwrap_or("Untitled");
+    let start = event.start.as_ref();
+    let end = event.end.as_ref();
+    let when = match (
+        start.and_then(|s| s.date_time),
+        end.and_then(|e| e.date_time),
+        start.and_then(|s| s.date),
+    ) {
+        (Some(start), Some(end), _) => format!(
+            "{} {} - {}",
+            start.with_timezone(&app_tz).format(&display.date_format),
+            start.with_timezone(&app_tz).format(display.time_format()),
+            end.with_timezone(&app_tz).format(display.time_format())
+        ),
+        (_, _, Some(date)) => format!("{} (all day)", date.format(&display.date_format)),
+        _ => String::new(),
+    };
+
+    let mut lines = vec![title.to_string(), when];
+    if let Some(location) = &event.location {
+        lines.push(format!("Location: {location}"));
+    }
+    if let Some(link) = meet_link(event) {
+        lines.push(format!("Meet: {link}"));
+    }
+    lines.join("\n")
+}
+
+// Label/value rows for the detail popup, skipping fields the event doesn't have
+pub fn detail_rows(
+    event: &api::Event,
+    calendar: &str,
+    app_tz: FixedOffset,
+    display: &DisplayConfig,
+) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    let start = event.start.as_ref();
+    let end = event.end.as_ref();
+    match (
+        start.and_then(|s| s.date_time),
+        end.and_then(|e| e.date_time),
+    ) {
+        (Some(start), Some(end)) => rows.push((
+            "When",
+            format!(
+                "{} {} - {} {}",
+                start.with_timezone(&app_tz).format(&display.date_format),
+                start.with_timezone(&app_tz).format(display.time_format()),
+                end.with_timezone(&app_tz).format(&display.date_format),
+                end.with_timezone(&app_tz).format(display.time_format())
+            ),
+        )),
+        _ => {
+            if let Some(date) = start.and_then(|s| s.date) {
+                rows.push((
+                    "When",
+                    format!("{} (all day)", date.format(&display.date_format)),
+                ));
+            }
+        }
+    }
+    if let Some(rules) = &event.recurrence {
+        rows.push(("Repeats", rules.join("; ")));
+    } else if event.recurring_event_id.is_some() {
+        rows.push(("Repeats", "part of a series".to_string()));
+    }
+    if let Some(location) = &event.location {
+        rows.push(("Location", location.clone()));
+    }
+    if let Some(organizer) = &event.organizer {
+        let name = organizer
+            .display_name
+            .clone()
+            .or_else(|| organizer.email.clone());
+        if let Some(name) = name {
+            rows.push(("Organizer", name));
+        }
+    }
+    for attendee in event.attendees.iter().flatten() {
+        let name = attendee
+            .display_name
+            .as_deref()
+            .or(attendee.email.as_deref())
+            .unwrap_or("?");
+        let optional = if attendee.optional == Some(true) {
+            ", optional"
+        } else {
+            ""
+        };
+        rows.push((
+            "Attendee",
+            format!(
+                "{name} ({}{optional})",
+                attendee.response_status.as_deref().unwrap_or("needsAction")
+            ),
+        ));
+    }
+    if let Some(link) = meet_link(event) {
+        rows.push(("Meet", link));
+    }
+    rows.push(("Calendar", calendar.to_string()));
+    rows
+}
+
+// Descriptions set in Google's editor are HTML; keep the line breaks, drop the tags
+pub fn plain_description(description: &str) -> String {
+    let with_breaks = description
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("</p>", "\n")
+        .replace("</li>", "\n");
+    let mut text = String::with_capacity(with_breaks.len());
+    let mut in_tag = false;
+    for c in with_breaks.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+}
+
+// Your own response, treating events without an attendee list as accepted (you made them)
+pub fn response_status(event: &api::Event) -> &str {
+    event
+        .attendees
+        .as_ref()
+        .and_then(|attendees| attendees.iter().find(|a| a.self_ == Some(true)))
+        .and_then(|me| me.response_status.as_deref())
+        .unwrap_or("accepted")
+}
+
+// Before the titles of events you've answered maybe to
+pub fn response_marker(event: &api::Event) -> &'static str {
+    if response_status(event) == "tentative" {
+        "? "
+    } else {
+        ""
+    }
+}
+
+// Events you've declined stay visible, struck through
+pub fn response_modifier(event: &api::Event) -> Modifier {
+    if response_status(event) == "declined" {
+        Modifier::DIM | Modifier::CROSSED_OUT
+    } else {
+        Modifier::empty()
+    }
+}
+
+pub const RESPONSE_STATUSES: [&str; 4] = ["accepted", "tentative", "needsAction", "declined"];
+
+// Quick filters for the Events popup; unset parts match everything
+#[derive(Default)]
+pub struct EventFilter {
+    pub calendar: Option<String>,
+    pub response: Option<&'static str>,
+    pub text: Option<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &api::Event, calendar_id: &str) -> bool {
+        self.calendar.as_ref().is_none_or(|c| c == calendar_id)
+            && self.response.is_none_or(|r| response_status(event) == r)
+            && self.text.as_ref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                [&event.summary, &event.location, &event.description]
+                    .iter()
+                    .any(|field| {
+                        field
+                            .as_deref()
+                            .is_some_and(|f| f.to_lowercase().contains(&text))
+                    })
+            })
+    }
+}
+
+// Most common title among the cached instances of each recurring series
+pub fn series_titles(cache: &EventsCache) -> HashMap<String, String> {
+    let mut counts: HashMap<&str, HashMap<&str, usize>> = HashMap::new();
+    for (event, _) in cache.values().flatten() {
+        if let (Some(series), Some(title)) = (&event.recurring_event_id, &event.summary) {
+            *counts.entry(series).or_default().entry(title).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter_map(|(series, titles)| {
+            let (title, _) = titles.into_iter().max_by_key(|(_, n)| *n)?;
+            Some((series.to_string(), title.to_string()))
+        })
+        .collect()
+}
+
+// How a single instance of a recurring series differs from the pattern, if it does
+pub fn exception_note(
+    event: &api::Event,
+    series_titles: &HashMap<String, String>,
+    app_tz: FixedOffset,
+    display: &DisplayConfig,
+) -> Option<String> {
+    let series = event.recurring_event_id.as_ref()?;
+    let mut notes = Vec::new();
+    let original = event.original_start_time.as_ref()?;
+    let start = event.start.as_ref()?;
+    if let (Some(from), Some(to)) = (original.date_time, start.date_time)
+        && from != to
+    {
+        let from = from.with_timezone(&app_tz);
+        let format = if from.date_naive() == to.with_timezone(&app_tz).date_naive() {
+            display.time_format().to_string()
+        } else {
+            format!("{} {}", display.date_format, display.time_format())
+        };
+        notes.push(format!("moved from {}", from.format(&format)));
+    } else if let (Some(from), Some(to)) = (original.date, start.date)
+        && from != to
+    {
+        notes.push(format!("moved from {}", from.format(&display.date_format)));
+    }
+    if let Some(title) = series_titles.get(series)
+        && event.summary.as_ref() != Some(title)
+    {
+        notes.push(format!("series: {title}"));
+    }
+    (!notes.is_empty()).then(|| notes.join("; "))
+}
+
+// A [calendars] or [calendar_colors] entry, by id or by the calendar's name
+pub fn calendar_entry<'a, T>(
+    table: &'a HashMap<String, T>,
+    calendar_id: &str,
+    name: Option<&str>,
+) -> Option<&'a T> {
+    // Ids are kept URL-encoded; the config has them as Google shows them
+    let id = urlencoding::decode(calendar_id).unwrap_or_default();
+    [Some(id.as_ref()), name]
+        .into_iter()
+        .flatten()
+        .find_map(|key| table.get(key))
+}
+
+// Why the calendar takes no changes, for the TUI and `calpersonal mcp` alike
+pub fn read_only_reason(
+    config: &Config,
+    calendar_id: &str,
+    name: Option<&str>,
+) -> Option<&'static str> {
+    if ics::is_feed(calendar_id) {
+        Some("Subscribed feeds are read-only")
+    } else if birthdays::is_birthday(calendar_id) {
+        Some("Birthdays come from your contacts")
+    } else if calendar_entry(&config.calendars, calendar_id, name).is_some_and(|o| o.read_only) {
+        Some("This calendar is read-only here")
+    } else {
+        None
+    }
+}
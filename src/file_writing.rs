@@ -1,16 +1,18 @@
-use chrono::NaiveDate;
-use dirs::home_dir;
+use crate::platform;
+use chrono::{Days, Local, NaiveDate};
 use google_calendar3::api;
-use std::collections::HashMap;
-use std::fs::{read_to_string, write};
+use std::collections::{HashMap, HashSet};
+use std::fs::{read_to_string, remove_file, write};
 
-const EVENTS_CACHE_FILE: &str = ".cache/calpersonal/calendar_cache/events_cache.json";
-const TASKS_CACHE_FILE: &str = ".cache/calpersonal/task_cache/tasks_cache.json";
+const EVENTS_CACHE_FILE: &str = "calendar_cache/events_cache.json";
+const CALENDARS_CACHE_FILE: &str = "calendar_cache/calendars_cache.json";
+const TASKS_CACHE_FILE: &str = "task_cache/tasks_cache.json";
+const ARCHIVE_FILE: &str = "calendar_cache/events_archive.json";
+const STARRED_FILE: &str = "calendar_cache/starred.json";
+const PANE_SIZE_FILE: &str = "pane_size";
 
 pub fn load_events_cache() -> HashMap<NaiveDate, Vec<(api::Event, String)>> {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(EVENTS_CACHE_FILE);
+    let secret_path = platform::cache_dir().join(EVENTS_CACHE_FILE);
 
     match read_to_string(secret_path) {
         Ok(data) => serde_json::from_str(&data).unwrap_or_default(), // Deserialize or default on error
@@ -19,18 +21,65 @@ pub fn load_events_cache() -> HashMap<NaiveDate, Vec<(api::Event, String)>> {
 }
 
 pub fn save_events_cache(cache: &HashMap<NaiveDate, Vec<(api::Event, String)>>) {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(EVENTS_CACHE_FILE);
-    if let Ok(json) = serde_json::to_string(cache) {
+    let secret_path = platform::cache_dir().join(EVENTS_CACHE_FILE);
+    if let Ok(json) = crate::slim::to_json(cache) {
         let _ = write(secret_path, json); // Ignore write errors (e.g., permissions)
     }
 }
 
+// Days past the horizon, kept apart so the working cache stays bounded
+pub fn load_archive() -> HashMap<NaiveDate, Vec<(api::Event, String)>> {
+    let path = platform::cache_dir().join(ARCHIVE_FILE);
+    match read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+// Moves days older than the horizon out of the cache into the archive; a day fetched again
+// replaces its archived copy
+pub fn archive_old_events(
+    cache: &mut HashMap<NaiveDate, Vec<(api::Event, String)>>,
+    horizon_days: u64,
+) {
+    let cutoff = Local::now().date_naive() - Days::new(horizon_days);
+    let old: Vec<NaiveDate> = cache
+        .keys()
+        .filter(|date| **date < cutoff)
+        .copied()
+        .collect();
+    if old.is_empty() {
+        return;
+    }
+    let mut archive = load_archive();
+    for date in old {
+        if let Some(events) = cache.remove(&date) {
+            archive.insert(date, events);
+        }
+    }
+    let path = platform::cache_dir().join(ARCHIVE_FILE);
+    if let Ok(json) = crate::slim::to_json(&archive) {
+        let _ = write(path, json);
+    }
+}
+
+pub fn load_calendars_cache() -> HashMap<String, api::CalendarListEntry> {
+    let secret_path = platform::cache_dir().join(CALENDARS_CACHE_FILE);
+    match read_to_string(secret_path) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+pub fn save_calendars_cache(cache: &HashMap<String, api::CalendarListEntry>) {
+    let secret_path = platform::cache_dir().join(CALENDARS_CACHE_FILE);
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = write(secret_path, json);
+    }
+}
+
 pub fn load_tasks_cache() -> Vec<(google_tasks1::api::Task, String)> {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(TASKS_CACHE_FILE);
+    let secret_path = platform::cache_dir().join(TASKS_CACHE_FILE);
     match read_to_string(secret_path) {
         Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
         Err(_) => Vec::new(),
@@ -38,10 +87,43 @@ pub fn load_tasks_cache() -> Vec<(google_tasks1::api::Task, String)> {
 }
 
 pub fn save_tasks_cache(cache: &[(google_tasks1::api::Task, String)]) {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(TASKS_CACHE_FILE);
-    if let Ok(json) = serde_json::to_string(cache) {
+    let secret_path = platform::cache_dir().join(TASKS_CACHE_FILE);
+    if let Ok(json) = crate::slim::to_json(cache) {
         let _ = write(secret_path, json);
     }
 }
+
+// Ids of events starred locally; never sent to Google
+pub fn load_starred() -> HashSet<String> {
+    let path = platform::cache_dir().join(STARRED_FILE);
+    match read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+pub fn save_starred(starred: &HashSet<String>) {
+    let path = platform::cache_dir().join(STARRED_FILE);
+    if let Ok(json) = serde_json::to_string(starred) {
+        let _ = write(path, json);
+    }
+}
+
+// Width of the side pane (the task details, the day panel) as last resized with `{`/`}`
+pub fn load_pane_size() -> Option<u16> {
+    let path = platform::cache_dir().join(PANE_SIZE_FILE);
+    read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// None forgets it, so the configured size applies again
+pub fn save_pane_size(size: Option<u16>) {
+    let path = platform::cache_dir().join(PANE_SIZE_FILE);
+    match size {
+        Some(size) => {
+            let _ = write(path, size.to_string());
+        }
+        None => {
+            let _ = remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// The `?` overlay; keep in step with handle_key_event and parse_input
+pub const SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Moving around",
+        &[
+            ("h j k l / arrows", "day or selection"),
+            ("< >", "month (weather: forecast day)"),
+            ("y Y", "year forward / back"),
+            ("t", "today"),
+            ("z", "zoom the selected day"),
+            ("[ ]", "scroll a day cell that has more events than fit"),
+            ("x", "mark a range of days, then a to add an event over it"),
+            ("1-9", "pick an event in the Events list or the day panel"),
+            ("Esc q", "close the view, quit from the calendar"),
+            ("Ctrl-Z", "suspend to the shell"),
+        ],
+    ),
+    (
+        "Views",
+        &[
+            ("E", "events of the selected day"),
+            ("T", "tasks"),
+            ("v", "day timeline"),
+            ("g", "agenda"),
+            ("/", "search every cached event as you type"),
+            ("V", "week timeline under the month"),
+            ("|", "selected day's events beside the month"),
+            ("{ }", "widen / narrow the side pane (= resets)"),
+            ("K", "compare with a year ago"),
+            ("W", "weather"),
+            ("m", "memos"),
+            ("S", "starred events"),
+            (
+                "I",
+                "invitations waiting for an answer (a/t/d: accept, maybe, decline)",
+            ),
+            ("B", "upcoming contact birthdays"),
+            ("J", "free/busy bars for the week, across every calendar"),
+            ("C", "calendars"),
+            ("X", "trash"),
+            ("A", "audit log"),
+            ("H", "status history"),
+            ("!", "message log: status messages, warnings and errors"),
+            ("G", "weekly review"),
+            ("?", "this help"),
+        ],
+    ),
+    (
+        "Changing things",
+        &[
+            ("o", "add an event (a task in the task list)"),
+            ("a", "edit the selected event or task; add in lists"),
+            ("D", "delete the selected item"),
+            ("space", "complete a task"),
+            ("L", "clear completed tasks"),
+            ("Enter", "details, open or confirm"),
+            ("*", "star an event"),
+            ("N", "note for the day in $EDITOR"),
+            ("w", "set a working location"),
+            ("M", "find a meeting slot"),
+            ("P", "unlock or lock private events"),
+            ("R", "refresh now"),
+            (
+                ":",
+                "run a command (:plan, :report, :remind, :reminders, scripts)",
+            ),
+        ],
+    ),
+    (
+        "In the Events list",
+        &[
+            ("f", "cycle calendars"),
+            ("r", "cycle your response"),
+            ("/ F", "filter by text / clear filters"),
+            ("c", "copy the event as text"),
+            ("U", "copy a calpersonal:// link"),
+            ("O", "open in the browser"),
+        ],
+    ),
+    (
+        "Input line",
+        &[
+            ("Title", "all-day event on the selected day"),
+            ("10:00 - 11:30 Title", "timed, on the selected day"),
+            ("6/12 10:00 - 11:30 Title", "timed, on another day"),
+            ("2026/6/12 10:00 - 11:30 Title", "with the year"),
+            ("6/12 Title", "all-day on 6/12"),
+            (
+                "6/12 - 6/14 Title",
+                "all-day on 6/12 and 6/13; the end day is not included",
+            ),
+            ("ooo / focus Title", "out of office / focus time"),
+            ("Title meet", "attach a Meet link"),
+            ("Title private", "encrypted, never sent to Google"),
+            ("6/12 Task", "task due on 6/12"),
+            ("Task notes: text", "task with notes"),
+            ("Task ~2h", "effort estimate"),
+            (
+                "Ctrl-A Ctrl-E Ctrl-U Ctrl-K",
+                "start, end, cut before / after",
+            ),
+        ],
+    ),
+];
+
+// Lines the overlay renders: a title and a gap per section
+pub fn line_count() -> u16 {
+    SECTIONS
+        .iter()
+        .map(|(_, entries)| entries.len() + 2)
+        .sum::<usize>() as u16
+}
@@ -0,0 +1,505 @@
+use chrono::Datelike;
+
+#[derive(Clone, Copy)]
+enum Lang {
+    En,
+    De,
+    Fr,
+    Es,
+    Zh,
+}
+
+pub struct Locale {
+    lang: Lang,
+}
+
+const WEEKDAYS_SHORT: [[&str; 7]; 5] = [
+    ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+    ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+    ["dim", "lun", "mar", "mer", "jeu", "ven", "sam"],
+    ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    ["日", "一", "二", "三", "四", "五", "六"],
+];
+
+const WEEKDAYS_LONG: [[&str; 7]; 5] = [
+    [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ],
+    [
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ],
+    [
+        "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+    ],
+    [
+        "domingo",
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+    ],
+    [
+        "星期日",
+        "星期一",
+        "星期二",
+        "星期三",
+        "星期四",
+        "星期五",
+        "星期六",
+    ],
+];
+
+const MONTHS: [[&str; 12]; 5] = [
+    [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    [
+        "一月",
+        "二月",
+        "三月",
+        "四月",
+        "五月",
+        "六月",
+        "七月",
+        "八月",
+        "九月",
+        "十月",
+        "十一月",
+        "十二月",
+    ],
+];
+
+// English text → translation; anything missing falls back to English
+const CATALOG: &[(&str, [&str; 4])] = &[
+    // de, fr, es, zh
+    ("Events", ["Termine", "Événements", "Eventos", "事件"]),
+    ("Tasks", ["Aufgaben", "Tâches", "Tareas", "任务"]),
+    (
+        "Calendars",
+        ["Kalender", "Calendriers", "Calendarios", "日历"],
+    ),
+    ("Sharing", ["Freigabe", "Partage", "Compartir", "共享"]),
+    ("Online", ["Online", "En ligne", "En línea", "在线"]),
+    ("Offline", ["Offline", "Hors ligne", "Sin conexión", "离线"]),
+    (
+        "Authenticating",
+        ["Anmeldung", "Authentification", "Autenticando", "认证中"],
+    ),
+    (
+        "Refreshing",
+        ["Aktualisiere", "Actualisation", "Actualizando", "刷新中"],
+    ),
+    ("Event", ["Termin", "Événement", "Evento", "事件"]),
+    ("Task", ["Aufgabe", "Tâche", "Tarea", "任务"]),
+    ("Where", ["Ort", "Lieu", "Dónde", "地点"]),
+    ("Name", ["Name", "Nom", "Nombre", "名称"]),
+    ("Share", ["Teilen", "Partager", "Compartir", "共享"]),
+    ("Meet", ["Treffen", "Réunion", "Reunión", "会议"]),
+    ("Run", ["Ausführen", "Exécuter", "Ejecutar", "运行"]),
+    ("Filter", ["Filter", "Filtre", "Filtro", "筛选"]),
+    (
+        "Passphrase",
+        [
+            "Passphrase",
+            "Phrase secrète",
+            "Frase de contraseña",
+            "口令",
+        ],
+    ),
+    ("Hourly", ["Stündlich", "Par heure", "Por hora", "逐小时"]),
+    (
+        "No hourly forecast this far ahead",
+        [
+            "Keine stündliche Vorhersage so weit voraus",
+            "Pas de prévision horaire aussi loin",
+            "No hay pronóstico por hora tan lejano",
+            "暂无这么远的逐小时预报",
+        ],
+    ),
+    ("as of", ["Stand", "à", "a las", "截至"]),
+    (
+        "Current Weather",
+        [
+            "Aktuelles Wetter",
+            "Météo actuelle",
+            "Tiempo actual",
+            "当前天气",
+        ],
+    ),
+    (
+        "Today's Forecast",
+        [
+            "Heutige Vorhersage",
+            "Prévisions du jour",
+            "Pronóstico de hoy",
+            "今日预报",
+        ],
+    ),
+    ("Weather", ["Wetter", "Météo", "Tiempo", "天气"]),
+    (
+        "Temperature",
+        ["Temperatur", "Température", "Temperatura", "温度"],
+    ),
+    ("Feels Like", ["Gefühlt", "Ressenti", "Sensación", "体感"]),
+    ("Humidity", ["Feuchte", "Humidité", "Humedad", "湿度"]),
+    (
+        "Precipitation",
+        ["Niederschlag", "Précipitations", "Precipitación", "降水"],
+    ),
+    ("Cloud cover", ["Bewölkung", "Nuages", "Nubosidad", "云量"]),
+    ("Wind", ["Wind", "Vent", "Viento", "风速"]),
+    ("Pressure", ["Luftdruck", "Pression", "Presión", "气压"]),
+    ("UV Index", ["UV-Index", "Indice UV", "Índice UV", "紫外线"]),
+    (
+        "Air quality",
+        [
+            "Luftqualität",
+            "Qualité de l'air",
+            "Calidad del aire",
+            "空气质量",
+        ],
+    ),
+    ("Low", ["Tief", "Min", "Mín", "最低"]),
+    ("High", ["Hoch", "Max", "Máx", "最高"]),
+    (
+        "Chance of rain",
+        ["Regenrisiko", "Risque de pluie", "Prob. lluvia", "降雨概率"],
+    ),
+    (
+        "Event created!",
+        [
+            "Termin erstellt!",
+            "Événement créé !",
+            "¡Evento creado!",
+            "事件已创建！",
+        ],
+    ),
+    (
+        "Task created!",
+        [
+            "Aufgabe erstellt!",
+            "Tâche créée !",
+            "¡Tarea creada!",
+            "任务已创建！",
+        ],
+    ),
+    (
+        "Task updated!",
+        [
+            "Aufgabe geändert!",
+            "Tâche modifiée !",
+            "¡Tarea actualizada!",
+            "任务已更新！",
+        ],
+    ),
+    (
+        "Task deleted!",
+        [
+            "Aufgabe gelöscht!",
+            "Tâche supprimée !",
+            "¡Tarea eliminada!",
+            "任务已删除！",
+        ],
+    ),
+    (
+        "Event Deleted!",
+        [
+            "Termin gelöscht!",
+            "Événement supprimé !",
+            "¡Evento eliminado!",
+            "事件已删除！",
+        ],
+    ),
+    (
+        "Completed",
+        ["Erledigt", "Terminée", "Completada", "已完成"],
+    ),
+    ("Cleared", ["Geleert", "Nettoyé", "Limpiado", "已清除"]),
+    ("Copied!", ["Kopiert!", "Copié !", "¡Copiado!", "已复制！"]),
+    ("Conflict", ["Konflikt", "Conflit", "Conflicto", "冲突"]),
+    ("Mine", ["Meine", "Local", "Local", "本地"]),
+    ("Theirs", ["Server", "Serveur", "Servidor", "服务器"]),
+    ("Trash", ["Papierkorb", "Corbeille", "Papelera", "回收站"]),
+    ("Starred", ["Markiert", "Favoris", "Destacados", "已加星标"]),
+    ("today", ["heute", "aujourd'hui", "hoy", "今天"]),
+    ("Week", ["Woche", "Semaine", "Semana", "周"]),
+    ("Plan", ["Plan", "Plan", "Plan", "计划"]),
+    (
+        "Weekly review",
+        [
+            "Wochenrückblick",
+            "Revue hebdomadaire",
+            "Revisión semanal",
+            "每周回顾",
+        ],
+    ),
+    (
+        "Overdue tasks",
+        [
+            "Überfällige Aufgaben",
+            "Tâches en retard",
+            "Tareas vencidas",
+            "逾期任务",
+        ],
+    ),
+    (
+        "Next week",
+        [
+            "Nächste Woche",
+            "Semaine prochaine",
+            "Próxima semana",
+            "下周",
+        ],
+    ),
+    (
+        "Unscheduled tasks",
+        [
+            "Aufgaben ohne Termin",
+            "Tâches sans date",
+            "Tareas sin fecha",
+            "未安排的任务",
+        ],
+    ),
+    ("keep", ["behalten", "garder", "mantener", "保留"]),
+    ("tomorrow", ["morgen", "demain", "mañana", "明天"]),
+    (
+        "next Monday",
+        [
+            "nächster Montag",
+            "lundi prochain",
+            "próximo lunes",
+            "下周一",
+        ],
+    ),
+    ("done", ["erledigt", "terminé", "hecho", "完成"]),
+    (
+        "Status history",
+        [
+            "Statusverlauf",
+            "Historique des statuts",
+            "Historial de estados",
+            "状态历史",
+        ],
+    ),
+    ("All day", ["Ganztägig", "Journée", "Todo el día", "全天"]),
+    ("Day", ["Tag", "Jour", "Día", "日"]),
+    ("Agenda", ["Agenda", "Agenda", "Agenda", "日程"]),
+    ("days", ["Tage", "jours", "días", "天"]),
+    ("Memos", ["Notizen", "Mémos", "Notas", "备忘"]),
+    ("Memo", ["Notiz", "Mémo", "Nota", "备忘"]),
+    ("When", ["Wann", "Quand", "Cuándo", "时间"]),
+    (
+        "Repeats",
+        ["Wiederholung", "Répétition", "Repetición", "重复"],
+    ),
+    ("Location", ["Ort", "Lieu", "Lugar", "地点"]),
+    (
+        "Organizer",
+        ["Organisator", "Organisateur", "Organizador", "组织者"],
+    ),
+    (
+        "Attendee",
+        ["Teilnehmer", "Participant", "Asistente", "参与者"],
+    ),
+    ("Calendar", ["Kalender", "Calendrier", "Calendario", "日历"]),
+    ("Private", ["Privat", "Privé", "Privado", "私人"]),
+    ("Help", ["Hilfe", "Aide", "Ayuda", "帮助"]),
+    ("more", ["weitere", "de plus", "más", "更多"]),
+    (
+        "What's new in",
+        ["Neu in", "Nouveautés de", "Novedades de", "新功能"],
+    ),
+    (
+        "Audit log",
+        ["Protokoll", "Journal d'audit", "Registro", "审计日志"],
+    ),
+    (
+        "Meetings",
+        ["Besprechungen", "Réunions", "Reuniones", "会议"],
+    ),
+    (
+        "Meeting hours",
+        [
+            "Besprechungsstunden",
+            "Heures de réunion",
+            "Horas de reunión",
+            "会议时长",
+        ],
+    ),
+    ("Per day", ["Pro Tag", "Par jour", "Por día", "每天"]),
+    (
+        "Back-to-back streaks",
+        [
+            "Direkt aufeinander",
+            "Enchaînements",
+            "Seguidas",
+            "连续会议",
+        ],
+    ),
+    (
+        "Longest streak",
+        [
+            "Längste Folge",
+            "Plus long enchaînement",
+            "Racha más larga",
+            "最长连续",
+        ],
+    ),
+    (
+        "Busiest day",
+        [
+            "Vollster Tag",
+            "Jour le plus chargé",
+            "Día más ocupado",
+            "最忙的一天",
+        ],
+    ),
+    ("Search", ["Suche", "Recherche", "Buscar", "搜索"]),
+    (
+        "Invitations",
+        ["Einladungen", "Invitations", "Invitaciones", "邀请"],
+    ),
+    ("results", ["Treffer", "résultats", "resultados", "条结果"]),
+    (
+        "Most frequent attendees",
+        [
+            "Häufigste Teilnehmer",
+            "Participants fréquents",
+            "Asistentes frecuentes",
+            "最常见的参与者",
+        ],
+    ),
+    ("Messages", ["Meldungen", "Messages", "Mensajes", "消息"]),
+    ("Alarm", ["Alarm", "Alarme", "Alarma", "闹钟"]),
+    ("Starts", ["Beginnt", "Commence", "Empieza", "开始于"]),
+    (
+        "Birthdays",
+        ["Geburtstage", "Anniversaires", "Cumpleaños", "生日"],
+    ),
+    (
+        "Reminders",
+        ["Erinnerungen", "Rappels", "Recordatorios", "提醒"],
+    ),
+    (
+        "Free/busy",
+        ["Frei/belegt", "Libre/occupé", "Libre/ocupado", "空闲/忙碌"],
+    ),
+    ("free", ["frei", "libre", "libre", "空闲"]),
+];
+
+impl Locale {
+    pub fn from_code(code: &str) -> Locale {
+        let lang = match code.split(['_', '-']).next().unwrap_or("") {
+            "de" => Lang::De,
+            "fr" => Lang::Fr,
+            "es" => Lang::Es,
+            "zh" => Lang::Zh,
+            _ => Lang::En,
+        };
+        Locale { lang }
+    }
+
+    fn index(&self) -> usize {
+        self.lang as usize
+    }
+
+    // Weekday header names, Sunday first
+    pub fn weekdays(&self) -> [&'static str; 7] {
+        WEEKDAYS_SHORT[self.index()]
+    }
+
+    // Looks up a UI string, returning it unchanged when there is no translation
+    pub fn t<'a>(&self, text: &'a str) -> &'a str {
+        if let Lang::En = self.lang {
+            return text;
+        }
+        CATALOG
+            .iter()
+            .find(|(english, _)| *english == text)
+            .map(|(_, translations)| translations[self.index() - 1])
+            .unwrap_or(text)
+    }
+
+    // Swaps the name specifiers for localized names so chrono only formats numbers
+    pub fn localize_format(&self, format: &str, date: &impl Datelike) -> String {
+        if let Lang::En = self.lang {
+            return format.to_string();
+        }
+        let weekday = date.weekday().num_days_from_sunday() as usize;
+        let month = date.month0() as usize;
+        let long_month = MONTHS[self.index()][month];
+        let short_month: String = long_month.chars().take(3).collect();
+        format
+            .replace("%A", WEEKDAYS_LONG[self.index()][weekday])
+            .replace("%a", WEEKDAYS_SHORT[self.index()][weekday])
+            .replace("%B", long_month)
+            .replace("%b", &short_month)
+    }
+}
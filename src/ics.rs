@@ -0,0 +1,287 @@
+use crate::config::FeedConfig;
+use crate::merge::{self, EventsCache};
+use crate::{error, net};
+use chrono::{
+    DateTime, Datelike, Days, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, TimeZone,
+    Utc, Weekday,
+};
+use google_calendar3::api;
+
+// Calendar ids of subscribed feeds carry this prefix so edits can be refused
+pub const FEED_PREFIX: &str = "ics:";
+const PAST_DAYS: u64 = 90;
+const FUTURE_DAYS: u64 = 366;
+const MAX_OCCURRENCES: usize = 1000;
+pub const FEED_REFRESH: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+// Feed calendar id with its events, or why fetching it failed
+pub type FeedResults = Vec<(String, Result<EventsCache, String>)>;
+
+pub fn is_feed(calendar_id: &str) -> bool {
+    calendar_id.starts_with(FEED_PREFIX)
+}
+
+pub fn feed_id(feed: &FeedConfig) -> String {
+    format!("{FEED_PREFIX}{}", feed.name)
+}
+
+#[derive(Clone, Copy)]
+enum When {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl When {
+    fn date(&self, app_tz: FixedOffset) -> NaiveDate {
+        match self {
+            When::Date(d) => *d,
+            When::DateTime(dt) => dt.with_timezone(&app_tz).date_naive(),
+        }
+    }
+
+    fn shift(&self, by: Duration) -> When {
+        match self {
+            When::Date(d) => When::Date(*d + by),
+            When::DateTime(dt) => When::DateTime(*dt + by),
+        }
+    }
+
+    // RECURRENCE-ID style, so an occurrence keeps its id however the window moves
+    fn recurrence_id(&self) -> String {
+        match self {
+            When::Date(d) => d.format("%Y%m%d").to_string(),
+            When::DateTime(dt) => dt.format("%Y%m%dT%H%M%SZ").to_string(),
+        }
+    }
+
+    fn to_api(self) -> api::EventDateTime {
+        match self {
+            When::Date(d) => api::EventDateTime {
+                date: Some(d),
+                ..Default::default()
+            },
+            When::DateTime(dt) => api::EventDateTime {
+                date_time: Some(dt),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct VEvent {
+    uid: String,
+    summary: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    start: Option<When>,
+    end: Option<When>,
+    rrule: Option<String>,
+    exdates: Vec<NaiveDate>,
+}
+
+// Lines starting with whitespace continue the previous one (RFC 5545 §3.1)
+fn unfold(data: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in data.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+// TZID times are read in the app's timezone; without a tz database that's the best guess
+fn parse_when(params: &str, value: &str, app_tz: FixedOffset) -> Option<When> {
+    if (params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME")) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(When::Date);
+    }
+    let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()?;
+    if value.ends_with('Z') {
+        Some(When::DateTime(naive.and_utc()))
+    } else {
+        app_tz
+            .from_local_datetime(&naive)
+            .single()
+            .map(|dt| When::DateTime(dt.with_timezone(&Utc)))
+    }
+}
+
+fn parse(data: &str, app_tz: FixedOffset) -> Vec<VEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<VEvent> = None;
+    for line in unfold(data) {
+        let Some((name_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name_params.split_once(';').unwrap_or((name_params, ""));
+        match (name, current.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => current = Some(VEvent::default()),
+            ("END", Some(_)) if value == "VEVENT" => events.extend(current.take()),
+            ("UID", Some(ev)) => ev.uid = value.to_string(),
+            ("SUMMARY", Some(ev)) => ev.summary = Some(unescape(value)),
+            ("LOCATION", Some(ev)) => ev.location = Some(unescape(value)),
+            ("DESCRIPTION", Some(ev)) => ev.description = Some(unescape(value)),
+            ("DTSTART", Some(ev)) => ev.start = parse_when(params, value, app_tz),
+            ("DTEND", Some(ev)) => ev.end = parse_when(params, value, app_tz),
+            ("RRULE", Some(ev)) => ev.rrule = Some(value.to_string()),
+            ("EXDATE", Some(ev)) => ev.exdates.extend(
+                value
+                    .split(',')
+                    .filter_map(|v| parse_when(params, v, app_tz))
+                    .map(|w| w.date(app_tz)),
+            ),
+            _ => {}
+        }
+    }
+    events
+}
+
+fn weekday(code: &str) -> Option<Weekday> {
+    // Ordinal prefixes like "1MO" aren't supported; those days are dropped
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// Start offsets from DTSTART for each occurrence in the window.
+// Handles FREQ, INTERVAL, COUNT, UNTIL and plain weekly BYDAY.
+fn occurrences(
+    start: When,
+    rrule: Option<&str>,
+    app_tz: FixedOffset,
+    window: (NaiveDate, NaiveDate),
+) -> Vec<Duration> {
+    let rule = |key: &str| {
+        rrule?
+            .split(';')
+            .find_map(|part| part.strip_prefix(key)?.strip_prefix('='))
+    };
+    let Some(freq) = rule("FREQ") else {
+        return vec![Duration::zero()];
+    };
+    let interval = rule("INTERVAL")
+        .and_then(|i| i.parse::<u32>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let count = rule("COUNT").and_then(|c| c.parse::<usize>().ok());
+    let until = rule("UNTIL")
+        .and_then(|u| parse_when("", u.get(..8).unwrap_or(u), app_tz))
+        .map(|w| w.date(app_tz));
+    let by_day: Vec<Weekday> = rule("BYDAY")
+        .map(|days| days.split(',').filter_map(weekday).collect())
+        .unwrap_or_default();
+
+    let first = start.date(app_tz);
+    let step = |n: u32| -> Option<NaiveDate> {
+        if n == 0 {
+            return Some(first);
+        }
+        match freq {
+            "DAILY" => first.checked_add_days(Days::new((n * interval) as u64)),
+            "WEEKLY" => first.checked_add_days(Days::new((n * interval * 7) as u64)),
+            "MONTHLY" => first.checked_add_months(Months::new(n * interval)),
+            "YEARLY" => first.checked_add_months(Months::new(n * interval * 12)),
+            _ => None,
+        }
+    };
+
+    let mut offsets = Vec::new();
+    let mut seen = 0;
+    for n in 0.. {
+        let Some(base) = step(n) else {
+            break;
+        };
+        // Weekly BYDAY expands each week into the listed days, starting from DTSTART's week
+        let days: Vec<NaiveDate> = if freq == "WEEKLY" && !by_day.is_empty() {
+            let monday = base - Days::new(base.weekday().num_days_from_monday() as u64);
+            let mut days: Vec<NaiveDate> = by_day
+                .iter()
+                .map(|d| monday + Days::new(d.num_days_from_monday() as u64))
+                .filter(|d| *d >= first)
+                .collect();
+            days.sort();
+            days
+        } else {
+            vec![base]
+        };
+        for day in days {
+            if until.is_some_and(|u| day > u) || count.is_some_and(|c| seen >= c) {
+                return offsets;
+            }
+            seen += 1;
+            if day >= window.0 && day <= window.1 {
+                offsets.push(Duration::days((day - first).num_days()));
+            }
+        }
+        if base > window.1 || offsets.len() >= MAX_OCCURRENCES {
+            break;
+        }
+    }
+    offsets
+}
+
+pub async fn fetch_feed(
+    feed: &FeedConfig,
+    app_tz: FixedOffset,
+    timeout: std::time::Duration,
+) -> error::Result<EventsCache> {
+    let url = match feed.url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{rest}"),
+        None => feed.url.clone(),
+    };
+    let client = net::http_client().timeout(timeout).build()?;
+    let data = client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let today = chrono::Local::now().date_naive();
+    let window = (today - Days::new(PAST_DAYS), today + Days::new(FUTURE_DAYS));
+    let calendar_id = feed_id(feed);
+    let mut cache = EventsCache::new();
+    for vevent in parse(&data, app_tz) {
+        let Some(start) = vevent.start else {
+            continue;
+        };
+        let end = vevent.end.unwrap_or(start);
+        for offset in occurrences(start, vevent.rrule.as_deref(), app_tz, window) {
+            let start = start.shift(offset);
+            if vevent.exdates.contains(&start.date(app_tz)) {
+                continue;
+            }
+            let event = api::Event {
+                id: Some(format!("{}-{}", vevent.uid, start.recurrence_id())),
+                summary: vevent.summary.clone(),
+                location: vevent.location.clone(),
+                description: vevent.description.clone(),
+                start: Some(start.to_api()),
+                end: Some(end.shift(offset).to_api()),
+                ..Default::default()
+            };
+            merge::insert(&mut cache, event, calendar_id.clone(), app_tz);
+        }
+    }
+    Ok(cache)
+}
@@ -0,0 +1,332 @@
+use crate::config::{ImapConfig, NetworkConfig};
+use crate::platform;
+use crate::{StatusColor, audit, net, tasks_auth};
+use google_tasks1::{TasksHub, api::Task};
+use rustls::pki_types::ServerName;
+use std::collections::HashSet;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+
+const SEEN_FILE: &str = "imap_seen.json";
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+struct FlaggedMail {
+    key: String, // "<uidvalidity>:<uid>", stable across polls
+    subject: String,
+    message_id: String,
+}
+
+struct Session {
+    stream: BufReader<TlsStream<TcpStream>>,
+    tag: u32,
+}
+
+// Each network step gets `net::timeout()`, so a stalled server fails this poll instead of
+// hanging the watcher
+async fn timed<T, E: Into<BoxError>>(
+    step: impl Future<Output = Result<T, E>>,
+) -> Result<T, BoxError> {
+    let limit = net::timeout();
+    match tokio::time::timeout(limit, step).await {
+        Ok(result) => result.map_err(Into::into),
+        Err(_) => Err(format!("IMAP timed out after {}s", limit.as_secs()).into()),
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Session {
+    // Straight to the server: [network] supplies the CA bundle, but the proxy is HTTP-only
+    async fn connect(config: &ImapConfig, network: &NetworkConfig) -> Result<Session, BoxError> {
+        let tls = TlsConnector::from(Arc::new(net::tls_config(network)?));
+        let tcp = timed(TcpStream::connect((config.host.as_str(), config.port))).await?;
+        let name = ServerName::try_from(config.host.clone())?;
+        let mut session = Session {
+            stream: BufReader::new(timed(tls.connect(name, tcp)).await?),
+            tag: 0,
+        };
+        session.read_line().await?; // greeting
+        Ok(session)
+    }
+
+    async fn read_line(&mut self) -> Result<String, BoxError> {
+        let mut line = String::new();
+        if timed(self.stream.read_line(&mut line)).await? == 0 {
+            return Err("IMAP connection closed".into());
+        }
+        Ok(line)
+    }
+
+    // Untagged responses up to the tagged status, with any {n} literal appended inline
+    async fn command(&mut self, command: &str) -> Result<Vec<String>, BoxError> {
+        self.tag += 1;
+        let tag = format!("a{}", self.tag);
+        let stream = self.stream.get_mut();
+        timed(stream.write_all(format!("{tag} {command}\r\n").as_bytes())).await?;
+        timed(stream.flush()).await?;
+
+        let mut responses = Vec::new();
+        loop {
+            let mut line = self.read_line().await?;
+            if let Some(rest) = line.strip_prefix(&format!("{tag} ")) {
+                if rest.starts_with("OK") {
+                    return Ok(responses);
+                }
+                return Err(format!("IMAP {}", rest.trim()).into());
+            }
+            if let Some(len) = line
+                .trim_end()
+                .strip_suffix('}')
+                .and_then(|l| l.rsplit_once('{'))
+                .and_then(|(_, n)| n.parse::<usize>().ok())
+            {
+                let mut literal = vec![0; len];
+                timed(self.stream.read_exact(&mut literal)).await?;
+                line.push_str(&String::from_utf8_lossy(&literal));
+                line.push_str(&self.read_line().await?);
+            }
+            responses.push(line);
+        }
+    }
+}
+
+fn base64_decode(text: &str) -> Vec<u8> {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes() {
+        let Some(value) = TABLE.iter().position(|b| *b == byte) else {
+            continue;
+        };
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    out
+}
+
+fn decode_word(encoding: &str, text: &str) -> Vec<u8> {
+    if encoding.eq_ignore_ascii_case("B") {
+        return base64_decode(text);
+    }
+    let mut bytes = Vec::new();
+    let mut chars = text.bytes();
+    while let Some(b) = chars.next() {
+        match b {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex: Vec<u8> = chars.by_ref().take(2).collect();
+                if let Ok(b) = u8::from_str_radix(&String::from_utf8_lossy(&hex), 16) {
+                    bytes.push(b);
+                }
+            }
+            b => bytes.push(b),
+        }
+    }
+    bytes
+}
+
+// RFC 2047 encoded words (=?charset?B|Q?text?=), all read as UTF-8
+fn decode_header(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let mut parts = rest[start + 2..].splitn(3, '?');
+        let (Some(_charset), Some(encoding), Some(tail)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            break;
+        };
+        let Some(end) = tail.find("?=") else {
+            break;
+        };
+        // Whitespace between adjacent encoded words is dropped
+        let before = &rest[..start];
+        if !(after_word && before.trim().is_empty()) {
+            out.push_str(before);
+        }
+        out.push_str(&String::from_utf8_lossy(&decode_word(
+            encoding,
+            &tail[..end],
+        )));
+        after_word = true;
+        rest = &tail[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// Header value from a fetched block, joining folded continuation lines
+fn header(block: &str, name: &str) -> Option<String> {
+    let mut value: Option<String> = None;
+    for line in block.split("\r\n") {
+        match &mut value {
+            Some(v) if line.starts_with([' ', '\t']) => v.push_str(line),
+            Some(_) => break,
+            None => {
+                if let Some((key, v)) = line.split_once(':')
+                    && key.eq_ignore_ascii_case(name)
+                {
+                    value = Some(v.trim().to_string());
+                }
+            }
+        }
+    }
+    value
+}
+
+async fn fetch_flagged(
+    config: &ImapConfig,
+    network: &NetworkConfig,
+    seen: &HashSet<String>,
+) -> Result<Vec<FlaggedMail>, BoxError> {
+    let mut session = Session::connect(config, network).await?;
+    session
+        .command(&format!(
+            "LOGIN {} {}",
+            quote(&config.username),
+            quote(&config.password)
+        ))
+        .await?;
+    let selected = session
+        .command(&format!("SELECT {}", quote(&config.folder)))
+        .await?;
+    let validity = selected
+        .iter()
+        .find_map(|l| l.split("[UIDVALIDITY ").nth(1)?.split(']').next())
+        .unwrap_or("0")
+        .to_string();
+
+    let uids: Vec<String> = session
+        .command("UID SEARCH FLAGGED")
+        .await?
+        .iter()
+        .filter_map(|l| l.strip_prefix("* SEARCH"))
+        .flat_map(|l| l.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .filter(|uid| !seen.contains(&format!("{validity}:{uid}")))
+        .collect();
+
+    let mut mails = Vec::new();
+    if !uids.is_empty() {
+        let fetched = session
+            .command(&format!(
+                "UID FETCH {} (UID BODY.PEEK[HEADER.FIELDS (SUBJECT MESSAGE-ID)])",
+                uids.join(",")
+            ))
+            .await?;
+        for response in fetched {
+            let Some(uid) = response
+                .split("UID ")
+                .nth(1)
+                .and_then(|r| r.split([' ', ')']).next())
+            else {
+                continue;
+            };
+            mails.push(FlaggedMail {
+                key: format!("{validity}:{uid}"),
+                subject: header(&response, "Subject")
+                    .map(|s| decode_header(&s))
+                    .unwrap_or("(no subject)".to_string()),
+                message_id: header(&response, "Message-ID").unwrap_or_default(),
+            });
+        }
+    }
+    let _ = session.command("LOGOUT").await;
+    Ok(mails)
+}
+
+fn load_seen() -> HashSet<String> {
+    let path = platform::cache_dir().join(SEEN_FILE);
+    match read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+fn save_seen(seen: &HashSet<String>) {
+    let path = platform::cache_dir().join(SEEN_FILE);
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(seen) {
+        let _ = write(path, json);
+    }
+}
+
+// Gmail can search by Message-ID; other clients understand message: URLs
+fn mail_link(host: &str, message_id: &str) -> String {
+    let id = message_id.trim_matches(['<', '>']);
+    if host.contains("gmail") {
+        format!("https://mail.google.com/mail/u/0/#search/rfc822msgid:{id}")
+    } else {
+        format!("message:%3C{id}%3E")
+    }
+}
+
+// Polls the folder and turns each newly flagged mail into a task on the default list.
+// A mail is only marked as handled once its task exists.
+pub async fn watch(
+    config: ImapConfig,
+    network: NetworkConfig,
+    hub: TasksHub<net::Connector>,
+    tx: tokio::sync::mpsc::Sender<(String, StatusColor)>,
+) {
+    let mut seen = load_seen();
+    loop {
+        match fetch_flagged(&config, &network, &seen).await {
+            Ok(mails) => {
+                let mut created = 0;
+                for mail in mails {
+                    let task = Task {
+                        title: Some(mail.subject),
+                        notes: Some(mail_link(&config.host, &mail.message_id)),
+                        ..Task::default()
+                    };
+                    let sent = audit::payload(&task);
+                    let result = hub
+                        .tasks()
+                        .insert(task, "@default")
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
+                        .doit()
+                        .await;
+                    audit::record(
+                        "create_task_from_mail",
+                        "@default",
+                        None,
+                        sent,
+                        &result.as_ref().map(|_| ()),
+                    );
+                    if result.is_ok() {
+                        seen.insert(mail.key);
+                        created += 1;
+                    }
+                }
+                if created > 0 {
+                    save_seen(&seen);
+                    let msg = format!("{created} flagged mails added as tasks");
+                    let _ = tx.send((msg, StatusColor::Green)).await;
+                }
+            }
+            Err(e) => {
+                let _ = tx
+                    .send((format!("IMAP failed: {e}"), StatusColor::Red))
+                    .await;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(config.poll_mins.max(1) * 60)).await;
+    }
+}
@@ -0,0 +1,166 @@
+use crate::config::{GithubConfig, IssuesConfig, JiraConfig};
+use crate::{error, net};
+use google_tasks1::api::Task;
+use serde::Deserialize;
+use std::time::Duration;
+
+// Tasklist ids of issue trackers carry this prefix so edits can be refused
+pub const SOURCE_PREFIX: &str = "issues:";
+pub const ISSUES_REFRESH: Duration = Duration::from_secs(30 * 60);
+
+// Source tasklist id with its tasks, or why fetching it failed
+pub type IssueResults = Vec<(String, Result<Vec<(Task, String)>, String>)>;
+
+pub fn is_issue(tasklist_id: &str) -> bool {
+    tasklist_id.starts_with(SOURCE_PREFIX)
+}
+
+fn github_id() -> String {
+    format!("{SOURCE_PREFIX}github")
+}
+
+fn jira_id() -> String {
+    format!("{SOURCE_PREFIX}jira")
+}
+
+pub fn badge(tasklist_id: &str) -> &'static str {
+    match tasklist_id.strip_prefix(SOURCE_PREFIX) {
+        Some("github") => "[GH] ",
+        Some("jira") => "[JIRA] ",
+        _ => "",
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    milestone: Option<GithubMilestone>,
+    repository: Option<GithubRepository>,
+}
+
+#[derive(Deserialize)]
+struct GithubMilestone {
+    due_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubRepository {
+    full_name: String,
+}
+
+#[derive(Deserialize)]
+struct JiraSearch {
+    issues: Vec<JiraIssue>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+struct JiraFields {
+    summary: String,
+    duedate: Option<String>, // YYYY-MM-DD
+}
+
+fn issue_task(id: String, title: String, due: String, url: String) -> Task {
+    Task {
+        id: Some(id),
+        title: Some(title),
+        due: Some(due),
+        status: Some("needsAction".to_string()),
+        web_view_link: Some(url),
+        ..Default::default()
+    }
+}
+
+// Open issues assigned to the token's user that sit in a milestone with a due date
+async fn fetch_github(
+    client: &reqwest::Client,
+    github: &GithubConfig,
+) -> error::Result<Vec<(Task, String)>> {
+    let issues: Vec<GithubIssue> = client
+        .get("https://api.github.com/issues?filter=assigned&state=open&per_page=100")
+        .bearer_auth(&github.token)
+        .header("User-Agent", "calpersonal")
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(issues
+        .into_iter()
+        .filter_map(|issue| {
+            let due = issue.milestone?.due_on?;
+            let repo = issue.repository.map(|r| r.full_name).unwrap_or_default();
+            Some((
+                issue_task(
+                    format!("{repo}#{}", issue.number),
+                    issue.title,
+                    due,
+                    issue.html_url,
+                ),
+                github_id(),
+            ))
+        })
+        .collect())
+}
+
+async fn fetch_jira(
+    client: &reqwest::Client,
+    jira: &JiraConfig,
+) -> error::Result<Vec<(Task, String)>> {
+    let base = jira.url.trim_end_matches('/');
+    let search: JiraSearch = client
+        .get(format!("{base}/rest/api/2/search"))
+        .query(&[("jql", jira.jql.as_str()), ("fields", "summary,duedate")])
+        .basic_auth(&jira.email, Some(&jira.token))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(search
+        .issues
+        .into_iter()
+        .filter_map(|issue| {
+            let due = format!("{}T00:00:00.000Z", issue.fields.duedate?);
+            Some((
+                issue_task(
+                    issue.key.clone(),
+                    format!("{} {}", issue.key, issue.fields.summary),
+                    due,
+                    format!("{base}/browse/{}", issue.key),
+                ),
+                jira_id(),
+            ))
+        })
+        .collect())
+}
+
+// Each configured source on its own so one failing doesn't hide the other
+pub async fn fetch(config: &IssuesConfig, timeout: Duration) -> IssueResults {
+    let mut results = Vec::new();
+    let client = match net::http_client().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => return vec![(github_id(), Err(e.to_string()))],
+    };
+    if let Some(github) = &config.github {
+        let result = fetch_github(&client, github)
+            .await
+            .map_err(|e| format!("GitHub issues failed: {e}"));
+        results.push((github_id(), result));
+    }
+    if let Some(jira) = &config.jira {
+        let result = fetch_jira(&client, jira)
+            .await
+            .map_err(|e| format!("Jira issues failed: {e}"));
+        results.push((jira_id(), result));
+    }
+    results
+}
@@ -0,0 +1,47 @@
+// The parts of calpersonal that need no terminal: config, the event model, sync, the input
+// parsers and the `calpersonal mcp` tool server. The binary in main.rs builds the TUI on top;
+// benches use them directly.
+pub mod audit;
+pub mod birthdays;
+pub mod calendar_auth;
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod file_writing;
+pub mod ics;
+pub mod local_reminders;
+pub mod logging;
+pub mod mcp;
+pub mod merge;
+pub mod net;
+pub mod parse_input;
+pub mod platform;
+pub mod reminders;
+pub mod scheduling;
+pub mod secrets;
+pub mod slim;
+pub mod tasks_auth;
+pub mod theme;
+pub mod update;
+pub mod usage;
+
+use ratatui::style::Color;
+
+#[derive(Clone)]
+pub enum StatusColor {
+    Green,
+    Yellow,
+    Red,
+    White,
+}
+
+impl StatusColor {
+    pub fn color(&self, theme: &theme::Theme) -> Color {
+        match self {
+            StatusColor::Green => theme.ok,
+            StatusColor::Yellow => theme.warning,
+            StatusColor::Red => theme.error,
+            StatusColor::White => Color::Reset,
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::platform;
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+// Recurring reminders that live only on this machine, e.g. "take medication 9:00 daily";
+// nothing is written to Google
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LocalReminder {
+    pub text: String,
+    pub time: NaiveTime,
+    pub repeat: Repeat,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum Repeat {
+    Daily,
+    Weekdays,
+    Weekly(Weekday),
+    Monthly(u32), // day of the month; shorter months skip it
+}
+
+impl Repeat {
+    pub fn on(self, date: NaiveDate) -> bool {
+        match self {
+            Repeat::Daily => true,
+            Repeat::Weekdays => !matches!(date.weekday(), Weekday::Sat | Weekday::Sun),
+            Repeat::Weekly(day) => date.weekday() == day,
+            Repeat::Monthly(day) => date.day() == day,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Repeat::Daily => "daily".to_string(),
+            Repeat::Weekdays => "weekdays".to_string(),
+            Repeat::Weekly(day) => format!("every {day}"),
+            Repeat::Monthly(day) => format!("monthly on the {day}"),
+        }
+    }
+}
+
+fn path() -> PathBuf {
+    platform::data_dir().join("reminders.json")
+}
+
+pub fn load() -> Vec<LocalReminder> {
+    match read_to_string(path()) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn save(reminders: &[LocalReminder]) {
+    let path = path();
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(reminders) {
+        let _ = write(path, json);
+    }
+}
+
+fn time(token: &str) -> Option<NaiveTime> {
+    let upper = token.to_uppercase();
+    // chrono wants the minutes, so 7pm is read as 7:00PM
+    let twelve_hour = match upper.strip_suffix("AM").or(upper.strip_suffix("PM")) {
+        Some(hour) if !hour.contains(':') => format!("{hour}:00{}", &upper[hour.len()..]),
+        _ => upper.clone(),
+    };
+    NaiveTime::parse_from_str(token, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&twelve_hour, "%I:%M%p"))
+        .ok()
+}
+
+// A time (9:00, 18:30, 7pm) and a repeat anywhere among the words, the rest is the text:
+// "daily" / "every day", "weekdays" / "every weekday", "every mon[day]", "monthly 15"
+pub fn parse(input: &str) -> Option<LocalReminder> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut used = vec![false; words.len()];
+    let mut at = None;
+    let mut repeat = None;
+    for i in 0..words.len() {
+        let word = words[i].to_lowercase();
+        let next = words.get(i + 1).map(|w| w.to_lowercase());
+        if at.is_none()
+            && let Some(t) = time(&word)
+        {
+            at = Some(t);
+            used[i] = true;
+            continue;
+        }
+        if repeat.is_some() {
+            continue;
+        }
+        let (found, width) = match (word.as_str(), next.as_deref()) {
+            ("daily", _) => (Some(Repeat::Daily), 1),
+            ("every", Some("day")) => (Some(Repeat::Daily), 2),
+            ("weekdays", _) => (Some(Repeat::Weekdays), 1),
+            ("every", Some("weekday")) => (Some(Repeat::Weekdays), 2),
+            ("every", Some(day)) => (day.parse::<Weekday>().ok().map(Repeat::Weekly), 2),
+            ("monthly", Some(day)) => (
+                day.trim_end_matches(|c: char| c.is_alphabetic())
+                    .parse()
+                    .ok()
+                    .filter(|d| (1..=31).contains(d))
+                    .map(Repeat::Monthly),
+                2,
+            ),
+            _ => (None, 0),
+        };
+        if let Some(found) = found {
+            repeat = Some(found);
+            for flag in used.iter_mut().skip(i).take(width) {
+                *flag = true;
+            }
+        }
+    }
+    let text: Vec<&str> = words
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|(word, _)| *word)
+        .collect();
+    if text.is_empty() {
+        return None;
+    }
+    Some(LocalReminder {
+        text: text.join(" "),
+        time: at?,
+        repeat: repeat?,
+    })
+}
@@ -0,0 +1,122 @@
+use crate::platform;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::create_dir_all;
+use std::io;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::time::ChronoLocal;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+// One file per day, e.g. ~/.cache/calpersonal/logs/calpersonal.2025-06-01.log
+const LOG_DIR: &str = "logs";
+const KEEP_FILES: usize = 7;
+const RECENT_LINES: usize = 200;
+const MESSAGE_LINES: usize = 200;
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+// The tail of the log for the --debug pane
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+// Warnings and errors, whatever the level, for the `!` message log
+static MESSAGES: Mutex<VecDeque<(DateTime<Local>, Level, String)>> = Mutex::new(VecDeque::new());
+
+pub fn recent() -> Vec<String> {
+    RECENT.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn messages() -> Vec<(DateTime<Local>, Level, String)> {
+    MESSAGES.lock().unwrap().iter().cloned().collect()
+}
+
+// The formatted lines, as written to the file, kept for the --debug pane
+struct Recent;
+
+impl io::Write for Recent {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut recent = RECENT.lock().unwrap();
+        for line in String::from_utf8_lossy(buf).lines() {
+            if recent.len() == RECENT_LINES {
+                recent.pop_front();
+            }
+            recent.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// `message` first, then the other fields as key=value
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            let _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+}
+
+struct Messages;
+
+impl<S: Subscriber> Layer<S> for Messages {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let level = *event.metadata().level();
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let mut messages = MESSAGES.lock().unwrap();
+        if messages.len() == MESSAGE_LINES {
+            messages.pop_front();
+        }
+        messages.push_back((Local::now(), level, fields.0));
+    }
+}
+
+// This crate's events only, other crates' (hyper, rustls) are left out. `log_requests`
+// adds net's request timings and retries whatever the level. Each layer filters on its own,
+// so the message log gets warnings even when the file is set to errors only.
+pub fn init(max_level: Level, log_requests: bool) {
+    let mut targets = Targets::new().with_target("calpersonal", max_level);
+    if log_requests {
+        targets = targets.with_target("calpersonal::net", max_level.max(Level::DEBUG));
+    }
+    // The appender prunes old files before it would create the directory
+    let dir = platform::cache_dir().join(LOG_DIR);
+    let _ = create_dir_all(&dir);
+    let file = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("calpersonal")
+        .filename_suffix("log")
+        .max_log_files(KEEP_FILES)
+        .build(dir)
+        .ok()
+        .map(|appender| {
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_timer(ChronoLocal::new(TIME_FORMAT.to_string()))
+                .with_writer(appender)
+                .with_filter(targets.clone())
+        });
+    let recent = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_timer(ChronoLocal::new(TIME_FORMAT.to_string()))
+        .with_writer(|| Recent)
+        .with_filter(targets);
+    let messages = Messages.with_filter(Targets::new().with_target("calpersonal", Level::WARN));
+    let _ = tracing_subscriber::registry()
+        .with(file)
+        .with(recent)
+        .with(messages)
+        .try_init();
+}
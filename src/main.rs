@@ -1,32 +1,77 @@
-mod calendar_auth;
-mod config;
-mod file_writing;
-mod parse_input;
-mod tasks_auth;
+mod bidi;
+mod browser;
+mod clipboard;
+mod day_index;
+mod dbus;
+mod deep_link;
+mod demo;
+mod help;
+mod i18n;
+mod imap;
+mod issues;
+mod memos;
+mod moon;
+mod notes;
+mod panels;
+mod report;
+mod review;
+mod scripting;
+mod streaks;
+mod sun;
+mod suspend;
+mod trash;
+mod vault;
+mod wakeup;
 mod weather;
-use chrono::{DateTime, Datelike, Days, FixedOffset, Local, Months, NaiveDate};
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+mod webhooks;
+mod widget;
+mod wttr;
+
+use calpersonal::{
+    StatusColor, audit, birthdays, calendar_auth, config, error, events, file_writing, ics,
+    local_reminders, logging, mcp, merge, net, parse_input, platform, reminders, scheduling,
+    secrets, slim, tasks_auth, theme, update, usage,
+};
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, Timelike, Utc,
+    Weekday,
+};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::StreamExt;
 use google_calendar3::{CalendarHub, api};
 use google_tasks1::{TasksHub, api::Task};
-use hyper_util::client::legacy::connect;
 use ratatui::{
     DefaultTerminal, Frame,
     buffer::Buffer,
     layout::Rect,
     layout::{Constraint, Direction, Layout},
     prelude::Stylize,
-    style::{Color, Modifier},
+    style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
 use rustls;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io;
-use weather::OneCallResponse;
+use tracing::Instrument;
 
 struct App {
-    config: Option<config::Config>,
+    config: config::Config,
+    locale: i18n::Locale,
+    // `--popup`: today's agenda filling a small pane, Esc quits
+    popup: bool,
+    event_details: bool, // Enter in the Events list; Esc closes it first
+    // Day's events fill the main area instead of the centred box
+    zoomed: bool,
+    event_filter: events::EventFilter,
+    starred: HashSet<String>, // event ids
+    note_dates: HashSet<NaiveDate>,
+    // Set by `N`; the run loop hands the terminal to $EDITOR for it
+    note_to_edit: Option<NaiveDate>,
+    // Unlocked for the session with `P`; private events only exist in memory while it is
+    vault: Option<vault::Vault>,
+    private_events: Vec<api::Event>,
     app_layout: MainArea,
     current_date: NaiveDate, // The date being displayed
     today: NaiveDate,        // Today's date for comparison
@@ -35,9 +80,12 @@ struct App {
     exit: bool,
 
     // Calendar stuff
-    event_hub: Option<CalendarHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>>, // The authenticated client
+    event_hub: Option<CalendarHub<net::Connector>>, // The authenticated client
     events_cache: HashMap<NaiveDate, Vec<(api::Event, String)>>, // date → events that day
-    task_hub: Option<TasksHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>>, // The authenticated client
+    day_index: day_index::DayIndex,
+    archive: merge::EventsCache, // days past the cache horizon, loaded for the compare view
+    calendars_cache: HashMap<String, api::CalendarListEntry>, // calendar id → calendar
+    task_hub: Option<TasksHub<net::Connector>>, // The authenticated client
     tasks_cache: Vec<(Task, String)>, // date → events that day
 
     change_feedback_tx: Option<tokio::sync::mpsc::Sender<(String, StatusColor)>>,
@@ -45,33 +93,105 @@ struct App {
     refreshing_status: (String, StatusColor),
     changing_status: (String, StatusColor),
 
-    weather_rx: Option<tokio::sync::mpsc::Receiver<OneCallResponse>>,
+    acl_rx: Option<tokio::sync::mpsc::Receiver<(String, Vec<api::AclRule>)>>, // calendar id, rules
+    acl_rules: Vec<api::AclRule>,
+
+    // Free slots, and the attendees whose calendars couldn't be checked
+    slots_rx: Option<tokio::sync::mpsc::Receiver<(Vec<scheduling::Slot>, Vec<String>)>>,
+    meeting_slots: Vec<scheduling::Slot>,
+    meeting_draft: Option<(Vec<String>, String)>, // attendees, title
+    plan_blocks: Vec<(String, scheduling::Slot)>, // proposed by :plan, booked on Enter
+    review: Option<review::Review>,
+
+    audit_entries: Vec<audit::AuditEntry>,
+
+    // Optimistic event edits waiting on the API, and the ones that collided with remote changes
+    pending_edits: Vec<merge::PendingEdit>,
+    next_edit_id: u64,
+    edit_ack_tx: tokio::sync::mpsc::Sender<u64>,
+    edit_ack_rx: tokio::sync::mpsc::Receiver<u64>,
+    conflicts: Vec<merge::Conflict>,
+    trash_items: Vec<trash::TrashItem>,
+    memos: Vec<memos::Memo>,
+    local_reminders: Vec<local_reminders::LocalReminder>, // :remind, never sent to Google
+    scripts: scripting::Scripts,
+    dbus: dbus::Service,
+    panels: Vec<Box<dyn panels::Panel>>,
+
+    // Subscribed iCal feeds: feed calendar id → its events, laid over events_cache
+    feed_events: HashMap<String, merge::EventsCache>,
+    feeds_rx: Option<tokio::sync::mpsc::Receiver<ics::FeedResults>>,
+    last_feed_fetch: Option<std::time::Instant>,
+    // Contact birthdays, laid over events_cache like the feeds
+    birthdays: Vec<birthdays::Birthday>,
+    birthdays_rx: Option<tokio::sync::mpsc::Receiver<Result<Vec<birthdays::Birthday>, String>>>,
+    last_birthday_fetch: Option<std::time::Instant>,
+
+    // GitHub/Jira issues, laid over tasks_cache the same way
+    issue_tasks: Vec<(Task, String)>,
+    issues_rx: Option<tokio::sync::mpsc::Receiver<issues::IssueResults>>,
+    last_issue_fetch: Option<std::time::Instant>,
+
+    weather_rx: Option<tokio::sync::mpsc::Receiver<weather::CachedWeather>>,
     onecall_weather: Option<weather::OneCallResponse>,
+    air_quality: Option<weather::AirQuality>,
+    weather_fetched_at: Option<DateTime<Local>>,
+    last_weather_fetch: Option<std::time::Instant>,
     weather_day: usize,
 
     inputting: bool,
     cursor_index: usize,
     input_buffer: String,
     updating_event_or_task: bool,
-
-    events_update_rx:
-        Option<tokio::sync::mpsc::Receiver<HashMap<NaiveDate, Vec<(api::Event, String)>>>>,
+    input_target: InputTarget,
+
+    // Screen-reader mode: what was last written out, so only changes are announced
+    last_announcement: String,
+    last_announced_status: String,
+    last_announced_input: String,
+
+    // Recent `changing_status` messages, newest last; `H` shows them
+    status_history: VecDeque<(DateTime<Local>, String, StatusColor)>,
+    show_status_history: bool,
+    message_log: Option<u16>, // `!` overlay, lines scrolled back from the newest; open when set
+    help_scroll: Option<u16>, // `?` overlay, open when set
+    cell_scroll: (NaiveDate, usize), // `[`/`]` in the month grid, for that day's cell only
+    demo: bool,               // --demo: sample data, no sign-in or network
+    range_start: Option<NaiveDate>, // `x` in the month grid; the range runs to current_date
+    report_period: report::Period, // :report covers the week or month around current_date
+    news_rx: Option<tokio::sync::mpsc::Receiver<update::News>>,
+    changelog: Option<(String, String, u16)>, // tag, release notes, scroll; shown while set
+    status_seen: String,
+    // Compare view: how many weeks back the right-hand week is
+    compare_weeks: u64,
+    pane_size: u16, // percent; starts at the saved size, else the configured one
+    theme: theme::Theme,
+    webhook_items: (webhooks::Items, webhooks::Items), // events and tasks as of the last sync
+    status_since: std::time::Instant,
+
+    events_update_rx: Option<
+        tokio::sync::mpsc::Receiver<(
+            HashMap<NaiveDate, Vec<(api::Event, String)>>,
+            HashMap<String, api::CalendarListEntry>,
+        )>,
+    >,
     tasks_update_rx: Option<tokio::sync::mpsc::Receiver<Vec<(Task, String)>>>,
     needs_refresh: bool,
+    last_refresh: std::time::Instant,
+    quota_warned: bool,
+    last_reminder_check: DateTime<FixedOffset>,
+    alarms: VecDeque<reminders::Alarm>, // ringing, the first one shown full screen
+    snoozed: Vec<(DateTime<FixedOffset>, reminders::Alarm)>, // ring again at
 
     auth_status: AuthStatus,
+    client_secret: Option<String>, // decrypted [secrets] client_secret, kept for re-auth
 
     // Channels to receive hubs when auth completes
-    calendar_hub_rx: Option<
-        tokio::sync::oneshot::Receiver<
-            Option<CalendarHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>>,
-        >,
-    >,
-    tasks_hub_rx: Option<
-        tokio::sync::oneshot::Receiver<
-            Option<TasksHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>>,
-        >,
-    >,
+    calendar_hub_rx: Option<tokio::sync::oneshot::Receiver<Option<CalendarHub<net::Connector>>>>,
+    tasks_hub_rx: Option<tokio::sync::oneshot::Receiver<Option<TasksHub<net::Connector>>>>,
+
+    // From a deep link; kept until the event shows up in a refresh
+    focus_event: Option<String>,
 }
 
 enum AuthStatus {
@@ -80,111 +200,572 @@ enum AuthStatus {
     Offline, // Failed or no internet
 }
 
-#[derive(Clone)]
-enum StatusColor {
-    Green,
-    Yellow,
-    Red,
-    White,
+const STATUS_HISTORY_LEN: usize = 100;
+// The free/busy bars run from 6:00 to 22:00
+const FREE_BUSY_HOURS: (u32, u32) = (6, 22);
+// Month cells narrower than this show a dot an event instead of clipped titles
+const DENSE_CELL_WIDTH: usize = 12;
+// Seven days three columns wide between borders; a title, weekday names and six weeks
+const MINI_MONTHS_WIDTH: u16 = 23;
+const MINI_MONTH_HEIGHT: u16 = 9;
+// How far back the month grid looks for all-day events still running into it
+const MAX_SPAN_DAYS: u64 = 62;
+
+// Per day, the multi-day events running through it and whether each goes on past it
+type Continuing<'a> = HashMap<NaiveDate, Vec<(&'a (api::Event, String), bool)>>;
+// Longest the loop sleeps with no input or results; reminders and status expiry run on it
+// Bounds and step for `{`/`}`
+const PANE_SIZE_MIN: u16 = 10;
+const PANE_SIZE_MAX: u16 = 70;
+const PANE_SIZE_STEP: u16 = 5;
+const IDLE_TICK: std::time::Duration = std::time::Duration::from_millis(250);
+// What the text in the input line is for
+#[derive(PartialEq)]
+enum InputTarget {
+    TaskOrEvent,
+    WorkingLocation,
+    NewCalendar,
+    ShareCalendar,
+    Meeting,
+    Command,
+    EventFilter,
+    Passphrase,
+    Memo,
+    Search,
 }
+
 enum MainArea {
     Calendar,
     Events,
     Tasks(bool),
     Weather,
+    Calendars,
+    Slots,
+    Plan,
+    Review,
+    Audit,
+    Trash,
+    Conflicts,
+    Starred,
+    Compare,
+    Day,
+    Agenda,
+    Memos,
+    Report,
+    Search,
+    Invitations,
+    Birthdays,
+    Reminders,
+    FreeBusy,
+}
+
+// A row of the agenda: an event, or a task due that day
+#[derive(Clone, Copy)]
+enum AgendaItem<'a> {
+    Event(&'a (api::Event, String)),
+    Task(&'a (Task, String)),
+}
+
+// What main can preset before the app starts
+#[derive(Default)]
+struct AppBuilder {
+    focus_event: Option<String>,
+}
+
+impl AppBuilder {
+    // Opens on this event's day with it selected, once it is in the cache
+    fn focus_event(mut self, id: String) -> Self {
+        self.focus_event = Some(id);
+        self
+    }
+
+    async fn build(self) -> App {
+        App::new(self).await
+    }
 }
 
 impl App {
-    async fn new() -> App {
+    fn builder() -> AppBuilder {
+        AppBuilder::default()
+    }
+
+    async fn new(builder: AppBuilder) -> App {
+        // Before anything is loaded, so the caches read are the sample ones
+        let demo = std::env::args().any(|a| a == "--demo");
+        if demo {
+            demo::prepare();
+        }
         let today = Local::now().date_naive();
         let app_tz = Local::now().offset().clone();
         let events_cache = file_writing::load_events_cache();
+        let day_index = day_index::DayIndex::build(&events_cache, app_tz);
+        let (onecall_weather, air_quality, weather_fetched_at) = match weather::load_cached() {
+            Some(cached) => (Some(cached.response), cached.air, Some(cached.fetched_at)),
+            None => (None, None, None),
+        };
+        let calendars_cache = file_writing::load_calendars_cache();
         let tasks_cache = file_writing::load_tasks_cache();
+        streaks::record(&tasks_cache);
+        // Changes since the caches were saved count as the first sync's
+        let webhook_items = (
+            webhooks::events(&events_cache),
+            webhooks::tasks(&tasks_cache),
+        );
         let (calendar_tx, calendar_rx) = tokio::sync::oneshot::channel();
         let (tasks_tx, tasks_rx) = tokio::sync::oneshot::channel();
-        let rt_handle = tokio::runtime::Handle::current();
         let (deletion_feedback_tx, deletion_feedback_rx) = tokio::sync::mpsc::channel(1);
-        rt_handle.spawn(async move {
-            let hub = calendar_auth::get_calendar_hub().await.ok();
+        let (edit_ack_tx, edit_ack_rx) = tokio::sync::mpsc::channel(16);
+        let mut config = config::parse_config();
+        config.display.screen_reader |= std::env::args().any(|a| a == "--screen-reader");
+        net::configure(&config.network);
+        let debug = std::env::args().any(|a| a == "--debug");
+        logging::init(
+            if debug {
+                tracing::Level::DEBUG
+            } else {
+                config.logging.level.parse().unwrap_or(tracing::Level::INFO)
+            },
+            config.network.log_requests,
+        );
+        tracing::info!("starting");
+        // Before the terminal is taken over, so gpg can still ask for a passphrase
+        let mut secret_errors = Vec::new();
+        match secrets::resolve(&config.api_key, &config.secrets.decrypt_command) {
+            Ok(key) => config.api_key = key,
+            Err(e) => {
+                config.api_key.clear();
+                secret_errors.push(e);
+            }
+        }
+        let client_secret = config.secrets.client_secret.as_ref().and_then(|value| {
+            secrets::resolve(value, &config.secrets.decrypt_command)
+                .map_err(|e| secret_errors.push(e))
+                .ok()
+        });
+        let mut panels: Vec<Box<dyn panels::Panel>> = config
+            .panels
+            .command
+            .iter()
+            .map(|p| Box::new(panels::CommandPanel::new(p.clone())) as Box<dyn panels::Panel>)
+            .collect();
+        if config.panels.streaks {
+            panels.push(Box::<streaks::StreaksPanel>::default());
+        }
+        if debug {
+            panels.push(Box::new(panels::LogPanel));
+            if matches!(config.panels.slot, config::PanelSlot::None) {
+                config.panels.slot = config::PanelSlot::Bottom;
+            }
+        }
+        let network = config.network.clone();
+        let secret = client_secret.clone();
+        let contacts = config.birthdays.contacts;
+        calendar_auth::set_access(config.auth.access);
+        let auth_config = config.auth.clone();
+        wakeup::spawn(async move {
+            let hub = if demo {
+                None
+            } else {
+                calendar_auth::get_calendar_hub(network, secret, auth_config, contacts)
+                    .await
+                    .inspect_err(|e| tracing::error!("calendar sign-in: {e}"))
+                    .ok()
+            };
             let _ = calendar_tx.send(hub);
         });
 
-        rt_handle.spawn(async move {
-            let hub = tasks_auth::get_tasks_hub().await.ok();
+        let network = config.network.clone();
+        let secret = client_secret.clone();
+        let auth_config = config.auth.clone();
+        wakeup::spawn(async move {
+            let hub = if demo {
+                None
+            } else {
+                tasks_auth::get_tasks_hub(network, secret, auth_config)
+                    .await
+                    .inspect_err(|e| tracing::error!("tasks sign-in: {e}"))
+                    .ok()
+            };
             let _ = tasks_tx.send(hub);
         });
-        let app = Self {
-            config: config::parse_config(),
+        let popup = std::env::args().any(|a| a == "--popup");
+        let birthdays = if config.birthdays.contacts {
+            birthdays::load_cached()
+        } else {
+            Vec::new()
+        };
+        let pane_size = file_writing::load_pane_size()
+            .unwrap_or(config.display.pane_size)
+            .clamp(PANE_SIZE_MIN, PANE_SIZE_MAX);
+        let theme = theme::Theme::from_config(&config.theme);
+        let mut app = Self {
+            locale: i18n::Locale::from_code(&config.display.locale),
+            config,
+            popup,
+            event_details: false,
+            zoomed: false,
+            event_filter: events::EventFilter::default(),
+            starred: file_writing::load_starred(),
+            note_dates: notes::dates(),
+            note_to_edit: None,
+            vault: None,
+            private_events: Vec::new(),
             current_date: today,
             today: today,
-            app_layout: MainArea::Calendar,
+            app_layout: if popup {
+                MainArea::Events
+            } else {
+                MainArea::Calendar
+            },
             cursor_line: 0,
             app_tz,
             exit: false,
 
             event_hub: None,
             events_cache,
+            day_index,
+            archive: HashMap::new(),
+            calendars_cache,
             task_hub: None,
             tasks_cache,
             refreshing_status: (String::new(), StatusColor::White),
             changing_status: (String::new(), StatusColor::White),
 
+            acl_rx: None,
+            acl_rules: Vec::new(),
+
+            slots_rx: None,
+            meeting_slots: Vec::new(),
+            meeting_draft: None,
+            plan_blocks: Vec::new(),
+            review: None,
+
+            audit_entries: Vec::new(),
+
+            pending_edits: Vec::new(),
+            next_edit_id: 0,
+            edit_ack_tx,
+            edit_ack_rx,
+            conflicts: Vec::new(),
+            trash_items: Vec::new(),
+            scripts: scripting::Scripts::load(),
+            dbus: dbus::Service::start(),
+            panels,
+
+            feed_events: HashMap::new(),
+            feeds_rx: None,
+            last_feed_fetch: None,
+            birthdays,
+            birthdays_rx: None,
+            last_birthday_fetch: None,
+
+            issue_tasks: Vec::new(),
+            issues_rx: None,
+            last_issue_fetch: None,
+
             weather_rx: None,
-            onecall_weather: None,
+            onecall_weather,
+            air_quality,
+            weather_fetched_at,
+            last_weather_fetch: None,
             weather_day: 1,
 
             change_feedback_tx: Some(deletion_feedback_tx),
             change_feedback_rx: Some(deletion_feedback_rx),
 
+            memos: memos::load(),
+            local_reminders: local_reminders::load(),
             inputting: false,
             cursor_index: 0,
             input_buffer: String::new(),
             updating_event_or_task: false,
+            input_target: InputTarget::TaskOrEvent,
+
+            last_announcement: String::new(),
+            last_announced_status: String::new(),
+            last_announced_input: String::new(),
+
+            status_history: VecDeque::new(),
+            show_status_history: false,
+            message_log: None,
+            help_scroll: None,
+            cell_scroll: (NaiveDate::MIN, 0),
+            demo,
+            range_start: None,
+            report_period: report::Period::Week,
+            news_rx: None,
+            changelog: None,
+            status_seen: String::new(),
+            compare_weeks: 52,
+            pane_size,
+            theme,
+            webhook_items,
+            status_since: std::time::Instant::now(),
 
             events_update_rx: None,
             tasks_update_rx: None,
             needs_refresh: false,
+            last_refresh: std::time::Instant::now(),
+            quota_warned: false,
+            last_reminder_check: Local::now().fixed_offset(),
+            alarms: VecDeque::new(),
+            snoozed: Vec::new(),
 
             auth_status: AuthStatus::Authenticating,
+            client_secret,
             calendar_hub_rx: Some(calendar_rx),
             tasks_hub_rx: Some(tasks_rx),
+            focus_event: builder.focus_event,
         };
+        secret_errors.append(&mut app.config.errors);
+        if !secret_errors.is_empty() {
+            app.changing_status = (secret_errors.join("; "), StatusColor::Red);
+        }
+        if demo {
+            app.changing_status = (
+                "Demo: sample data, nothing is synced or kept".to_string(),
+                StatusColor::Yellow,
+            );
+        }
+        app.apply_birthday_events();
+        app.scripts.hook("startup", "");
+        app.focus_pending_event();
+        if app.config.updates.check && !demo {
+            let (tx, rx) = tokio::sync::mpsc::channel(2);
+            app.news_rx = Some(rx);
+            wakeup::spawn(update::check(tx));
+        }
         app
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        use crossterm::event::{poll, read};
-        use std::time::Duration;
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         self.start_background_weather_fetch();
 
+        let mut input = EventStream::new();
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
+            self.next_event(&mut input).await?;
+            if let Some(date) = self.note_to_edit.take() {
+                // A fresh stream stops the old one's reader thread taking the editor's keys
+                input = EventStream::new();
+                crossterm::execute!(io::stdout(), crossterm::event::DisableBracketedPaste)?;
+                ratatui::restore();
+                let result = notes::edit(date);
+                *terminal = ratatui::init();
+                crossterm::execute!(io::stdout(), crossterm::event::EnableBracketedPaste)?;
+                self.finish_note_edit(result);
+            }
+            // The shell may have reset the tty while stopped, so set it up from scratch
+            let suspended = suspend::take_request();
+            if suspended {
+                input = EventStream::new();
+                restore_terminal();
+                suspend::stop();
+            }
+            if suspended || suspend::take_resumed() {
+                restore_terminal();
+                *terminal = ratatui::init();
+                crossterm::execute!(io::stdout(), crossterm::event::EnableBracketedPaste)?;
+            }
+        }
+        Ok(())
+    }
 
-            if poll(Duration::from_millis(250))? {
-                match read()? {
-                    Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                        if self.inputting {
-                            self.input_handle_key_event(key_event);
-                        } else {
-                            self.handle_key_event(key_event);
+    // Plain line output for screen readers instead of the full-screen UI
+    pub async fn run_linear(&mut self) -> io::Result<()> {
+        self.start_background_weather_fetch();
+
+        let mut input = EventStream::new();
+        while !self.exit {
+            self.announce()?;
+            self.next_event(&mut input).await?;
+            if let Some(date) = self.note_to_edit.take() {
+                input = EventStream::new();
+                crossterm::terminal::disable_raw_mode()?;
+                let result = notes::edit(date);
+                crossterm::terminal::enable_raw_mode()?;
+                self.finish_note_edit(result);
+            }
+            if suspend::take_request() {
+                input = EventStream::new();
+                crossterm::terminal::disable_raw_mode()?;
+                suspend::stop();
+                crossterm::terminal::enable_raw_mode()?;
+            } else if suspend::take_resumed() {
+                crossterm::terminal::disable_raw_mode()?;
+                crossterm::terminal::enable_raw_mode()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn record_status(&mut self) {
+        let (text, color) = &self.changing_status;
+        if *text == self.status_seen {
+            return;
+        }
+        self.status_seen = text.clone();
+        self.status_since = std::time::Instant::now();
+        if text.is_empty() {
+            return;
+        }
+        self.status_history
+            .push_back((Local::now(), text.clone(), color.clone()));
+        if self.status_history.len() > STATUS_HISTORY_LEN {
+            self.status_history.pop_front();
+        }
+    }
+
+    // Finished results fade after the TTL; yellow in-progress messages stay until replaced
+    fn expire_status(&mut self) {
+        let ttl = self.config.display.status_ttl_secs;
+        if ttl == 0
+            || self.changing_status.0.is_empty()
+            || matches!(self.changing_status.1, StatusColor::Yellow)
+        {
+            return;
+        }
+        if self.status_since.elapsed() >= std::time::Duration::from_secs(ttl) {
+            self.changing_status = (String::new(), StatusColor::White);
+        }
+    }
+
+    fn finish_note_edit(&mut self, result: io::Result<()>) {
+        match result {
+            Ok(()) => self.note_dates = notes::dates(),
+            Err(e) => self.changing_status = (format!("Editor failed: {e}"), StatusColor::Red),
+        }
+    }
+
+    // Waits for a key, finished background work or the idle tick, whichever comes first
+    async fn next_event(&mut self, input: &mut EventStream) -> io::Result<()> {
+        tokio::select! {
+            event = input.next() => match event {
+                Some(event) => self.handle_event(event?),
+                None => self.exit(),
+            },
+            _ = wakeup::woken() => {}
+            _ = tokio::time::sleep(IDLE_TICK) => {}
+        }
+        self.tick();
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key_event)
+                // No job control outside Unix
+                if cfg!(unix)
+                    && key_event.kind == KeyEventKind::Press
+                    && key_event.modifiers == KeyModifiers::CONTROL
+                    && key_event.code == KeyCode::Char('z') =>
+            {
+                suspend::request()
+            }
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                if !self.alarms.is_empty() {
+                    self.alarm_handle_key_event(key_event);
+                } else if self.inputting {
+                    self.input_handle_key_event(key_event);
+                } else if let Some(scroll) = self.help_scroll {
+                    self.help_handle_key_event(key_event, scroll);
+                } else if let Some(scroll) = &mut self.message_log {
+                    match key_event.code {
+                        KeyCode::Char('k') | KeyCode::Up => *scroll += 1,
+                        KeyCode::Char('j') | KeyCode::Down => *scroll = scroll.saturating_sub(1),
+                        KeyCode::PageUp => *scroll += 10,
+                        KeyCode::PageDown => *scroll = scroll.saturating_sub(10),
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('!') => {
+                            self.message_log = None
                         }
+                        _ => {}
+                    }
+                } else if let Some((_, _, scroll)) = &mut self.changelog {
+                    match key_event.code {
+                        KeyCode::Char('j') | KeyCode::Down => *scroll += 1,
+                        KeyCode::Char('k') | KeyCode::Up => *scroll = scroll.saturating_sub(1),
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => self.changelog = None,
+                        _ => {}
                     }
-                    _ => {}
+                } else {
+                    self.handle_key_event(key_event);
                 }
             }
+            Event::Paste(text) => self.paste(text),
+            _ => {}
+        }
+    }
+
+    fn tick(&mut self) {
+        self.check_updates();
+        if cfg!(feature = "dbus") {
+            self.dbus.publish(self.dbus_snapshot());
+        }
+        self.check_reminders();
+        self.record_status();
+        self.expire_status();
+        if self
+            .last_feed_fetch
+            .is_none_or(|t| t.elapsed() >= ics::FEED_REFRESH)
+        {
+            self.start_background_feed_fetch();
+        }
+        if self
+            .last_issue_fetch
+            .is_none_or(|t| t.elapsed() >= issues::ISSUES_REFRESH)
+        {
+            self.start_background_issue_fetch();
+        }
+        if self.config.birthdays.contacts
+            && self.event_hub.is_some()
+            && self
+                .last_birthday_fetch
+                .is_none_or(|t| t.elapsed() >= birthdays::BIRTHDAYS_REFRESH)
+        {
+            self.start_background_birthday_fetch();
+        }
+        // Retried at most once per TTL while offline
+        if self
+            .last_weather_fetch
+            .is_none_or(|t| t.elapsed() >= self.weather_ttl())
+        {
+            self.start_background_weather_fetch();
+        }
+        for panel in &mut self.panels {
+            panel.update();
+        }
 
-            self.check_updates();
+        // Hold off on automatic refreshes while close to the API quota
+        let near_limit = usage::near_limit(&self.config.quota);
+        if near_limit && !self.quota_warned {
+            let (minute, day) = usage::counts();
+            self.changing_status = (
+                format!("Nearing API quota ({minute}/min, {day}/day)"),
+                StatusColor::Yellow,
+            );
+        }
+        self.quota_warned = near_limit;
 
-            if self.needs_refresh {
-                self.start_background_refresh();
-                self.needs_refresh = false;
-            }
+        if self.needs_refresh
+            && self.last_refresh.elapsed() >= usage::refresh_delay(&self.config.quota)
+        {
+            self.start_background_refresh();
+            self.needs_refresh = false;
+            self.last_refresh = std::time::Instant::now();
         }
-        Ok(())
     }
 
     fn input_handle_key_event(&mut self, key_event: KeyEvent) {
+        let searching = matches!(self.input_target, InputTarget::Search);
+        let query = searching.then(|| self.input_buffer.clone());
         match (key_event.modifiers, key_event.code) {
+            (_, KeyCode::Up) | (KeyModifiers::CONTROL, KeyCode::Char('p')) if searching => {
+                self.cursor_line = self.cursor_line.saturating_sub(1)
+            }
+            (_, KeyCode::Down) | (KeyModifiers::CONTROL, KeyCode::Char('n')) if searching => {
+                let last = self.search_results().len().saturating_sub(1);
+                self.cursor_line = (self.cursor_line + 1).min(last)
+            }
             (KeyModifiers::CONTROL, KeyCode::Char('c')) | (_, KeyCode::Esc) => self.cancel_input(),
             (KeyModifiers::NONE, KeyCode::Char(ch)) | (KeyModifiers::SHIFT, KeyCode::Char(ch)) => {
                 self.insert_char_at(ch, self.cursor_index);
@@ -197,7 +778,7 @@ impl App {
                 }
             }
             (KeyModifiers::NONE, KeyCode::Right) | (KeyModifiers::CONTROL, KeyCode::Char('f')) => {
-                if self.cursor_index < self.input_buffer.len() {
+                if self.cursor_index < self.char_count() {
                     self.cursor_index += 1
                 }
             }
@@ -209,7 +790,7 @@ impl App {
                 }
             }
             (KeyModifiers::NONE, KeyCode::Delete) | (KeyModifiers::CONTROL, KeyCode::Char('d')) => {
-                if self.cursor_index < self.input_buffer.len() {
+                if self.cursor_index < self.char_count() {
                     self.remove_char_at(self.cursor_index);
                 }
             }
@@ -227,6 +808,14 @@ impl App {
             }
             _ => {}
         }
+        // A new query starts from its first match today or later
+        if query.is_some_and(|q| q != self.input_buffer) {
+            let results = self.search_results();
+            self.cursor_line = results
+                .iter()
+                .position(|(date, _)| *date >= self.today)
+                .unwrap_or(results.len().saturating_sub(1));
+        }
     }
 
     fn char_count(&self) -> usize {
@@ -256,8 +845,12 @@ impl App {
     }
 
     fn cancel_input(&mut self) {
+        if matches!(self.input_target, InputTarget::Search) {
+            self.app_layout = MainArea::Calendar;
+        }
         self.input_buffer.clear();
         self.updating_event_or_task = false;
+        self.input_target = InputTarget::TaskOrEvent;
         self.cursor_index = 0;
         self.inputting = false
     }
@@ -274,6 +867,7 @@ impl App {
         self.cursor_index = 0;
         self.inputting = false;
 
+        self.scripts.hook("create", &title);
         if let MainArea::Tasks(_) = self.app_layout {
             self.create_task_in_background(title);
         } else {
@@ -282,11 +876,89 @@ impl App {
     }
 
     fn update_or_create_task_or_event(&mut self) {
+        if let InputTarget::Search = self.input_target {
+            self.open_selected_search_result();
+            return;
+        }
         // Trimming and checking empty is done here
         if self.input_buffer.trim().is_empty() {
             self.cancel_input();
             return;
         }
+        match self.input_target {
+            InputTarget::WorkingLocation => {
+                let location = self.input_buffer.trim().to_string();
+                self.cancel_input();
+                self.set_working_location_in_background(location);
+                return;
+            }
+            InputTarget::NewCalendar => {
+                let name = self.input_buffer.trim().to_string();
+                self.cancel_input();
+                self.create_calendar_in_background(name);
+                return;
+            }
+            InputTarget::ShareCalendar => {
+                let share = self.input_buffer.trim().to_string();
+                self.cancel_input();
+                self.share_calendar_in_background(share);
+                return;
+            }
+            InputTarget::Meeting => {
+                let request = self.input_buffer.trim().to_string();
+                self.cancel_input();
+                self.find_meeting_slots_in_background(request);
+                return;
+            }
+            InputTarget::Command => {
+                let line = self.input_buffer.trim().to_string();
+                self.cancel_input();
+                if line == "plan" {
+                    self.plan_tasks();
+                } else if line == "reminders" {
+                    self.app_layout = MainArea::Reminders;
+                    self.cursor_line = 0;
+                } else if let Some(request) = line.strip_prefix("remind ") {
+                    self.add_local_reminder(request);
+                } else if line == "report" || line == "report month" {
+                    self.report_period = if line == "report" {
+                        report::Period::Week
+                    } else {
+                        report::Period::Month
+                    };
+                    self.app_layout = MainArea::Report;
+                } else if let Err(e) = self.scripts.command(&line) {
+                    self.changing_status = (e, StatusColor::Red);
+                }
+                return;
+            }
+            InputTarget::Passphrase => {
+                let passphrase = self.input_buffer.clone();
+                self.cancel_input();
+                self.unlock_private_events(&passphrase);
+                return;
+            }
+            InputTarget::EventFilter => {
+                self.event_filter.text = Some(self.input_buffer.trim().to_string());
+                self.cursor_line = 0;
+                self.cancel_input();
+                return;
+            }
+            InputTarget::Memo => {
+                self.memos.insert(
+                    0,
+                    memos::Memo {
+                        created_at: Local::now(),
+                        text: self.input_buffer.trim().to_string(),
+                    },
+                );
+                memos::save(&self.memos);
+                self.cursor_line = 0;
+                self.cancel_input();
+                return;
+            }
+            InputTarget::TaskOrEvent | InputTarget::Search => {}
+        }
         if self.updating_event_or_task {
             self.updating_event_or_task = false;
             let title = self.input_buffer.trim().to_string();
@@ -305,6 +977,9 @@ impl App {
     }
 
     fn update_event_in_background(&mut self, title: String) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
         // Trimming and checking empty is already done
         let Some(hub) = self.event_hub.as_ref().cloned() else {
             self.changing_status = ("Offline".to_string(), StatusColor::Red);
@@ -371,29 +1046,47 @@ impl App {
             },
         };
 
-        tokio::spawn(async move {
+        let before = audit::payload(&current_event.0);
+        let after = audit::payload(&updated_event);
+        let edit_id = self.begin_edit(
+            current_event.1.clone(),
+            merge::LocalChange::Update(merge::patched(&current_event.0, &updated_event)),
+            current_event.0.updated,
+        );
+        let ack_tx = self.edit_ack_tx.clone();
+        wakeup::spawn(async move {
+            let event_id = current_event.0.id.unwrap();
             let result = hub
                 .events()
-                .patch(
-                    updated_event,
-                    &current_event.1,
-                    &current_event.0.id.unwrap(),
-                )
+                .patch(updated_event, &current_event.1, &event_id)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
                 .doit()
                 .await;
+            audit::record(
+                "update_event",
+                &format!("{}/{event_id}", current_event.1),
+                before,
+                after,
+                &result.as_ref().map(|_| ()),
+            );
+            let _ = ack_tx.send(edit_id).await;
 
             let msg = match result {
                 Ok((_, _)) => {
                     // You could update cache with real ID here if you track it
                     ("Event created!".to_string(), StatusColor::Green)
                 }
-                Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                Err(e) => error::Error::from(e).status(),
             };
             let _ = tx.send(msg).await;
         });
     }
 
     fn update_task_in_background(&mut self, title: String) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
         // Trimming and checking empty is already done
         let Some(hub) = self.task_hub.as_ref().cloned() else {
             self.changing_status = ("Offline".to_string(), StatusColor::Red);
@@ -414,24 +1107,32 @@ impl App {
             },
         };
 
-        tokio::spawn(async move {
+        let before = audit::payload(&updating_task);
+        let after = audit::payload(&updated_task);
+        wakeup::spawn(async move {
             let msg = {
+                let task_id = updating_task.id.unwrap();
                 let result = hub
                     .tasks()
-                    .patch(
-                        updated_task,
-                        &updating_tasklist_id,
-                        &updating_task.id.unwrap(),
-                    )
+                    .patch(updated_task, &updating_tasklist_id, &task_id)
+                    .add_scope(tasks_auth::write_scope())
+                    .delegate(&mut net::Middleware::default())
                     .doit()
                     .await;
+                audit::record(
+                    "update_task",
+                    &format!("{updating_tasklist_id}/{task_id}"),
+                    before,
+                    after,
+                    &result.as_ref().map(|_| ()),
+                );
 
                 match result {
                     Ok((_, _)) => {
                         // You could update cache with real ID here if you track it
                         ("Task updated!".to_string(), StatusColor::Green)
                     }
-                    Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                    Err(e) => error::Error::from(e).status(),
                 }
             };
             let _ = tx.send(msg).await;
@@ -439,6 +1140,9 @@ impl App {
     }
 
     fn create_task_in_background(&mut self, title: String) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
         // Trimming and checking empty is already done
         let Some(hub) = self.task_hub.as_ref().cloned() else {
             self.changing_status = ("Offline".to_string(), StatusColor::Red);
@@ -459,11 +1163,18 @@ impl App {
             },
         };
 
-        tokio::spawn(async move {
-            let tasklists = match hub.tasklists().list().doit().await {
+        wakeup::spawn(async move {
+            let tasklists = match hub
+                .tasklists()
+                .list()
+                .add_scope(tasks_auth::read_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await
+            {
                 Ok((_, tasks_list)) => tasks_list.items.unwrap_or_default(),
                 Err(e) => {
-                    eprintln!("Failed to fetch tasklists: {e:?}");
+                    tracing::error!("fetching tasklists: {}", error::Error::from(e));
                     Vec::new()
                 }
             };
@@ -471,18 +1182,33 @@ impl App {
             let msg = match tasklists.first() {
                 None => ("No Tasklist!".to_string(), StatusColor::Red),
                 Some(primary_tasklist) => {
+                    let tasklist_id = primary_tasklist.id.clone().unwrap();
+                    let sent = audit::payload(&new_task);
                     let result = hub
                         .tasks()
-                        .insert(new_task, primary_tasklist.id.as_ref().unwrap()) // Use primary list
+                        .insert(new_task, &tasklist_id) // Use primary list
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
                         .doit()
                         .await;
+                    audit::record(
+                        "create_task",
+                        &tasklist_id,
+                        None,
+                        result
+                            .as_ref()
+                            .ok()
+                            .and_then(|(_, t)| audit::payload(t))
+                            .or(sent),
+                        &result.as_ref().map(|_| ()),
+                    );
 
                     match result {
                         Ok((_, _)) => {
                             // You could update cache with real ID here if you track it
                             ("Task created!".to_string(), StatusColor::Green)
                         }
-                        Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                        Err(e) => error::Error::from(e).status(),
                     }
                 }
             };
@@ -490,19 +1216,13 @@ impl App {
         });
     }
 
-    fn create_event_in_background(&mut self, title: String) {
-        // Trimming and checking empty is already done
-        let Some(hub) = self.event_hub.as_ref().cloned() else {
-            self.changing_status = ("Offline".to_string(), StatusColor::Red);
-            return;
-        };
-
-        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
-        self.changing_status = ("Creating event".to_string(), StatusColor::Yellow);
-
+    // Parses one line of input into a new event on the selected day
+    fn event_from_input(&self, title: &str) -> api::Event {
         // Use current_date as the day
         let date = self.current_date;
-        let new_event = match parse_input::parse_time_range(&title.trim(), date) {
+        let (kind, title) = parse_input::parse_event_kind(title);
+        let (title, with_meet) = parse_input::parse_meet_token(title);
+        let mut new_event = match parse_input::parse_time_range(title.trim(), date) {
             (title, Some(start_datetime), Some(end_datetime), _, _) => {
                 let start_tz = start_datetime
                     .and_local_timezone(self.app_tz)
@@ -570,121 +1290,3286 @@ impl App {
             }
         };
 
-        tokio::spawn(async move {
-            let result = hub.events().insert(new_event, "primary").doit().await;
+        if kind.is_background() {
+            // Out-of-office and focus time can't be all-day, so dates become local midnights
+            for edge in [new_event.start.as_mut(), new_event.end.as_mut()]
+                .into_iter()
+                .flatten()
+            {
+                if let Some(day) = edge.date.take() {
+                    edge.date_time = day
+                        .and_hms_opt(0, 0, 0)
+                        .and_then(|dt| dt.and_local_timezone(self.app_tz).latest())
+                        .map(|dt| dt.to_utc());
+                }
+            }
+            kind.apply_to(&mut new_event);
+        }
+        if with_meet {
+            new_event.conference_data = Some(api::ConferenceData {
+                create_request: Some(api::CreateConferenceRequest {
+                    conference_solution_key: Some(api::ConferenceSolutionKey {
+                        type_: Some("hangoutsMeet".to_string()),
+                    }),
+                    request_id: Some(format!("calpersonal-{}", Utc::now().timestamp_millis())),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+        }
+        new_event
+    }
+
+    fn create_event_in_background(&mut self, title: String) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        // Trimming and checking empty is already done
+        if let (title, true) = parse_input::parse_private_token(&title) {
+            self.create_private_event(title);
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Creating event".to_string(), StatusColor::Yellow);
+
+        let new_event = self.event_from_input(&title);
+
+        let edit_id = self.begin_edit(
+            "primary".to_string(),
+            merge::LocalChange::Create(new_event.clone()),
+            None,
+        );
+        let ack_tx = self.edit_ack_tx.clone();
+        wakeup::spawn(async move {
+            let sent = audit::payload(&new_event);
+            let result = hub
+                .events()
+                .insert(new_event, "primary")
+                .conference_data_version(1)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "create_event",
+                "primary",
+                None,
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|(_, e)| audit::payload(e))
+                    .or(sent),
+                &result.as_ref().map(|_| ()),
+            );
+            let _ = ack_tx.send(edit_id).await;
 
             let msg = match result {
                 Ok((_, _)) => ("Event created!".to_string(), StatusColor::Green),
-                Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                Err(e) => error::Error::from(e).status(),
             };
             let _ = tx.send(msg).await;
         });
     }
 
-    fn first_day_of_month(&self) -> NaiveDate {
-        NaiveDate::from_ymd_opt(self.current_date.year(), self.current_date.month(), 1).unwrap()
-    }
+    // Several pasted lines at once: each becomes its own event (or task), one summary at the end
+    fn create_batch_in_background(&mut self, lines: Vec<String>) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        for line in &lines {
+            self.scripts.hook("create", line);
+        }
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        let total = lines.len();
 
-    fn last_day_of_month(&self) -> NaiveDate {
-        let first_day = self.first_day_of_month();
-        first_day
-            .checked_add_months(Months::new(1))
-            .unwrap()
-            .pred_opt()
-            .unwrap()
+        if let MainArea::Tasks(_) = self.app_layout {
+            let Some(hub) = self.task_hub.as_ref().cloned() else {
+                self.changing_status = ("Offline".to_string(), StatusColor::Red);
+                return;
+            };
+            self.changing_status = (format!("Creating {total} tasks"), StatusColor::Yellow);
+            self.cursor_line = 0;
+            let current_year = self.current_date.year();
+            let new_tasks: Vec<Task> = lines
+                .iter()
+                .map(|line| {
+                    let (title, due, notes) = parse_input::parse_date_and_note(line, current_year);
+                    Task {
+                        title: Some(title),
+                        due,
+                        notes,
+                        ..Task::default()
+                    }
+                })
+                .collect();
+
+            wakeup::spawn(async move {
+                let tasklist_id = match hub
+                    .tasklists()
+                    .list()
+                    .add_scope(tasks_auth::read_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await
+                {
+                    Ok((_, lists)) => lists
+                        .items
+                        .and_then(|items| items.into_iter().next())
+                        .and_then(|list| list.id),
+                    Err(_) => None,
+                };
+                let Some(tasklist_id) = tasklist_id else {
+                    let _ = tx
+                        .send(("No Tasklist!".to_string(), StatusColor::Red))
+                        .await;
+                    return;
+                };
+                let mut created = 0;
+                for task in new_tasks {
+                    let sent = audit::payload(&task);
+                    let result = hub
+                        .tasks()
+                        .insert(task, &tasklist_id)
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
+                        .doit()
+                        .await;
+                    audit::record(
+                        "create_task",
+                        &tasklist_id,
+                        None,
+                        sent,
+                        &result.as_ref().map(|_| ()),
+                    );
+                    created += result.is_ok() as usize;
+                }
+                let _ = tx
+                    .send(batch_summary("Created", created, total, "tasks"))
+                    .await;
+            });
+            return;
+        }
+
+        // Private lines stay local, even when the rest can't be sent
+        let (private, lines): (Vec<String>, Vec<String>) = lines
+            .into_iter()
+            .partition(|line| parse_input::parse_private_token(line).1);
+        for line in &private {
+            self.create_private_event(parse_input::parse_private_token(line).0);
+        }
+        if lines.is_empty() {
+            return;
+        }
+        let new_events = lines
+            .iter()
+            .map(|line| self.event_from_input(line))
+            .collect();
+        self.insert_events_in_background(new_events);
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        frame.render_widget(self, frame.area());
+    // One spawned task inserting every event into the primary calendar in turn
+    fn insert_events_in_background(&mut self, new_events: Vec<api::Event>) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        let total = new_events.len();
+        self.changing_status = (format!("Creating {total} events"), StatusColor::Yellow);
+        let new_events: Vec<(u64, api::Event)> = new_events
+            .into_iter()
+            .map(|event| {
+                let edit_id = self.begin_edit(
+                    "primary".to_string(),
+                    merge::LocalChange::Create(event.clone()),
+                    None,
+                );
+                (edit_id, event)
+            })
+            .collect();
+        let ack_tx = self.edit_ack_tx.clone();
+
+        wakeup::spawn(async move {
+            let mut created = 0;
+            for (edit_id, event) in new_events {
+                let sent = audit::payload(&event);
+                let result = hub
+                    .events()
+                    .insert(event, "primary")
+                    .conference_data_version(1)
+                    .add_scope(calendar_auth::write_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await;
+                audit::record(
+                    "create_event",
+                    "primary",
+                    None,
+                    result
+                        .as_ref()
+                        .ok()
+                        .and_then(|(_, e)| audit::payload(e))
+                        .or(sent),
+                    &result.as_ref().map(|_| ()),
+                );
+                let _ = ack_tx.send(edit_id).await;
+                created += result.is_ok() as usize;
+            }
+            let _ = tx
+                .send(batch_summary("Created", created, total, "events"))
+                .await;
+        });
     }
 
-    fn generate_calendar_grid(&self) -> (Vec<Vec<(NaiveDate, bool, bool)>>, usize) {
-        let first_day = self.first_day_of_month();
-        let last_day = self.last_day_of_month();
-        let current_month = self.current_date.month();
+    // Multi-line pastes are bulk-added; anything else is typed into the open prompt
+    fn paste(&mut self, text: String) {
+        let lines: Vec<String> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+        let can_add = matches!(
+            self.app_layout,
+            MainArea::Calendar | MainArea::Events | MainArea::Weather | MainArea::Tasks(_)
+        );
+        if lines.len() > 1
+            && can_add
+            && !self.updating_event_or_task
+            && matches!(self.input_target, InputTarget::TaskOrEvent)
+        {
+            self.cancel_input();
+            self.create_batch_in_background(lines);
+        } else if self.inputting {
+            for ch in lines.join(" ").chars() {
+                self.insert_char_at(ch, self.cursor_index);
+                self.cursor_index += 1;
+            }
+        }
+    }
+
+    // Timed meetings plus the `~2h` estimates of open tasks due that day
+    fn day_load(&self, date: NaiveDate) -> chrono::Duration {
+        let meetings = self
+            .events_on(date)
+            .filter(|(ev, _)| {
+                !events::EventKind::of(ev).is_background()
+                    && events::response_status(ev) != "declined"
+            })
+            .filter_map(|(ev, _)| {
+                Some(ev.end.as_ref()?.date_time? - ev.start.as_ref()?.date_time?)
+            });
+        let day = date.format("%Y-%m-%d").to_string();
+        let tasks = self
+            .tasks_cache
+            .iter()
+            .filter(|(task, _)| {
+                task.status.as_deref() != Some("completed")
+                    && task.due.as_deref().and_then(|d| d.get(..10)) == Some(day.as_str())
+            })
+            .filter_map(|(task, _)| parse_input::parse_effort(task.title.as_deref()?).1);
+        meetings.chain(tasks).sum()
+    }
+
+    fn over_capacity(&self, date: NaiveDate) -> bool {
+        self.config
+            .capacity_hours
+            .is_some_and(|hours| self.day_load(date).num_minutes() as f64 > hours * 60.0)
+    }
+
+    fn working_location_event(&self, date: NaiveDate) -> Option<&(api::Event, String)> {
+        self.events_on(date)
+            .find(|(event, _)| events::EventKind::of(event) == events::EventKind::WorkingLocation)
+    }
+
+    fn set_working_location_in_background(&mut self, location: String) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Setting location".to_string(), StatusColor::Yellow);
+
+        let date = self.current_date;
+        let before = self
+            .working_location_event(date)
+            .and_then(|(event, _)| audit::payload(event));
+        let existing = self
+            .working_location_event(date)
+            .and_then(|(event, calendar_id)| Some((event.id.clone()?, calendar_id.clone())));
+        let new_event = api::Event {
+            summary: Some(location.clone()),
+            event_type: Some("workingLocation".to_string()),
+            visibility: Some("public".to_string()),
+            transparency: Some("transparent".to_string()),
+            start: Some(api::EventDateTime {
+                date: Some(date),
+                date_time: None,
+                time_zone: None,
+            }),
+            end: Some(api::EventDateTime {
+                date: Some(date.succ_opt().unwrap()),
+                date_time: None,
+                time_zone: None,
+            }),
+            working_location_properties: Some(events::working_location_properties(&location)),
+            ..Default::default()
+        };
+
+        let after = audit::payload(&new_event);
+        wakeup::spawn(async move {
+            let target = match &existing {
+                Some((event_id, calendar_id)) => format!("{calendar_id}/{event_id}"),
+                None => "primary".to_string(),
+            };
+            let result = match existing {
+                Some((event_id, calendar_id)) => hub
+                    .events()
+                    .patch(new_event, &calendar_id, &event_id)
+                    .add_scope(calendar_auth::write_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await
+                    .map(|_| ()),
+                None => hub
+                    .events()
+                    .insert(new_event, "primary")
+                    .add_scope(calendar_auth::write_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await
+                    .map(|_| ()),
+            };
+            audit::record("set_working_location", &target, before, after, &result);
+
+            let msg = match result {
+                Ok(_) => ("Location set!".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    // Calendars in a stable order for the management popup
+    fn sorted_calendars(&self) -> Vec<(&String, &api::CalendarListEntry)> {
+        let mut calendars: Vec<_> = self.calendars_cache.iter().collect();
+        calendars.sort_by_key(|(_, entry)| {
+            (
+                !entry.primary.unwrap_or(false),
+                entry.summary.clone().unwrap_or_default(),
+            )
+        });
+        calendars
+    }
+
+    fn selected_calendar_id(&self) -> Option<String> {
+        let calendars = self.sorted_calendars();
+        let idx = self.cursor_line.min(calendars.len().checked_sub(1)?);
+        Some(calendars[idx].0.clone())
+    }
+
+    fn create_calendar_in_background(&mut self, name: String) {
+        if self.refuse_access(config::Access::Full) {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Creating calendar".to_string(), StatusColor::Yellow);
+
+        let new_calendar = api::Calendar {
+            summary: Some(name),
+            time_zone: self
+                .calendars_cache
+                .values()
+                .find(|c| c.primary.unwrap_or(false))
+                .and_then(|c| c.time_zone.clone()),
+            ..Default::default()
+        };
+
+        wakeup::spawn(async move {
+            let sent = audit::payload(&new_calendar);
+            let result = hub
+                .calendars()
+                .insert(new_calendar)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "create_calendar",
+                "",
+                None,
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|(_, c)| audit::payload(c))
+                    .or(sent),
+                &result.as_ref().map(|_| ()),
+            );
+            let msg = match result {
+                Ok(_) => ("Calendar created!".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    // Input is `email [role]`, role being reader (default), writer, owner or freeBusyReader
+    fn share_calendar_in_background(&mut self, share: String) {
+        if self.refuse_access(config::Access::Full) {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let Some(calendar_id) = self.selected_calendar_id() else {
+            return;
+        };
+
+        let mut parts = share.split_whitespace();
+        let email = parts.next().unwrap_or_default().to_string();
+        let role = parts.next().unwrap_or("reader").to_string();
+        if !["reader", "writer", "owner", "freeBusyReader"].contains(&role.as_str()) {
+            self.changing_status = (format!("Unknown role {role}"), StatusColor::Red);
+            return;
+        }
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Sharing".to_string(), StatusColor::Yellow);
+
+        let rule = api::AclRule {
+            role: Some(role),
+            scope: Some(api::AclRuleScope {
+                type_: Some("user".to_string()),
+                value: Some(email),
+            }),
+            ..Default::default()
+        };
+
+        wakeup::spawn(async move {
+            let after = audit::payload(&rule);
+            let result = hub
+                .acl()
+                .insert(rule, &calendar_id)
+                .send_notifications(true)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "share_calendar",
+                &calendar_id,
+                None,
+                after,
+                &result.as_ref().map(|_| ()),
+            );
+            let msg = match result {
+                Ok(_) => ("Calendar shared!".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    fn start_background_acl_fetch(&mut self) {
+        if self.refuse_access(config::Access::Full) {
+            return;
+        }
+        let Some(hub) = self.event_hub.clone() else {
+            return;
+        };
+        let Some(calendar_id) = self.selected_calendar_id() else {
+            return;
+        };
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.acl_rx = Some(rx);
+        self.acl_rules.clear();
+        wakeup::spawn(async move {
+            match hub
+                .acl()
+                .list(&calendar_id)
+                .add_scope(calendar_auth::read_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await
+            {
+                Ok((_, acl)) => {
+                    let _ = tx.send((calendar_id, acl.items.unwrap_or_default())).await;
+                }
+                Err(e) => tracing::error!("fetching acl: {}", error::Error::from(e)),
+            }
+        });
+    }
+
+    fn find_meeting_slots_in_background(&mut self, request: String) {
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let (attendees, duration, title) = parse_input::parse_meeting_request(&request);
+        let Some(duration) = duration else {
+            self.changing_status = ("Missing duration".to_string(), StatusColor::Red);
+            return;
+        };
+        if attendees.is_empty() {
+            self.changing_status = ("No attendees".to_string(), StatusColor::Red);
+            return;
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.slots_rx = Some(rx);
+        let feedback_tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Finding slots".to_string(), StatusColor::Yellow);
+
+        let now = Local::now().with_timezone(&self.app_tz);
+        // Only this user's protected time; the attendees' comes from Google
+        let protected =
+            scheduling::protected_busy(&self.config.protected, now.date_naive(), 7, self.app_tz);
+        let mut items: Vec<api::FreeBusyRequestItem> = attendees
+            .iter()
+            .map(|email| api::FreeBusyRequestItem {
+                id: Some(email.clone()),
+            })
+            .collect();
+        items.push(api::FreeBusyRequestItem {
+            id: Some("primary".to_string()),
+        });
+        let request = api::FreeBusyRequest {
+            items: Some(items),
+            time_min: Some(now.to_utc()),
+            time_max: Some((now + chrono::Duration::days(7)).to_utc()),
+            ..Default::default()
+        };
+        self.meeting_draft = Some((
+            attendees,
+            if title.is_empty() {
+                "Meeting".to_string()
+            } else {
+                title
+            },
+        ));
+
+        wakeup::spawn(async move {
+            match hub
+                .freebusy()
+                .query(request)
+                .add_scope(calendar_auth::read_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await
+            {
+                Ok((_, response)) => {
+                    let (mut busy, unavailable) = scheduling::busy_periods(response);
+                    busy.extend(protected);
+                    let slots = scheduling::free_slots(&busy, now, 7, duration);
+                    let _ = tx.send((slots, unavailable)).await;
+                }
+                Err(e) => {
+                    let _ = feedback_tx.send(error::Error::from(e).status()).await;
+                }
+            }
+        });
+    }
+
+    // Proposes blocks for open tasks with a `~2h` estimate and a due date, placed in free
+    // working hours from the cache. Tasks that already have a block of the same name coming
+    // up are left out.
+    fn plan_tasks(&mut self) {
+        let now = Local::now().with_timezone(&self.app_tz);
+        let upcoming: Vec<&api::Event> = self
+            .events_between(now.date_naive()..=NaiveDate::MAX)
+            .map(|(_, (ev, _))| ev)
+            .collect();
+        let tasks: Vec<_> = self
+            .tasks_cache
+            .iter()
+            .filter(|(task, _)| task.status.as_deref() != Some("completed"))
+            .filter_map(|(task, _)| {
+                let (title, effort) = parse_input::parse_effort(task.title.as_deref()?);
+                let due = NaiveDate::parse_from_str(task.due.as_deref()?.get(..10)?, "%Y-%m-%d");
+                Some((title, effort?, due.ok()?))
+            })
+            .filter(|(title, _, _)| {
+                !upcoming
+                    .iter()
+                    .any(|ev| ev.summary.as_deref() == Some(title.as_str()))
+            })
+            .collect();
+        if tasks.is_empty() {
+            self.changing_status = ("No estimated tasks to plan".to_string(), StatusColor::Red);
+            return;
+        }
+        let last_due = tasks
+            .iter()
+            .map(|(_, _, due)| *due)
+            .max()
+            .unwrap_or(now.date_naive());
+        let busy: Vec<_> = upcoming
+            .iter()
+            .filter(|ev| {
+                !events::EventKind::of(ev).is_background()
+                    && events::response_status(ev) != "declined"
+            })
+            .filter_map(|ev| Some((ev.start.as_ref()?.date_time?, ev.end.as_ref()?.date_time?)))
+            .chain(scheduling::protected_busy(
+                &self.config.protected,
+                now.date_naive(),
+                (last_due - now.date_naive()).num_days().max(0) as u64 + 1,
+                self.app_tz,
+            ))
+            .collect();
+        let (blocks, unplaced) = scheduling::plan(tasks, &busy, now.fixed_offset());
+        self.changing_status = if unplaced > 0 {
+            (
+                format!("{unplaced} tasks don't fit before their due date"),
+                StatusColor::Red,
+            )
+        } else {
+            (
+                format!("{} blocks proposed", blocks.len()),
+                StatusColor::Green,
+            )
+        };
+        if !blocks.is_empty() {
+            self.plan_blocks = blocks;
+            self.cursor_line = 0;
+            self.app_layout = MainArea::Plan;
+        }
+    }
+
+    fn book_plan(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let new_events = std::mem::take(&mut self.plan_blocks)
+            .into_iter()
+            .map(|(title, (start, end))| api::Event {
+                summary: Some(title),
+                start: Some(api::EventDateTime {
+                    date: None,
+                    date_time: Some(start.to_utc()),
+                    time_zone: None,
+                }),
+                end: Some(api::EventDateTime {
+                    date: None,
+                    date_time: Some(end.to_utc()),
+                    time_zone: None,
+                }),
+                ..Default::default()
+            })
+            .collect();
+        self.app_layout = MainArea::Calendar;
+        self.insert_events_in_background(new_events);
+    }
+
+    fn start_review(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let tasks: Vec<(Task, String)> = self
+            .tasks_cache
+            .iter()
+            .filter(|(_, tasklist_id)| !issues::is_issue(tasklist_id))
+            .cloned()
+            .collect();
+        self.review = Some(review::Review::new(&tasks, &self.events_cache, self.today));
+        self.cursor_line = 0;
+        self.app_layout = MainArea::Review;
+    }
+
+    fn advance_review(&mut self) {
+        let Some(review) = &mut self.review else {
+            return;
+        };
+        self.cursor_line = 0;
+        if review.advance() {
+            return;
+        }
+        let changes = review.changes();
+        self.review = None;
+        self.app_layout = MainArea::Calendar;
+        if changes.is_empty() {
+            self.changing_status = (
+                "Review done, nothing changed".to_string(),
+                StatusColor::Green,
+            );
+            return;
+        }
+        let Some(hub) = self.task_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        let total = changes.len();
+        self.changing_status = (format!("Updating {total} tasks"), StatusColor::Yellow);
+
+        wakeup::spawn(async move {
+            let mut updated = 0;
+            for (tasklist_id, task_id, patch) in changes {
+                let after = audit::payload(&patch);
+                let result = hub
+                    .tasks()
+                    .patch(patch, &tasklist_id, &task_id)
+                    .add_scope(tasks_auth::write_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await;
+                audit::record(
+                    "review_task",
+                    &format!("{tasklist_id}/{task_id}"),
+                    None,
+                    after,
+                    &result.as_ref().map(|_| ()),
+                );
+                updated += result.is_ok() as usize;
+            }
+            let _ = tx
+                .send(batch_summary("Updated", updated, total, "tasks"))
+                .await;
+        });
+    }
+
+    fn create_meeting_from_selected_slot(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let Some(&(start, end)) = self.meeting_slots.get(
+            self.cursor_line
+                .min(self.meeting_slots.len().saturating_sub(1)),
+        ) else {
+            return;
+        };
+        let Some((attendees, title)) = self.meeting_draft.take() else {
+            return;
+        };
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Creating meeting".to_string(), StatusColor::Yellow);
+        self.app_layout = MainArea::Calendar;
+        self.current_date = start.date_naive();
+        self.meeting_slots.clear();
+
+        let new_event = api::Event {
+            summary: Some(title),
+            start: Some(api::EventDateTime {
+                date: None,
+                date_time: Some(start.to_utc()),
+                time_zone: None,
+            }),
+            end: Some(api::EventDateTime {
+                date: None,
+                date_time: Some(end.to_utc()),
+                time_zone: None,
+            }),
+            attendees: Some(
+                attendees
+                    .into_iter()
+                    .map(|email| api::EventAttendee {
+                        email: Some(email),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        wakeup::spawn(async move {
+            let sent = audit::payload(&new_event);
+            let result = hub
+                .events()
+                .insert(new_event, "primary")
+                .send_updates("all")
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "create_meeting",
+                "primary",
+                None,
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|(_, e)| audit::payload(e))
+                    .or(sent),
+                &result.as_ref().map(|_| ()),
+            );
+            let msg = match result {
+                Ok(_) => ("Meeting created!".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    // What the DBus service answers with until the next tick
+    fn dbus_snapshot(&self) -> dbus::Snapshot {
+        let now = Utc::now();
+        let next_event = (0..7)
+            .flat_map(|d| self.events_on(self.today + Days::new(d)))
+            .filter(|(ev, _)| !events::EventKind::of(ev).is_background())
+            .filter_map(|entry| {
+                let start = entry.0.start.as_ref()?.date_time?;
+                let end = entry
+                    .0
+                    .end
+                    .as_ref()
+                    .and_then(|e| e.date_time)
+                    .unwrap_or(start);
+                (end > now).then_some((entry, start))
+            })
+            .min_by_key(|(_, start)| *start)
+            .map(|(entry, start)| {
+                (
+                    self.display_title(entry),
+                    start.with_timezone(&self.app_tz).to_rfc3339(),
+                )
+            });
+        let (open, due) = widget::task_counts(&self.tasks_cache);
+        dbus::Snapshot {
+            next_event,
+            events_today: self.events_on(self.today).count() as u32,
+            tasks_due: due as u32,
+            tasks_open: open as u32,
+        }
+    }
+
+    fn focus_pending_event(&mut self) {
+        let Some(id) = &self.focus_event else {
+            return;
+        };
+        let Some(date) = self.day_index.date_of(id) else {
+            return;
+        };
+        self.current_date = date;
+        self.app_layout = MainArea::Events;
+        self.event_filter = events::EventFilter::default();
+        self.cursor_line = self
+            .current_day_events()
+            .iter()
+            .position(|(event, _)| event.id.as_ref() == Some(id))
+            .unwrap_or(0);
+        self.focus_event = None;
+    }
+
+    fn copy_selected_event_link(&mut self) {
+        let Some(id) = self
+            .selected_event()
+            .and_then(|(event, _)| event.id.clone())
+        else {
+            return;
+        };
+        self.changing_status = if clipboard::copy(&deep_link::event_link(&id)) {
+            ("Copied link!".to_string(), StatusColor::Green)
+        } else {
+            ("Copy failed".to_string(), StatusColor::Red)
+        };
+    }
+
+    // Meeting stats for the week or month around current_date
+    fn meeting_report(&self) -> report::Report {
+        let range = self.report_period.range(self.current_date);
+        report::build(
+            self.report_period,
+            self.current_date,
+            self.events_between(range).map(|(_, (ev, _))| ev),
+            &self.app_tz,
+        )
+    }
+
+    fn copy_meeting_report(&mut self) {
+        let text = self
+            .meeting_report()
+            .markdown(&self.config.display.date_format);
+        self.changing_status = if clipboard::copy(&text) {
+            ("Copied as Markdown".to_string(), StatusColor::Green)
+        } else {
+            ("Copy failed".to_string(), StatusColor::Red)
+        };
+    }
+
+    fn copy_selected_event(&mut self) {
+        let Some((event, _)) = self.selected_event() else {
+            return;
+        };
+        let text = events::summary_text(event, self.app_tz, &self.config.display);
+        self.changing_status = if clipboard::copy(&text) {
+            ("Copied!".to_string(), StatusColor::Green)
+        } else {
+            ("Copy failed".to_string(), StatusColor::Red)
+        };
+    }
+
+    fn first_day_of_month(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.current_date.year(), self.current_date.month(), 1).unwrap()
+    }
+
+    fn last_day_of_month(&self) -> NaiveDate {
+        let first_day = self.first_day_of_month();
+        first_day
+            .checked_add_months(Months::new(1))
+            .unwrap()
+            .pred_opt()
+            .unwrap()
+    }
+
+    fn announce(&mut self) -> io::Result<()> {
+        use std::io::Write;
+        let mut out = io::stdout();
+
+        let announcement = self.announcement();
+        if announcement != self.last_announcement {
+            // Raw mode needs the carriage return
+            write!(out, "{announcement}\r\n")?;
+            self.last_announcement = announcement;
+            // A prefilled buffer is read out along with the prompt
+            self.last_announced_input.clear();
+        }
+        if !self.inputting {
+            self.last_announced_input.clear();
+        } else if self.input_buffer != self.last_announced_input {
+            let echo = input_echo(&self.last_announced_input, &self.input_buffer);
+            if echo.is_empty() {
+                write!(out, "{}\r\n", self.locale.t("Blank"))?;
+            } else {
+                write!(out, "{echo}\r\n")?;
+            }
+            self.last_announced_input = self.input_buffer.clone();
+        }
+        let status = self.changing_status.0.clone();
+        if status != self.last_announced_status {
+            if !status.is_empty() {
+                write!(out, "{}\r\n", self.locale.t(&status))?;
+            }
+            self.last_announced_status = status;
+        }
+        out.flush()
+    }
+
+    // One line describing whatever currently has focus
+    fn announcement(&self) -> String {
+        if self.inputting {
+            return format!("{}:", self.locale.t(self.input_prompt()));
+        }
+        match self.app_layout {
+            MainArea::Calendar => {
+                let date = self
+                    .current_date
+                    .format(&self.locale.localize_format(
+                        &format!("%A {}", self.config.display.date_format),
+                        &self.current_date,
+                    ))
+                    .to_string();
+                let location = self
+                    .working_location_event(self.current_date)
+                    .and_then(|(event, _)| events::working_location_label(event))
+                    .map(|label| format!(" @{label}"))
+                    .unwrap_or_default();
+                format!(
+                    "{date}{location}, {} {}",
+                    self.current_day_events().len(),
+                    self.locale.t("Events")
+                )
+            }
+            MainArea::Events => match self.selected_event() {
+                Some((event, _)) => format!(
+                    "{} {} / {}: {}",
+                    self.locale.t("Event"),
+                    self.selected_event_index().unwrap_or(0) + 1,
+                    self.current_day_events().len(),
+                    events::summary_text(event, self.app_tz, &self.config.display)
+                        .replace('\n', ", ")
+                ),
+                None => format!("0 {}", self.locale.t("Events")),
+            },
+            MainArea::Tasks(_) => match self.selected_task() {
+                Some((task, _)) => {
+                    let due = task
+                        .due
+                        .as_deref()
+                        .and_then(|due| DateTime::parse_from_rfc3339(due).ok())
+                        .map(|due| format!(", {}", due.format(&self.config.display.date_format)))
+                        .unwrap_or_default();
+                    let completed = match task.completed {
+                        Some(_) => format!(", {}", self.locale.t("Completed")),
+                        None => String::new(),
+                    };
+                    format!(
+                        "{} {} / {}: {}{due}{completed}",
+                        self.locale.t("Task"),
+                        self.selected_task_index().unwrap_or(0) + 1,
+                        self.tasks_cache.len(),
+                        task.title.as_deref().unwrap_or("Untitled")
+                    )
+                }
+                None => format!("0 {}", self.locale.t("Tasks")),
+            },
+            MainArea::Weather => match &self.onecall_weather {
+                Some(weather) => format!(
+                    "{}: {}, {}°C",
+                    self.locale.t("Current Weather"),
+                    weather
+                        .current
+                        .weather
+                        .first()
+                        .map(|w| w.main.as_str())
+                        .unwrap_or(""),
+                    weather.current.temp
+                ),
+                None => format!("{}: -", self.locale.t("Weather")),
+            },
+            MainArea::Calendars => {
+                let calendars = self.sorted_calendars();
+                match calendars.get(self.cursor_line) {
+                    Some((id, _)) => format!(
+                        "{} {} / {}: {}",
+                        self.locale.t("Calendars"),
+                        self.cursor_line + 1,
+                        calendars.len(),
+                        self.calendar_name(id)
+                    ),
+                    None => format!("0 {}", self.locale.t("Calendars")),
+                }
+            }
+            MainArea::Review => match &self.review {
+                Some(review) => format!(
+                    "{}: {} / {}",
+                    self.locale.t(review.step.title()),
+                    (self.cursor_line + 1).min(review.len()),
+                    review.len()
+                ),
+                None => String::new(),
+            },
+            MainArea::Plan => match self.plan_blocks.get(self.cursor_line) {
+                Some((title, (start, end))) => format!(
+                    "{} {} / {}: {title}, {} - {}",
+                    self.locale.t("Plan"),
+                    self.cursor_line + 1,
+                    self.plan_blocks.len(),
+                    start.format(&format!(
+                        "{} {}",
+                        self.config.display.date_format,
+                        self.config.display.time_format()
+                    )),
+                    end.format(self.config.display.time_format())
+                ),
+                None => format!("0 {}", self.locale.t("Plan")),
+            },
+            MainArea::Slots => match self.meeting_slots.get(self.cursor_line) {
+                Some((start, end)) => format!(
+                    "{} {} / {}: {} - {}",
+                    self.locale.t("Meet"),
+                    self.cursor_line + 1,
+                    self.meeting_slots.len(),
+                    start.format(&format!(
+                        "%a {} {}",
+                        self.config.display.date_format,
+                        self.config.display.time_format()
+                    )),
+                    end.format(self.config.display.time_format())
+                ),
+                None => format!("0 {}", self.locale.t("Meet")),
+            },
+            MainArea::Audit => match self.audit_entries.get(self.cursor_line) {
+                Some(entry) => format!(
+                    "{} {} / {}: {} {} {}",
+                    self.locale.t("Audit log"),
+                    self.cursor_line + 1,
+                    self.audit_entries.len(),
+                    entry.action,
+                    entry.title(),
+                    entry.result
+                ),
+                None => format!("0 {}", self.locale.t("Audit log")),
+            },
+            MainArea::Compare => {
+                let (this_week, other_week) = self.compare_week_starts();
+                let count = |start: NaiveDate| self.history_week(start).count();
+                format!(
+                    "{} {}: {} {}, {}: {} {}",
+                    self.locale.t("Week"),
+                    this_week.format(&self.config.display.date_format),
+                    count(this_week),
+                    self.locale.t("Events"),
+                    other_week.format(&self.config.display.date_format),
+                    count(other_week),
+                    self.locale.t("Events")
+                )
+            }
+            MainArea::Report => self
+                .meeting_report()
+                .title(&self.config.display.date_format),
+            MainArea::Invitations => match self.invitations().get(self.cursor_line) {
+                Some((_, entry)) => format!(
+                    "{} {} / {}: {}",
+                    self.locale.t("Invitations"),
+                    self.cursor_line + 1,
+                    self.invitations().len(),
+                    self.display_title(entry)
+                ),
+                None => format!("0 {}", self.locale.t("Invitations")),
+            },
+            MainArea::Birthdays => format!(
+                "{} {}",
+                self.upcoming_birthdays().len(),
+                self.locale.t("Birthdays")
+            ),
+            MainArea::Search => format!(
+                "{} {}",
+                self.search_results().len(),
+                self.locale.t("results")
+            ),
+            MainArea::Reminders => format!(
+                "{} {}",
+                self.local_reminders.len(),
+                self.locale.t("Reminders")
+            ),
+            MainArea::Memos => match self.memos.get(self.cursor_line) {
+                Some(memo) => format!(
+                    "{} {} / {}: {}",
+                    self.locale.t("Memos"),
+                    self.cursor_line + 1,
+                    self.memos.len(),
+                    memo.text
+                ),
+                None => format!("0 {}", self.locale.t("Memos")),
+            },
+            MainArea::Agenda => match self.agenda().get(self.cursor_line) {
+                Some((date, item)) => format!(
+                    "{} {} / {}: {} {}",
+                    self.locale.t("Agenda"),
+                    self.cursor_line + 1,
+                    self.agenda().len(),
+                    date.format(&self.config.display.date_format),
+                    match item {
+                        AgendaItem::Event(ev) => self.display_title(ev),
+                        AgendaItem::Task((task, _)) => task.title.clone().unwrap_or_default(),
+                    }
+                ),
+                None => format!("0 {}", self.locale.t("Agenda")),
+            },
+            MainArea::Day => format!(
+                "{}: {} {}",
+                self.current_date.format(&self.config.display.date_format),
+                self.events_on(self.current_date).count(),
+                self.locale.t("Events")
+            ),
+            MainArea::FreeBusy => {
+                let (from, to) = scheduling::workday(self.current_date);
+                let free: i64 = self
+                    .free_on(self.current_date, from, to)
+                    .iter()
+                    .map(|(start, end)| (*end - *start).num_minutes())
+                    .sum();
+                format!(
+                    "{} {}: {}h{:02} {}",
+                    self.locale.t("Free/busy"),
+                    self.current_date.format(&self.config.display.date_format),
+                    free / 60,
+                    free % 60,
+                    self.locale.t("free")
+                )
+            }
+            MainArea::Starred => match self.starred_events().get(self.cursor_line) {
+                Some((date, (event, _))) => format!(
+                    "{} {} / {}: {} {}",
+                    self.locale.t("Starred"),
+                    self.cursor_line + 1,
+                    self.starred_events().len(),
+                    date.format(&self.config.display.date_format),
+                    event.summary.as_deref().unwrap_or("Untitled")
+                ),
+                None => format!("0 {}", self.locale.t("Starred")),
+            },
+            MainArea::Trash => match self.trash_items.get(self.cursor_line) {
+                Some(item) => format!(
+                    "{} {} / {}: {}",
+                    self.locale.t("Trash"),
+                    self.cursor_line + 1,
+                    self.trash_items.len(),
+                    item.title()
+                ),
+                None => format!("0 {}", self.locale.t("Trash")),
+            },
+            MainArea::Conflicts => match self.conflicts.first() {
+                Some(conflict) => format!(
+                    "{}: {} / {}",
+                    self.locale.t("Conflict"),
+                    events::summary_text(&conflict.local, self.app_tz, &self.config.display)
+                        .replace('\n', ", "),
+                    events::summary_text(&conflict.remote, self.app_tz, &self.config.display)
+                        .replace('\n', ", ")
+                ),
+                None => format!("0 {}", self.locale.t("Conflict")),
+            },
+        }
+    }
+
+    // Footer keys for what's on screen right now, most useful first
+    fn hints(&self) -> &'static str {
+        if self.inputting {
+            if let InputTarget::Search = self.input_target {
+                return "↑/↓:select  Enter:go to day  Esc:cancel";
+            }
+            return "Enter:save  Esc:cancel  ^A/^E:start/end  ^U/^K:clear";
+        }
+        match self.app_layout {
+            MainArea::Calendar if self.range_start.is_some() => {
+                "hjkl:extend  a:add over these days  x/Esc:cancel"
+            }
+            MainArea::Calendar => {
+                "a:add  E:events  T:tasks  W:weather  N:note  z:zoom  ?:help  q:quit"
+            }
+            MainArea::Events if self.popup => "a:add  j/k:select  D:delete  Esc:quit",
+            MainArea::Events if self.selected_event().is_some() => {
+                "a:edit  D:delete  c:copy  *:star  O:open  /:filter  Esc:back"
+            }
+            MainArea::Events => "Enter:details  a:add  f:calendar  r:response  /:filter  Esc:back",
+            MainArea::Tasks(false) => {
+                "o:add  a:edit  space:done  Enter:notes  D:delete  L:clear done"
+            }
+            MainArea::Tasks(true) => "a:edit  space:done  D:delete  Esc:close notes",
+            MainArea::Weather => "</>:day  W:close",
+            MainArea::Calendars => "a:new  Enter:sharing  s:share  Esc:back",
+            MainArea::Slots => "Enter:book slot  Esc:cancel",
+            MainArea::Plan => "Enter:book all blocks  Esc:cancel",
+            MainArea::Review => "space:reschedule  Enter:next step  Esc:cancel",
+            MainArea::Audit => "j/k:scroll  Esc:back",
+            MainArea::Trash => "Enter:restore  Esc:back",
+            MainArea::Conflicts => "Enter:keep mine  D:keep theirs",
+            MainArea::Starred => "Enter:go to day  *:unstar  Esc:back",
+            MainArea::Compare => "h/l:week  </>:compare further/closer  K:close",
+            MainArea::Day => "h/l:day  j/k:scroll  v:close",
+            MainArea::Agenda => "Enter:go to day  g:close",
+            MainArea::Memos => "a:add  t:to task  e:to event  D:delete  m:close",
+            MainArea::Report => "h/l:previous/next  space:week/month  c:copy as Markdown  Esc:back",
+            MainArea::Search => "Esc:back",
+            MainArea::Invitations => "a:accept  t:maybe  d:decline  Enter:go to day  I:close",
+            MainArea::Birthdays => "Enter:go to day  B:close",
+            MainArea::Reminders => "a:add  D:delete  Esc:back",
+            MainArea::FreeBusy => "h/l/j/k:day  </>:week  J:close",
+        }
+    }
+
+    // Untranslated label for the input line
+    fn input_prompt(&self) -> &'static str {
+        match self.input_target {
+            InputTarget::WorkingLocation => "Where",
+            InputTarget::NewCalendar => "Name",
+            InputTarget::ShareCalendar => "Share",
+            InputTarget::Meeting => "Meet",
+            InputTarget::Command => "Run",
+            InputTarget::EventFilter => "Filter",
+            InputTarget::Passphrase => "Passphrase",
+            InputTarget::Memo => "Memo",
+            InputTarget::Search => "Search",
+            InputTarget::TaskOrEvent => match self.app_layout {
+                MainArea::Tasks(_) => "Tasks",
+                _ => "Event",
+            },
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+    }
+
+    fn generate_calendar_grid(&self) -> (Vec<Vec<(NaiveDate, bool, bool)>>, usize) {
+        let first_day = self.first_day_of_month();
+        let last_day = self.last_day_of_month();
 
         // Get weekday of first day (0 = Sunday, 6 = Saturday)
         let first_weekday = first_day.weekday().num_days_from_sunday() as i32;
 
-        // Calculate starting date (might be from previous month)
-        let start_date = first_day - chrono::Duration::days(first_weekday as i64);
-        let number_of_days = last_day.signed_duration_since(start_date).num_days();
-        let number_of_rows = if number_of_days > 34 {
-            6
-        } else if number_of_days < 29 {
-            4
+        // Calculate starting date (might be from previous month)
+        let start_date = first_day - chrono::Duration::days(first_weekday as i64);
+        let number_of_days = last_day.signed_duration_since(start_date).num_days();
+        let number_of_rows = if number_of_days > 34 {
+            6
+        } else if number_of_days < 29 {
+            4
+        } else {
+            5
+        };
+        (self.month_grid(first_day), number_of_rows)
+    }
+
+    // Six weeks from the Sunday on or before the 1st: (date, in this month, today)
+    fn month_grid(&self, first_day: NaiveDate) -> Vec<Vec<(NaiveDate, bool, bool)>> {
+        let current_month = first_day.month();
+        let first_weekday = first_day.weekday().num_days_from_sunday() as i32;
+        let start_date = first_day - chrono::Duration::days(first_weekday as i64);
+        let mut grid = Vec::new();
+
+        // Generate 6 weeks (42 days total)
+        for week in 0..6 {
+            let mut week_days = Vec::new();
+            for day in 0..7 {
+                let drawing_date = start_date + chrono::Duration::days((week * 7 + day) as i64);
+                // Check if this date is in the current month
+                let is_current_month = drawing_date.month() == current_month;
+
+                // Check if this date is today
+                let is_today = drawing_date == self.today;
+                week_days.push((drawing_date, is_current_month, is_today));
+            }
+            grid.push(week_days);
+        }
+        grid
+    }
+
+    // One dot an event in its calendar's color, and a count of those that don't fit: ●●● +2
+    fn density_line(&self, calendars: &[&str], in_month: bool, width: usize) -> Line<'static> {
+        let total = calendars.len();
+        let shown = (0..=total)
+            .rev()
+            .find(|shown| {
+                let rest = total - shown;
+                let badge = if rest > 0 {
+                    rest.to_string().len() + 2
+                } else {
+                    0
+                };
+                shown + badge <= width
+            })
+            .unwrap_or(0);
+        let mut spans: Vec<Span> = calendars[..shown]
+            .iter()
+            .map(|calendar| {
+                let dot = Span::raw("●");
+                match self.calendar_color(calendar) {
+                    _ if !in_month => dot.fg(self.theme.dimmed),
+                    Some(color) => dot.fg(color),
+                    None => dot,
+                }
+            })
+            .collect();
+        if shown < total {
+            spans.push(Span::raw(format!(" +{}", total - shown)).fg(self.theme.dimmed));
+        }
+        Line::from(spans)
+    }
+
+    // Day numbers in the month grid and the mini months alike
+    fn day_number_color(&self, date: NaiveDate, in_month: bool) -> Option<Color> {
+        if date == self.today {
+            Some(self.theme.today)
+        } else if !in_month {
+            Some(self.theme.dimmed)
+        } else {
+            match date.weekday() {
+                Weekday::Sun => Some(self.theme.sunday),
+                Weekday::Sat => Some(self.theme.saturday),
+                _ => None,
+            }
+        }
+    }
+
+    // The days picked with x, then the cursor, then countdown days
+    fn day_number_background(
+        &self,
+        date: NaiveDate,
+        in_month: bool,
+        cursor: bool,
+    ) -> Option<Color> {
+        if in_month
+            && self
+                .selected_range()
+                .is_some_and(|(first, last)| (first..=last).contains(&date))
+        {
+            Some(self.theme.range)
+        } else if cursor {
+            Some(self.theme.selected)
+        } else if self.config.countdowns.iter().any(|c| c.date == date) {
+            Some(Color::Magenta)
+        } else {
+            None
+        }
+    }
+
+    // Starred events across all cached days, each at its first day
+    fn starred_events(&self) -> Vec<(NaiveDate, &(api::Event, String))> {
+        let mut seen = HashSet::new();
+        self.events_between(NaiveDate::MIN..=NaiveDate::MAX)
+            .filter(|(_, (event, _))| {
+                event
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| self.starred.contains(id) && seen.insert(id.clone()))
+            })
+            .collect()
+    }
+
+    fn is_starred(&self, event: &api::Event) -> bool {
+        event
+            .id
+            .as_ref()
+            .is_some_and(|id| self.starred.contains(id))
+    }
+
+    fn toggle_star(&mut self) {
+        let event = match self.app_layout {
+            MainArea::Events => self.selected_event(),
+            MainArea::Starred => self
+                .starred_events()
+                .get(self.cursor_line)
+                .map(|(_, ev)| *ev),
+            _ => None,
+        };
+        let Some(id) = event.and_then(|(event, _)| event.id.clone()) else {
+            return;
+        };
+        if !self.starred.remove(&id) {
+            self.starred.insert(id);
+        }
+        file_writing::save_starred(&self.starred);
+        if let MainArea::Starred = self.app_layout {
+            self.cursor_line = self
+                .cursor_line
+                .min(self.starred_events().len().saturating_sub(1));
+        }
+    }
+
+    fn add_local_reminder(&mut self, request: &str) {
+        let Some(reminder) = local_reminders::parse(request) else {
+            self.changing_status = (
+                "Give a time and how often, e.g. :remind stretch 15:00 weekdays".to_string(),
+                StatusColor::Red,
+            );
+            return;
+        };
+        self.changing_status = (
+            format!(
+                "Reminder added: {} {} {}",
+                reminder.text,
+                reminder.time.format(self.config.display.time_format()),
+                reminder.repeat.label()
+            ),
+            StatusColor::Green,
+        );
+        self.local_reminders.push(reminder);
+        self.local_reminders
+            .sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.text.cmp(&b.text)));
+        local_reminders::save(&self.local_reminders);
+    }
+
+    fn delete_selected_local_reminder(&mut self) {
+        if self.cursor_line >= self.local_reminders.len() {
+            return;
+        }
+        self.local_reminders.remove(self.cursor_line);
+        local_reminders::save(&self.local_reminders);
+        self.cursor_line = self
+            .cursor_line
+            .min(self.local_reminders.len().saturating_sub(1));
+    }
+
+    fn delete_selected_memo(&mut self) {
+        if self.cursor_line >= self.memos.len() {
+            return;
+        }
+        self.memos.remove(self.cursor_line);
+        memos::save(&self.memos);
+        self.cursor_line = self.cursor_line.min(self.memos.len().saturating_sub(1));
+    }
+
+    // The memo's text goes through the same parsing as typed input, so dates and times in it
+    // count; events land on the selected day. Kept while offline.
+    fn convert_selected_memo(&mut self, to_task: bool) {
+        let Some(memo) = self.memos.get(self.cursor_line).cloned() else {
+            return;
+        };
+        let online = if to_task {
+            self.task_hub.is_some()
+        } else {
+            self.event_hub.is_some() || parse_input::parse_private_token(&memo.text).1
+        };
+        if !online {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        }
+        self.delete_selected_memo();
+        self.scripts.hook("create", &memo.text);
+        if to_task {
+            self.create_task_in_background(memo.text);
+        } else {
+            self.create_event_in_background(memo.text);
+        }
+    }
+
+    // The next `agenda_days` days from today: events in start order, then open tasks due
+    fn agenda(&self) -> Vec<(NaiveDate, AgendaItem<'_>)> {
+        (0..self.config.display.agenda_days)
+            .map(|d| self.today + Days::new(d))
+            .flat_map(|date| {
+                let day = date.format("%Y-%m-%d").to_string();
+                let tasks = self.tasks_cache.iter().filter(move |(task, _)| {
+                    task.status.as_deref() != Some("completed")
+                        && task.due.as_deref().and_then(|d| d.get(..10)) == Some(day.as_str())
+                });
+                self.events_on(date)
+                    .map(AgendaItem::Event)
+                    .chain(tasks.map(AgendaItem::Task))
+                    .map(move |item| (date, item))
+            })
+            .collect()
+    }
+
+    // Timed events over by now that ended today; these fade with display.fade_past
+    fn ended_earlier_today(&self, event: &api::Event) -> bool {
+        let Some(end) = event.end.as_ref().and_then(|e| e.date_time) else {
+            return false;
+        };
+        let now = Utc::now();
+        self.config.display.fade_past
+            && end <= now
+            && end.with_timezone(&self.app_tz).date_naive() == self.today
+    }
+
+    // The first row of today's events, or of the agenda, that is still to come
+    fn next_event_line(&self) -> usize {
+        if self.current_date != self.today {
+            return 0;
+        }
+        self.current_day_events()
+            .iter()
+            .position(|(ev, _)| !self.ended_earlier_today(ev))
+            .unwrap_or(0)
+    }
+
+    fn next_agenda_line(&self) -> usize {
+        self.agenda()
+            .iter()
+            .position(|(_, item)| match item {
+                AgendaItem::Event((ev, _)) => !self.ended_earlier_today(ev),
+                AgendaItem::Task(_) => true,
+            })
+            .unwrap_or(0)
+    }
+
+    // Events open in the day's event list, tasks just select their day
+    fn open_selected_agenda_item(&mut self) {
+        let Some((date, item)) = self.agenda().get(self.cursor_line).copied() else {
+            return;
+        };
+        let position = match item {
+            AgendaItem::Event((event, _)) => {
+                let id = event.id.clone();
+                self.events_on(date).position(|(e, _)| e.id == id)
+            }
+            AgendaItem::Task(_) => None,
+        };
+        self.current_date = date;
+        self.cursor_line = position.unwrap_or(0);
+        self.app_layout = match position {
+            Some(_) => MainArea::Events,
+            None => MainArea::Calendar,
+        };
+    }
+
+    // Every cached event whose title, location or description holds the search text, by date
+    fn search_results(&self) -> Vec<(NaiveDate, &(api::Event, String))> {
+        let text = self.input_buffer.trim();
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let filter = events::EventFilter {
+            text: Some(text.to_string()),
+            ..Default::default()
+        };
+        self.events_between(NaiveDate::MIN..=NaiveDate::MAX)
+            .filter(|(_, (event, calendar_id))| filter.matches(event, calendar_id))
+            .collect()
+    }
+
+    fn open_selected_search_result(&mut self) {
+        let selected = self
+            .search_results()
+            .get(self.cursor_line)
+            .map(|(date, (event, _))| (*date, event.id.clone()));
+        self.cancel_input();
+        let Some((date, id)) = selected else {
+            return;
+        };
+        let position = self.events_on(date).position(|(e, _)| e.id == id);
+        self.current_date = date;
+        self.event_filter = events::EventFilter::default();
+        self.app_layout = MainArea::Events;
+        self.cursor_line = position.unwrap_or(0);
+    }
+
+    // Upcoming events you haven't answered yet, soonest first
+    fn invitations(&self) -> Vec<(NaiveDate, &(api::Event, String))> {
+        let mut seen = HashSet::new();
+        self.events_between(self.today..=NaiveDate::MAX)
+            .filter(|(_, (event, _))| {
+                events::response_status(event) == "needsAction"
+                    && event.status.as_deref() != Some("cancelled")
+                    && event.id.as_ref().is_some_and(|id| seen.insert(id.clone()))
+            })
+            .collect()
+    }
+
+    fn open_selected_invitation(&mut self) {
+        let Some((date, id)) = self
+            .invitations()
+            .get(self.cursor_line)
+            .map(|(date, (event, _))| (*date, event.id.clone()))
+        else {
+            return;
+        };
+        let position = self.events_on(date).position(|(e, _)| e.id == id);
+        self.current_date = date;
+        self.app_layout = MainArea::Events;
+        self.cursor_line = position.unwrap_or(0);
+    }
+
+    // Sends only the attendee list, with your own entry changed; others' answers stay as
+    // they were
+    fn respond_to_selected_invitation(&mut self, response: &'static str) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let Some((event, calendar_id)) = self
+            .invitations()
+            .get(self.cursor_line)
+            .map(|(_, (event, calendar_id))| (event.clone(), calendar_id.clone()))
+        else {
+            return;
+        };
+        if let Some(reason) = self.read_only_reason(&calendar_id) {
+            self.changing_status = (reason.to_string(), StatusColor::Red);
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let Some(event_id) = event.id.clone() else {
+            return;
+        };
+        let mut attendees = event.attendees.clone().unwrap_or_default();
+        for attendee in attendees.iter_mut().filter(|a| a.self_ == Some(true)) {
+            attendee.response_status = Some(response.to_string());
+        }
+        let patch = api::Event {
+            attendees: Some(attendees.clone()),
+            ..Default::default()
+        };
+        let mut local = event.clone();
+        local.attendees = Some(attendees);
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Responding".to_string(), StatusColor::Yellow);
+        let before = audit::payload(&event);
+        let after = audit::payload(&patch);
+        let edit_id = self.begin_edit(
+            calendar_id.clone(),
+            merge::LocalChange::Update(local),
+            event.updated,
+        );
+        let ack_tx = self.edit_ack_tx.clone();
+        wakeup::spawn(async move {
+            let result = hub
+                .events()
+                .patch(patch, &calendar_id, &event_id)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "respond_event",
+                &format!("{calendar_id}/{event_id}"),
+                before,
+                after,
+                &result.as_ref().map(|_| ()),
+            );
+            let _ = ack_tx.send(edit_id).await;
+            let msg = match result {
+                Ok(_) => (format!("Invitation {response}"), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    fn open_selected_starred(&mut self) {
+        let Some((date, (event, _))) = self.starred_events().get(self.cursor_line).cloned() else {
+            return;
+        };
+        let position = self.events_on(date).position(|(e, _)| e.id == event.id);
+        self.current_date = date;
+        self.app_layout = MainArea::Events;
+        self.cursor_line = position.unwrap_or(0);
+    }
+
+    // A day's events in start order
+    fn events_on(&self, date: NaiveDate) -> impl Iterator<Item = &(api::Event, String)> {
+        let events = self.events_cache.get(&date);
+        self.day_index
+            .day(date)
+            .iter()
+            .filter_map(move |r| events?.get(r.index))
+    }
+
+    // Days past the cache horizon are only in the archive, which the compare view loads
+    fn history_on(&self, date: NaiveDate) -> Vec<&(api::Event, String)> {
+        match self.archive.get(&date) {
+            Some(events) if self.day_index.day(date).is_empty() => events.iter().collect(),
+            _ => self.events_on(date).collect(),
+        }
+    }
+
+    fn history_week(&self, start: NaiveDate) -> impl Iterator<Item = &(api::Event, String)> {
+        (0..7).flat_map(move |d| self.history_on(start + Days::new(d)))
+    }
+
+    // Events on the days in `dates`, day by day in start order
+    fn events_between(
+        &self,
+        dates: std::ops::RangeInclusive<NaiveDate>,
+    ) -> impl Iterator<Item = (NaiveDate, &(api::Event, String))> {
+        self.day_index.range(dates).flat_map(move |(date, refs)| {
+            let events = self.events_cache.get(&date);
+            refs.iter()
+                .filter_map(move |r| Some((date, events?.get(r.index)?)))
+        })
+    }
+
+    // All-day events that started before a day in `dates` and still cover it; the cache only
+    // lists them under their start day
+    fn continuing_events(&self, dates: std::ops::RangeInclusive<NaiveDate>) -> Continuing<'_> {
+        let mut continuing: Continuing = HashMap::new();
+        let first = *dates.start() - Days::new(MAX_SPAN_DAYS);
+        for (start, entry) in self.events_between(first..=*dates.end()) {
+            let span = events::all_day_span(&entry.0);
+            for d in 1..span {
+                let date = start + chrono::Duration::days(d);
+                if dates.contains(&date) {
+                    continuing
+                        .entry(date)
+                        .or_default()
+                        .push((entry, d + 1 < span));
+                }
+            }
+        }
+        continuing
+    }
+
+    // A row of a day's event list: number for 1-9, times, title and markers
+    fn event_list_item(
+        &self,
+        i: usize,
+        ev: &(api::Event, String),
+        series_titles: &HashMap<String, String>,
+        selected: bool,
+    ) -> ratatui::widgets::ListItem<'static> {
+        let title = format!(
+            "{}{}",
+            events::response_marker(&ev.0),
+            self.display_title(ev)
+        );
+        let start_time =
+            ev.0.start
+                .as_ref()
+                .and_then(|s| s.date_time)
+                .map(|dt| {
+                    format!(
+                        " {} ",
+                        dt.with_timezone(&self.app_tz)
+                            .format(self.config.display.time_format())
+                    )
+                })
+                .unwrap_or(" ".to_string());
+        let end_time =
+            ev.0.end
+                .as_ref()
+                .and_then(|s| s.date_time)
+                .map(|dt| {
+                    format!(
+                        "- {} ",
+                        dt.with_timezone(&self.app_tz)
+                            .format(self.config.display.time_format())
+                    )
+                })
+                .unwrap_or("".to_string());
+        let mut title = bidi::display(&title);
+        // Mark events whose join link `c` will copy
+        if events::meet_link(&ev.0).is_some() {
+            title.push_str(" [Meet]");
+        }
+        if let Some(note) =
+            events::exception_note(&ev.0, series_titles, self.app_tz, &self.config.display)
+        {
+            title.push_str(&format!(" * ({note})"));
+        }
+        // 1-9 jump straight to the first nine
+        let number = if i < 9 {
+            (i + 1).to_string()
+        } else {
+            " ".to_string()
+        };
+        let kind = events::EventKind::of(&ev.0);
+        let mut item = if kind.is_background() {
+            ratatui::widgets::ListItem::new(format!(
+                "{number}{start_time}{end_time}[{}] {title}",
+                kind.label()
+            ))
+            .fg(kind.color())
+        } else {
+            let item =
+                ratatui::widgets::ListItem::new(format!("{number}{start_time}{end_time}{title}"));
+            match self.calendar_color(&ev.1) {
+                Some(color) => item.fg(color),
+                None => item,
+            }
+        };
+        if self.ended_earlier_today(&ev.0) {
+            item = item.dark_gray();
+        }
+        item = item.add_modifier(events::response_modifier(&ev.0));
+        if selected {
+            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+        };
+        item
+    }
+
+    // The selected day's events beside the grid with display.day_panel, so browsing days
+    // needs no popup
+    fn render_day_panel(
+        &self,
+        area: Rect,
+        series_titles: &HashMap<String, String>,
+        buf: &mut Buffer,
+    ) {
+        let items: Vec<ratatui::widgets::ListItem> = self
+            .events_on(self.current_date)
+            .enumerate()
+            .map(|(i, ev)| self.event_list_item(i, ev, series_titles, false))
+            .collect();
+        let mut block = Block::bordered().title(format!(
+            "{} {}",
+            self.locale.t("Events"),
+            self.current_date.format(&self.config.display.date_format)
+        ));
+        if let Some(sun) = self.sun_label(self.current_date) {
+            block = block.title_bottom(Line::from(sun).right_aligned());
+        }
+        ratatui::widgets::List::new(items)
+            .block(block)
+            .render(area, buf);
+    }
+
+    // The selected day's events that pass the Events popup filters
+    fn current_day_events(&self) -> Vec<&(api::Event, String)> {
+        self.events_on(self.current_date)
+            .filter(|(event, calendar_id)| self.event_filter.matches(event, calendar_id))
+            .collect()
+    }
+
+    // Steps through the calendars with events on this day, then back to all
+    fn cycle_calendar_filter(&mut self) {
+        let mut calendars: Vec<&String> = self
+            .events_on(self.current_date)
+            .map(|(_, calendar_id)| calendar_id)
+            .collect();
+        calendars.sort();
+        calendars.dedup();
+        let next = match &self.event_filter.calendar {
+            None => calendars.first(),
+            Some(current) => calendars
+                .iter()
+                .position(|c| *c == current)
+                .and_then(|i| calendars.get(i + 1)),
+        };
+        self.event_filter.calendar = next.map(|c| c.to_string());
+        self.cursor_line = 0;
+    }
+
+    fn cycle_response_filter(&mut self) {
+        let statuses = events::RESPONSE_STATUSES;
+        self.event_filter.response = match self.event_filter.response {
+            None => Some(statuses[0]),
+            Some(current) => statuses
+                .iter()
+                .position(|s| *s == current)
+                .and_then(|i| statuses.get(i + 1))
+                .copied(),
+        };
+        self.cursor_line = 0;
+    }
+
+    fn event_filter_label(&self) -> String {
+        let mut label = String::new();
+        if let Some(calendar_id) = &self.event_filter.calendar {
+            label.push_str(&format!(" [{}]", self.calendar_name(calendar_id)));
+        }
+        if let Some(response) = self.event_filter.response {
+            label.push_str(&format!(" [{response}]"));
+        }
+        if let Some(text) = &self.event_filter.text {
+            label.push_str(&format!(" [/{text}]"));
+        }
+        label
+    }
+
+    fn selected_event_index(&self) -> Option<usize> {
+        let events = self.current_day_events();
+        if events.is_empty() {
+            return None;
+        }
+
+        let idx = self.cursor_line;
+        if idx < events.len() {
+            Some(idx)
+        } else {
+            Some(events.len().saturating_sub(1))
+        }
+    }
+
+    fn selected_task_index(&self) -> Option<usize> {
+        if self.tasks_cache.is_empty() {
+            return None;
+        }
+
+        let idx = self.cursor_line;
+        if idx < self.tasks_cache.len() {
+            Some(idx)
+        } else {
+            Some(self.tasks_cache.len().saturating_sub(1))
+        }
+    }
+
+    // Event title after any display_title script has had its say
+    fn display_title(&self, event: &(api::Event, String)) -> String {
+        let title = event.0.summary.as_deref().unwrap_or("Untitled");
+        let calendar = self
+            .calendars_cache
+            .get(&event.1)
+            .and_then(|c| c.summary.as_deref())
+            .unwrap_or(&event.1);
+        let title = self
+            .scripts
+            .display_title(title, calendar)
+            .unwrap_or(title.to_string());
+        let title = if vault::is_private(&event.1) {
+            format!("🔒 {title}")
+        } else {
+            title
+        };
+        let title = match self.rain_risk(&event.0) {
+            Some(pct) => format!("☂{pct}% {title}"),
+            None => title,
+        };
+        if self.is_starred(&event.0) {
+            format!("★ {title}")
+        } else {
+            title
+        }
+    }
+
+    // Chance of rain in percent for an outdoor-looking event, when it reaches the warning level
+    fn rain_risk(&self, event: &api::Event) -> Option<u8> {
+        let weather = self.onecall_weather.as_ref()?;
+        if !weather::looks_outdoor(
+            event.summary.as_deref().unwrap_or_default(),
+            event.location.as_deref().unwrap_or_default(),
+            event.description.as_deref().unwrap_or_default(),
+        ) {
+            return None;
+        }
+        let start = reminders::event_start(event, self.app_tz)?;
+        if start < Local::now() {
+            return None;
+        }
+        let all_day = event.start.as_ref().is_some_and(|s| s.date.is_some());
+        let pct = (weather::pop_at(weather, start, all_day)? * 100.0).round() as u8;
+        (pct >= self.config.rain_warning_pct).then_some(pct)
+    }
+
+    fn selected_event(&self) -> Option<&(api::Event, String)> {
+        let idx = self.selected_event_index()?;
+        self.current_day_events().get(idx).copied()
+    }
+
+    fn selected_task(&self) -> Option<&(Task, String)> {
+        let idx = self.selected_task_index()?;
+        self.tasks_cache.get(idx)
+    }
+
+    fn start_background_refresh(&mut self) {
+        self.start_background_event_fetch();
+        self.start_background_task_fetch();
+        self.start_background_weather_fetch();
+        self.start_background_feed_fetch();
+        self.start_background_issue_fetch();
+    }
+
+    fn start_imap_watcher(&mut self) {
+        let (Some(config), Some(hub)) = (self.config.imap.clone(), self.task_hub.clone()) else {
+            return;
+        };
+        // It only ever creates tasks
+        if calendar_auth::access() == config::Access::ReadOnly {
+            tracing::warn!("not watching mail: signed in read-only");
+            return;
+        }
+        let network = self.config.network.clone();
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        tokio::spawn(imap::watch(config, network, hub, tx));
+    }
+
+    fn start_background_issue_fetch(&mut self) {
+        self.last_issue_fetch = Some(std::time::Instant::now());
+        let config = self.config.issues.clone();
+        if config.github.is_none() && config.jira.is_none() {
+            return;
+        }
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.issues_rx = Some(rx);
+        wakeup::spawn(async move {
+            let _ = tx.send(issues::fetch(&config, net::timeout()).await).await;
+        });
+    }
+
+    fn apply_issue_tasks(&mut self) {
+        self.tasks_cache
+            .retain(|(_, tasklist_id)| !issues::is_issue(tasklist_id));
+        self.tasks_cache.extend(self.issue_tasks.iter().cloned());
+        self.tasks_cache.sort_by_key(|t| {
+            (
+                t.0.status.clone().unwrap_or_default(),
+                t.0.due.clone().unwrap_or_default(),
+            )
+        });
+    }
+
+    // Issue tasks belong to their tracker; say so instead of sending them to Google
+    fn selected_task_is_read_only(&mut self) -> bool {
+        let read_only = self
+            .selected_task()
+            .is_some_and(|(_, tasklist_id)| issues::is_issue(tasklist_id));
+        if read_only {
+            self.changing_status = (
+                "Issues are read-only here; O opens them".to_string(),
+                StatusColor::Red,
+            );
+        }
+        read_only
+    }
+
+    fn open_selected_in_browser(&mut self) {
+        let link = match self.app_layout {
+            MainArea::Tasks(_) => self
+                .selected_task()
+                .and_then(|(task, _)| task.web_view_link.clone()),
+            MainArea::Events => self
+                .selected_event()
+                .and_then(|(event, _)| event.html_link.clone()),
+            _ => None,
+        };
+        let Some(link) = link else {
+            return;
+        };
+        if !browser::open(&link) {
+            self.changing_status = ("No browser opener found".to_string(), StatusColor::Red);
+        }
+    }
+
+    fn start_background_feed_fetch(&mut self) {
+        self.last_feed_fetch = Some(std::time::Instant::now());
+        if self.config.feeds.is_empty() {
+            return;
+        }
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.feeds_rx = Some(rx);
+        let feeds = self.config.feeds.clone();
+        let app_tz = self.app_tz;
+        wakeup::spawn(async move {
+            let mut results = Vec::new();
+            for feed in &feeds {
+                let result = ics::fetch_feed(feed, app_tz, net::timeout())
+                    .instrument(tracing::info_span!("fetch_feed", feed = feed.name))
+                    .await
+                    .inspect_err(|e| tracing::warn!("{e}"))
+                    .map_err(|e| format!("Feed {} failed: {e}", feed.name));
+                results.push((ics::feed_id(feed), result));
+            }
+            let _ = tx.send(results).await;
+        });
+    }
+
+    fn start_background_birthday_fetch(&mut self) {
+        let Some(hub) = self.event_hub.clone() else {
+            return;
+        };
+        self.last_birthday_fetch = Some(std::time::Instant::now());
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.birthdays_rx = Some(rx);
+        wakeup::spawn(async move {
+            let result = birthdays::fetch(&hub, net::timeout())
+                .instrument(tracing::info_span!("fetch_birthdays"))
+                .await
+                .inspect(|list| birthdays::save_cached(list))
+                .inspect_err(|e| tracing::warn!("birthdays: {e}"))
+                .map_err(|e| format!("Birthdays failed: {e}"));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    // Like the feeds, laid over the cache and never saved with it. Google's own birthday
+    // events would only repeat them.
+    fn apply_birthday_events(&mut self) {
+        if !self.config.birthdays.contacts {
+            return;
+        }
+        for events in self.events_cache.values_mut() {
+            events.retain(|(event, calendar_id)| {
+                !birthdays::is_birthday(calendar_id)
+                    && (self.birthdays.is_empty()
+                        || event.event_type.as_deref() != Some("birthday"))
+            });
+        }
+        for (date, events) in birthdays::events(&self.birthdays, self.today) {
+            self.events_cache.entry(date).or_default().extend(events);
+        }
+        self.day_index = day_index::DayIndex::build(&self.events_cache, self.app_tz);
+    }
+
+    fn upcoming_birthdays(&self) -> Vec<(NaiveDate, &birthdays::Birthday)> {
+        birthdays::upcoming(&self.birthdays, self.today)
+    }
+
+    fn open_selected_birthday(&mut self) {
+        let Some((date, _)) = self.upcoming_birthdays().get(self.cursor_line).copied() else {
+            return;
+        };
+        self.current_date = date;
+        self.app_layout = MainArea::Calendar;
+    }
+
+    fn toggle_free_busy_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::FreeBusy => MainArea::Calendar,
+            _ => MainArea::FreeBusy,
+        };
+    }
+
+    // What takes up time on `date` across every shown calendar, clipped to the day:
+    // (start, end, tentative). Declined, free-marked and working location events don't.
+    fn busy_on(&self, date: NaiveDate) -> Vec<(NaiveDateTime, NaiveDateTime, bool)> {
+        let (day_start, day_end) = (
+            date.and_hms_opt(0, 0, 0).unwrap(),
+            date.and_hms_opt(0, 0, 0).unwrap() + Days::new(1),
+        );
+        // Yesterday's events can run past midnight
+        [date.pred_opt().unwrap_or(date), date]
+            .into_iter()
+            .flat_map(|d| self.events_on(d))
+            .filter(|(ev, _)| {
+                events::EventKind::of(ev) != events::EventKind::WorkingLocation
+                    && ev.transparency.as_deref() != Some("transparent")
+                    && events::response_status(ev) != "declined"
+            })
+            .filter_map(|(ev, _)| {
+                let start = ev.start.as_ref()?.date_time?.with_timezone(&self.app_tz);
+                let end = ev.end.as_ref()?.date_time?.with_timezone(&self.app_tz);
+                let (start, end) = (
+                    start.naive_local().max(day_start),
+                    end.naive_local().min(day_end),
+                );
+                (start < end).then(|| (start, end, events::response_status(ev) == "tentative"))
+            })
+            .collect()
+    }
+
+    // Gaps of half an hour or more between `from` and `to` on `date`; maybes count as busy
+    fn free_on(
+        &self,
+        date: NaiveDate,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let busy: Vec<_> = self
+            .busy_on(date)
+            .into_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect();
+        scheduling::free_between(&busy, from, to, chrono::Duration::minutes(30))
+    }
+
+    // A bar a day for the selected week, hours across, busy time filled in, with the free
+    // working hours spelled out under each
+    fn render_free_busy(&self, area: Rect, buf: &mut Buffer) {
+        let week_start = self.current_date
+            - Days::new(self.current_date.weekday().num_days_from_sunday() as u64);
+        let block = Block::bordered()
+            .title(format!(
+                "{} {}",
+                self.locale.t("Free/busy"),
+                week_start.format(&self.config.display.date_format)
+            ))
+            .title_bottom(Line::from(vec![
+                Span::raw("█ busy ").fg(self.theme.error),
+                Span::raw("▒ maybe ").fg(self.theme.warning),
+                Span::raw("░ free").fg(self.theme.ok),
+            ]));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [labels, bars] =
+            Layout::horizontal([Constraint::Length(11), Constraint::Fill(1)]).areas(inner);
+        if bars.width == 0 {
+            return;
+        }
+        let (first_hour, last_hour) = FREE_BUSY_HOURS;
+        let minutes = ((last_hour - first_hour) * 60) as f64;
+        let column_minutes = minutes / bars.width as f64;
+
+        // Hour ruler, every other hour where there's room
+        let every = if bars.width as u32 / (last_hour - first_hour) >= 3 {
+            1
         } else {
-            5
+            2
         };
+        for hour in (first_hour..last_hour).step_by(every) {
+            let x = ((hour - first_hour) as f64 * 60.0 / column_minutes) as u16;
+            Span::raw(format!("{hour}")).fg(self.theme.dimmed).render(
+                Rect {
+                    x: bars.x + x,
+                    width: bars.width.saturating_sub(x).min(3),
+                    height: 1,
+                    ..bars
+                },
+                buf,
+            );
+        }
 
-        let mut grid = Vec::new();
+        let weekdays = self.locale.weekdays();
+        for d in 0..7u64 {
+            let y = inner.y + 1 + d as u16 * 3;
+            if y >= inner.bottom() {
+                break;
+            }
+            let date = week_start + Days::new(d);
+            let mut label = Span::raw(format!(
+                "{} {}",
+                weekdays[d as usize],
+                date.format("%-m/%-d")
+            ));
+            if date == self.today {
+                label = label.fg(self.theme.today);
+            }
+            if date == self.current_date {
+                label = label.bg(self.theme.selected).bold();
+            }
+            label.render(
+                Rect {
+                    y,
+                    height: 1,
+                    ..labels
+                },
+                buf,
+            );
+
+            let busy = self.busy_on(date);
+            let (work_start, work_end) = scheduling::workday(date);
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+            let bar: Vec<Span> = (0..bars.width)
+                .map(|x| {
+                    let from = midnight
+                        + chrono::Duration::minutes(
+                            (first_hour * 60) as i64 + (x as f64 * column_minutes) as i64,
+                        );
+                    let to = midnight
+                        + chrono::Duration::minutes(
+                            (first_hour * 60) as i64 + ((x + 1) as f64 * column_minutes) as i64,
+                        );
+                    let overlapping = busy
+                        .iter()
+                        .filter(|(start, end, _)| *start < to && *end > from);
+                    let mut tentative_only = None;
+                    for (_, _, tentative) in overlapping {
+                        tentative_only = Some(tentative_only.unwrap_or(true) && *tentative);
+                    }
+                    match tentative_only {
+                        Some(false) => Span::raw("█").fg(self.theme.error),
+                        Some(true) => Span::raw("▒").fg(self.theme.warning),
+                        None if from >= work_start && to <= work_end => {
+                            Span::raw("░").fg(self.theme.ok)
+                        }
+                        None => Span::raw("·").fg(self.theme.dimmed),
+                    }
+                })
+                .collect();
+            Line::from(bar).render(
+                Rect {
+                    y,
+                    height: 1,
+                    ..bars
+                },
+                buf,
+            );
+
+            if y + 1 >= inner.bottom() {
+                break;
+            }
+            let time = |t: NaiveDateTime| t.format(self.config.display.time_format()).to_string();
+            let free: Vec<String> = self
+                .free_on(date, work_start, work_end)
+                .into_iter()
+                .map(|(start, end)| format!("{}–{}", time(start), time(end)))
+                .collect();
+            let free = if free.is_empty() {
+                "—".to_string()
+            } else {
+                free.join("  ")
+            };
+            Span::raw(format!("{} {free}", self.locale.t("free")))
+                .fg(self.theme.dimmed)
+                .render(
+                    Rect {
+                        y: y + 1,
+                        height: 1,
+                        ..bars
+                    },
+                    buf,
+                );
+        }
+    }
 
-        // Generate 6 weeks (42 days total)
-        for week in 0..6 {
-            let mut week_days = Vec::new();
-            for day in 0..7 {
-                let drawing_date = start_date + chrono::Duration::days((week * 7 + day) as i64);
-                // Check if this date is in the current month
-                let is_current_month = drawing_date.month() == current_month;
+    fn toggle_birthdays_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Birthdays => MainArea::Calendar,
+            _ => MainArea::Birthdays,
+        };
+        self.cursor_line = 0;
+    }
 
-                // Check if this date is today
-                let is_today = drawing_date == self.today;
-                week_days.push((drawing_date, is_current_month, is_today));
+    // Feed events are never cached or merged, only laid over whatever Google returned
+    fn apply_feed_events(&mut self) {
+        for events in self.events_cache.values_mut() {
+            events.retain(|(_, calendar_id)| !ics::is_feed(calendar_id));
+        }
+        for feed in self.feed_events.values() {
+            for (date, events) in feed {
+                self.events_cache
+                    .entry(*date)
+                    .or_default()
+                    .extend(events.iter().cloned());
             }
-            grid.push(week_days);
         }
-        (grid, number_of_rows)
+        self.day_index = day_index::DayIndex::build(&self.events_cache, self.app_tz);
     }
 
-    fn current_day_events(&self) -> &[(api::Event, String)] {
-        static EMPTY: Vec<(api::Event, String)> = Vec::new();
-        self.events_cache
-            .get(&self.current_date)
-            .map(|v| v.as_slice())
-            .unwrap_or(&EMPTY)
+    fn apply_private_events(&mut self) {
+        for events in self.events_cache.values_mut() {
+            events.retain(|(_, calendar_id)| !vault::is_private(calendar_id));
+        }
+        for event in &self.private_events {
+            merge::insert(
+                &mut self.events_cache,
+                event.clone(),
+                vault::PRIVATE_CALENDAR.to_string(),
+                self.app_tz,
+            );
+        }
+        self.day_index = day_index::DayIndex::build(&self.events_cache, self.app_tz);
     }
 
-    fn selected_event_index(&self) -> Option<usize> {
-        let events = self.current_day_events();
-        if events.is_empty() {
-            return None;
+    // `P` asks for the passphrase, or locks again and hides them
+    fn toggle_private_events(&mut self) {
+        if self.vault.take().is_some() {
+            self.private_events.clear();
+            self.apply_private_events();
+            self.changing_status = ("Private events locked".to_string(), StatusColor::White);
+        } else {
+            self.input_target = InputTarget::Passphrase;
+            self.inputting = true;
         }
+    }
 
-        let idx = self.cursor_line;
-        if idx < events.len() {
-            Some(idx)
+    fn unlock_private_events(&mut self, passphrase: &str) {
+        match vault::Vault::unlock(passphrase) {
+            Ok((vault, events)) => {
+                self.vault = Some(vault);
+                self.private_events = events;
+                self.apply_private_events();
+                self.changing_status = ("Private events unlocked".to_string(), StatusColor::Green);
+            }
+            Err(e) => self.changing_status = (format!("Could not unlock: {e}"), StatusColor::Red),
+        }
+    }
+
+    fn create_private_event(&mut self, title: &str) {
+        if self.vault.is_none() {
+            self.changing_status = (
+                "Unlock private events with P first".to_string(),
+                StatusColor::Red,
+            );
+            return;
+        }
+        let mut event = self.event_from_input(title);
+        event.id = Some(format!("private{}", Utc::now().timestamp_millis()));
+        // Nothing to join without Google
+        event.conference_data = None;
+        self.private_events.push(event);
+        self.save_private_events();
+    }
+
+    fn save_private_events(&mut self) {
+        let Some(vault) = &self.vault else {
+            return;
+        };
+        self.changing_status = match vault.save(&self.private_events) {
+            Ok(()) => ("Saved privately".to_string(), StatusColor::Green),
+            Err(e) => (format!("Failed: {e}"), StatusColor::Red),
+        };
+        self.apply_private_events();
+    }
+
+    // Config tables are keyed by calendar id or by the name in Google
+    fn calendar_config<'a, T>(
+        &self,
+        table: &'a HashMap<String, T>,
+        calendar_id: &str,
+    ) -> Option<&'a T> {
+        let name = self
+            .calendars_cache
+            .get(calendar_id)
+            .and_then(|c| c.summary.as_deref());
+        events::calendar_entry(table, calendar_id, name)
+    }
+
+    fn calendar_override(&self, calendar_id: &str) -> Option<&config::CalendarOverride> {
+        self.calendar_config(&self.config.calendars, calendar_id)
+    }
+
+    // The local name from [calendars] over Google's
+    fn calendar_name(&self, calendar_id: &str) -> String {
+        self.calendar_override(calendar_id)
+            .and_then(|o| o.name.clone())
+            .or_else(|| {
+                self.calendars_cache
+                    .get(calendar_id)
+                    .and_then(|c| c.summary.clone())
+            })
+            .or_else(|| birthdays::is_birthday(calendar_id).then(|| "Birthdays".to_string()))
+            .unwrap_or_else(|| calendar_id.to_string())
+    }
+
+    // Tells why, when what's being done needs more than [auth] access grants
+    fn refuse_access(&mut self, needs: config::Access) -> bool {
+        let reason = match (calendar_auth::access(), needs) {
+            (config::Access::ReadOnly, _) => "Signed in read-only",
+            (config::Access::Events, config::Access::Full) => {
+                "Needs access = \"full\" under [auth]"
+            }
+            _ => return false,
+        };
+        self.changing_status = (reason.to_string(), StatusColor::Red);
+        true
+    }
+
+    // Why events in this calendar can't be changed from here, if they can't
+    fn read_only_reason(&self, calendar_id: &str) -> Option<&'static str> {
+        let name = self
+            .calendars_cache
+            .get(calendar_id)
+            .and_then(|c| c.summary.as_deref());
+        events::read_only_reason(&self.config, calendar_id, name)
+    }
+
+    // [calendars] or [calendar_colors] by id or name, then the feed's color, then the
+    // calendar's own in Google
+    fn calendar_color(&self, calendar_id: &str) -> Option<Color> {
+        let calendar = self.calendars_cache.get(calendar_id);
+        let configured = self
+            .calendar_override(calendar_id)
+            .and_then(|o| o.color.as_ref())
+            .or_else(|| self.calendar_config(&self.config.calendar_colors, calendar_id));
+        let feed = || {
+            self.config
+                .feeds
+                .iter()
+                .find(|feed| ics::feed_id(feed) == calendar_id)?
+                .color
+                .as_ref()
+        };
+        let birthdays =
+            || Some(&self.config.birthdays.color).filter(|_| birthdays::is_birthday(calendar_id));
+        let google = || {
+            calendar
+                .filter(|_| self.config.display.google_colors)?
+                .background_color
+                .as_ref()
+        };
+        configured
+            .or_else(feed)
+            .or_else(birthdays)
+            .or_else(google)?
+            .parse()
+            .ok()
+    }
+
+    // Configured coordinates, else wherever the weather was fetched for
+    fn location(&self) -> Option<(f64, f64)> {
+        match (self.config.latitude, self.config.longitude) {
+            (Some(lat), Some(lon)) => Some((lat, lon)),
+            _ => self.onecall_weather.as_ref().map(|w| (w.lat, w.lon)),
+        }
+    }
+
+    fn sun_label(&self, date: NaiveDate) -> Option<String> {
+        let (lat, lon) = self.location()?;
+        let format = self.config.display.time_format();
+        Some(match sun::sun_times(date, lat, lon) {
+            Some((rise, set)) => {
+                let length = set - rise;
+                format!(
+                    "☀ {} – {} ({}h{:02}m)",
+                    rise.with_timezone(&self.app_tz).format(format),
+                    set.with_timezone(&self.app_tz).format(format),
+                    length.num_hours(),
+                    length.num_minutes() % 60
+                )
+            }
+            None if sun::daylight_fraction(date, lat, lon) > 0.5 => "☀ 24h".to_string(),
+            None => "☾ 0h".to_string(),
+        })
+    }
+
+    // Sunday of the selected week and of the week it is compared against
+    fn compare_week_starts(&self) -> (NaiveDate, NaiveDate) {
+        let this_week = self.current_date
+            - Days::new(self.current_date.weekday().num_days_from_sunday() as u64);
+        (this_week, this_week - Days::new(7 * self.compare_weeks))
+    }
+
+    // Temperature and chance of rain hour by hour for the day on the left of the forecast
+    fn render_hourly(
+        &self,
+        weather: &weather::OneCallResponse,
+        date: NaiveDate,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let hours: Vec<(u32, &weather::HourlyWeather)> = weather
+            .hourly
+            .iter()
+            .filter_map(|h| {
+                let time = DateTime::from_timestamp(h.dt, 0)?.with_timezone(&self.app_tz);
+                (time.date_naive() == date).then_some((time.hour(), h))
+            })
+            .collect();
+        let title = date
+            .format(&self.locale.localize_format("%A, %B %d", &date))
+            .to_string();
+        let block = Block::bordered().title(format!("{} · {title}", self.locale.t("Hourly")));
+        if hours.is_empty() {
+            Paragraph::new(self.locale.t("No hourly forecast this far ahead"))
+                .centered()
+                .dim()
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+        let temps: Vec<f64> = hours.iter().map(|(_, h)| h.temp).collect();
+        let (low, high) = temps
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), t| (lo.min(*t), hi.max(*t)));
+        let pops: Vec<f64> = hours.iter().map(|(_, h)| h.pop).collect();
+        let row = |label: &str, glyphs: Vec<char>| -> String {
+            glyphs
+                .into_iter()
+                .fold(format!("{:<12}", self.locale.t(label)), |mut line, g| {
+                    line.extend([g, g, ' ']);
+                    line
+                })
+        };
+        let lines = vec![
+            Line::raw(format!(
+                "{} {low:.0}–{high:.0}°C",
+                row("Temperature", weather::sparkline(&temps, low, high))
+            ))
+            .yellow(),
+            Line::raw(row("Chance of rain", weather::sparkline(&pops, 0.0, 1.0))).blue(),
+            Line::raw(hours.iter().fold(" ".repeat(12), |mut line, (hour, _)| {
+                line.push_str(&format!("{hour:02} "));
+                line
+            }))
+            .dim(),
+        ];
+        Paragraph::new(lines)
+            .block(block.padding(ratatui::widgets::Padding::horizontal(1)))
+            .render(area, buf);
+    }
+
+    // The selected week next to an earlier one, day by day. Titles only on one side are
+    // highlighted: green for new this week, red for ones that are gone.
+    fn render_compare(&self, area: Rect, buf: &mut Buffer) {
+        let (this_week, other_week) = self.compare_week_starts();
+        let titles = |start: NaiveDate| -> HashSet<String> {
+            self.history_week(start)
+                .filter_map(|(ev, _)| ev.summary.clone())
+                .collect()
+        };
+        let (this_titles, other_titles) = (titles(this_week), titles(other_week));
+
+        let columns = Layout::new(
+            Direction::Horizontal,
+            Constraint::from_percentages([50, 50]),
+        )
+        .split(area);
+        let weekdays = self.locale.weekdays();
+        for (start, column, others, color) in [
+            (this_week, columns[0], &other_titles, Color::Green),
+            (other_week, columns[1], &this_titles, Color::Red),
+        ] {
+            Clear.render(column, buf);
+            let mut lines = Vec::new();
+            let mut scheduled = chrono::Duration::zero();
+            let mut count = 0;
+            for d in 0..7 {
+                let date = start + Days::new(d);
+                lines.push(
+                    Line::raw(format!(
+                        "{} {}",
+                        weekdays[d as usize],
+                        date.format(&self.config.display.date_format)
+                    ))
+                    .bold(),
+                );
+                for ev in self.history_on(date) {
+                    count += 1;
+                    let start_time = ev.0.start.as_ref().and_then(|s| s.date_time);
+                    let end_time = ev.0.end.as_ref().and_then(|e| e.date_time);
+                    if let (Some(s), Some(e)) = (start_time, end_time) {
+                        scheduled += e - s;
+                    }
+                    let time = start_time
+                        .map(|s| {
+                            s.with_timezone(&self.app_tz)
+                                .format(self.config.display.time_format())
+                                .to_string()
+                        })
+                        .unwrap_or_default();
+                    let line = Line::raw(format!(
+                        "  {time:<7}{}",
+                        bidi::display(&self.display_title(ev))
+                    ));
+                    let changed = ev.0.summary.as_ref().is_some_and(|t| !others.contains(t));
+                    lines.push(if changed { line.fg(color) } else { line });
+                }
+            }
+            Paragraph::new(lines)
+                .block(
+                    Block::bordered()
+                        .title(format!(
+                            "{} {}",
+                            self.locale.t("Week"),
+                            start.format(&self.config.display.date_format)
+                        ))
+                        .title_bottom(format!(
+                            "{count} {}, {}h{:02}m",
+                            self.locale.t("Events"),
+                            scheduled.num_hours(),
+                            scheduled.num_minutes() % 60
+                        )),
+                )
+                .render(column, buf);
+        }
+    }
+
+    // Last, this and next month in miniature, for bearings beside the day and week views
+    fn render_mini_months(&self, area: Rect, buf: &mut Buffer) {
+        let first = self.first_day_of_month();
+        let months = [first - Months::new(1), first, first + Months::new(1)];
+        let areas = Layout::vertical([Constraint::Length(MINI_MONTH_HEIGHT); 3]).split(area);
+        // Two columns a name, as CJK names are already that wide
+        let header: Vec<Span> = self
+            .locale
+            .weekdays()
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let mut name: String = name.chars().take(2).collect();
+                while Span::raw(name.as_str()).width() < 2 {
+                    name.push(' ');
+                }
+                let span = Span::raw(format!("{name} "));
+                match i {
+                    0 => span.fg(self.theme.sunday),
+                    6 => span.fg(self.theme.saturday),
+                    _ => span,
+                }
+            })
+            .collect();
+        for (first_day, area) in months.iter().zip(areas.iter()) {
+            let title = first_day
+                .format(
+                    &self
+                        .locale
+                        .localize_format(&self.config.display.month_format, first_day),
+                )
+                .to_string();
+            let block = Block::bordered().title(Line::from(title).centered());
+            let mut lines = vec![Line::from(header.clone())];
+            for week in self.month_grid(*first_day) {
+                let days: Vec<Span> = week
+                    .iter()
+                    .map(|(date, in_month, _)| {
+                        let mut day = Span::raw(format!("{:>2}", date.day()));
+                        let cursor = *in_month && *date == self.current_date;
+                        if let Some(background) =
+                            self.day_number_background(*date, *in_month, cursor)
+                        {
+                            day = day.bg(background);
+                        }
+                        if let Some(color) = self.day_number_color(*date, *in_month) {
+                            day = day.fg(color);
+                        }
+                        day
+                    })
+                    .flat_map(|day| [day, Span::raw(" ")])
+                    .collect();
+                lines.push(Line::from(days));
+            }
+            Paragraph::new(lines).block(block).render(*area, buf);
+        }
+    }
+
+    // Beside `area` when [display] mini_months is on; what's left for the view
+    fn with_mini_months(&self, area: Rect, buf: &mut Buffer) -> Rect {
+        if !self.config.display.mini_months || area.width < MINI_MONTHS_WIDTH * 3 {
+            return area;
+        }
+        let [months_area, rest] =
+            Layout::horizontal([Constraint::Length(MINI_MONTHS_WIDTH), Constraint::Fill(1)])
+                .areas(area);
+        Clear.render(months_area, buf);
+        self.render_mini_months(months_area, buf);
+        rest
+    }
+
+    // Hour-by-hour columns for the week around the cursor, one row per hour that fits
+    fn render_week_timeline(&self, area: Rect, buf: &mut Buffer) {
+        let week_start = self.current_date
+            - Days::new(self.current_date.weekday().num_days_from_sunday() as u64);
+        let block = Block::bordered().title(format!(
+            "{} {}",
+            self.locale.t("Week"),
+            week_start.format(&self.config.display.date_format)
+        ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 3 {
+            return;
+        }
+
+        let [gutter, days_area] = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Length(6), Constraint::Fill(1)],
+        )
+        .areas(inner);
+        let columns = Layout::new(Direction::Horizontal, [Constraint::Fill(1); 7]).split(days_area);
+        let days: Vec<NaiveDate> = (0..7).map(|d| week_start + Days::new(d)).collect();
+        // Timed events on a day with their local start and end
+        let timed =
+            |date: &NaiveDate| -> Vec<(&(api::Event, String), NaiveDateTime, NaiveDateTime)> {
+                self.events_on(*date)
+                    .filter(|(ev, _)| {
+                        events::EventKind::of(ev) != events::EventKind::WorkingLocation
+                    })
+                    .filter_map(|entry| {
+                        let start = entry.0.start.as_ref()?.date_time?;
+                        let end = entry
+                            .0
+                            .end
+                            .as_ref()
+                            .and_then(|e| e.date_time)
+                            .unwrap_or(start);
+                        Some((
+                            entry,
+                            start.with_timezone(&self.app_tz).naive_local(),
+                            end.with_timezone(&self.app_tz).naive_local(),
+                        ))
+                    })
+                    .collect()
+            };
+
+        // Start at 08:00 unless something this week begins earlier
+        let earliest = days
+            .iter()
+            .flat_map(|d| {
+                timed(d)
+                    .into_iter()
+                    .filter(|(_, start, _)| start.date() == *d)
+                    .map(|(_, start, _)| start.hour())
+            })
+            .min()
+            .unwrap_or(8);
+        let hours = (inner.height - 2) as u32;
+        let first_hour = earliest.min(8).min(24u32.saturating_sub(hours));
+
+        Span::raw(self.locale.t("All day")).dark_gray().render(
+            Rect {
+                y: inner.y + 1,
+                height: 1,
+                ..gutter
+            },
+            buf,
+        );
+        for h in 0..hours.min(24) {
+            Span::raw(format!("{:02}:00", first_hour + h))
+                .dark_gray()
+                .render(
+                    Rect {
+                        y: inner.y + 2 + h as u16,
+                        height: 1,
+                        ..gutter
+                    },
+                    buf,
+                );
+        }
+
+        let weekdays = self.locale.weekdays();
+        for (i, (date, column)) in days.iter().zip(columns.iter()).enumerate() {
+            let width = column.width.saturating_sub(1) as usize;
+            let mut header = Span::raw(format!("{} {}", weekdays[i], date.day()));
+            if *date == self.current_date {
+                header = header.reversed();
+            } else if *date == self.today {
+                header = header.green();
+            }
+            header.render(
+                Rect {
+                    height: 1,
+                    ..*column
+                },
+                buf,
+            );
+
+            let all_day: Vec<String> = self
+                .events_on(*date)
+                .filter(|(ev, _)| ev.start.as_ref().is_some_and(|s| s.date.is_some()))
+                .map(|ev| self.display_title(ev))
+                .collect();
+            Span::raw(bidi::truncate_display(&all_day.join(", "), width)).render(
+                Rect {
+                    y: column.y + 1,
+                    height: 1,
+                    ..*column
+                },
+                buf,
+            );
+
+            let events = timed(date);
+            let protected: Vec<_> =
+                scheduling::protected_on(&self.config.protected, *date).collect();
+            for h in 0..hours.min(24) {
+                let hour = first_hour + h;
+                let row = Rect {
+                    y: column.y + 2 + h as u16,
+                    height: 1,
+                    ..*column
+                };
+                // Starts in this hour get the title; ones still running get a bar
+                let hour_start = date.and_hms_opt(hour, 0, 0).unwrap();
+                let hour_end = hour_start + chrono::Duration::hours(1);
+                if protected
+                    .iter()
+                    .any(|(_, start, end)| *start < hour_end && *end > hour_start)
+                {
+                    Span::raw("░".repeat(width)).dark_gray().render(row, buf);
+                }
+                if let Some((ev, _, _)) = events
+                    .iter()
+                    .find(|(_, start, _)| *start >= hour_start && *start < hour_end)
+                {
+                    Span::raw(bidi::truncate_display(&self.display_title(ev), width))
+                        .fg(events::EventKind::of(&ev.0).color())
+                        .render(row, buf);
+                } else if events
+                    .iter()
+                    .any(|(_, start, end)| *start < hour_start && *end > hour_start)
+                {
+                    Span::raw("│").dark_gray().render(row, buf);
+                }
+            }
+        }
+    }
+
+    fn render_help(&self, area: Rect, scroll: u16, buf: &mut Buffer) {
+        let area = area.centered(Constraint::Max(72), Constraint::Percentage(90));
+        Clear.render(area, buf);
+        let mut lines = Vec::new();
+        for (title, entries) in help::SECTIONS {
+            if !lines.is_empty() {
+                lines.push(Line::raw(""));
+            }
+            lines.push(Line::raw(self.locale.t(title)).yellow().bold());
+            for (keys, what) in *entries {
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {keys:<30}")).cyan(),
+                    Span::raw(*what),
+                ]));
+            }
+        }
+        // Stop once the last line reaches the bottom
+        let visible = area.height.saturating_sub(2);
+        let scroll = scroll.min((lines.len() as u16).saturating_sub(visible));
+        Paragraph::new(lines)
+            .scroll((scroll, 0))
+            .block(
+                Block::bordered()
+                    .title(self.locale.t("Help"))
+                    .title_bottom("j/k: scroll  Esc: close"),
+            )
+            .render(area, buf);
+    }
+
+    // Everything the cache has on the selected event, over the Events list
+    fn render_event_details(&self, area: Rect, buf: &mut Buffer) {
+        let Some(entry) = self.selected_event() else {
+            return;
+        };
+        let (event, calendar_id) = entry;
+        let calendar = if vault::is_private(calendar_id) {
+            self.locale.t("Private").to_string()
         } else {
-            Some(events.len().saturating_sub(1))
+            self.calendar_name(calendar_id)
+        };
+        let area = area.centered(Constraint::Percentage(70), Constraint::Percentage(80));
+        Clear.render(area, buf);
+
+        let mut lines: Vec<Line> =
+            events::detail_rows(event, &calendar, self.app_tz, &self.config.display)
+                .into_iter()
+                .map(|(label, value)| {
+                    Line::from(vec![
+                        Span::raw(format!("{:<12}", self.locale.t(label))).dark_gray(),
+                        Span::raw(bidi::display(&value)),
+                    ])
+                })
+                .collect();
+        if let Some(description) = event.description.as_deref().filter(|d| !d.is_empty()) {
+            lines.push(Line::raw(""));
+            lines.extend(
+                events::plain_description(description)
+                    .lines()
+                    .map(|line| Line::raw(bidi::display(line))),
+            );
+        }
+        Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title(bidi::display(&self.display_title(entry)))
+                    .title_bottom("Esc: close"),
+            )
+            .render(area, buf);
+    }
+
+    // Date headers with the day's events and due tasks under them; headers aren't selectable
+    fn render_agenda(&self, area: Rect, buf: &mut Buffer) {
+        let agenda = self.agenda();
+        let selected = self.cursor_line.min(agenda.len().saturating_sub(1));
+        let time_format = self.config.display.time_format();
+        let weekdays = self.locale.weekdays();
+        let mut items: Vec<ratatui::widgets::ListItem> = Vec::new();
+        let mut selected_row = 0;
+        let mut last_date = None;
+        for (i, (date, item)) in agenda.iter().enumerate() {
+            if last_date != Some(*date) {
+                last_date = Some(*date);
+                let mut header = Line::raw(format!(
+                    "{} {}",
+                    weekdays[date.weekday().num_days_from_sunday() as usize],
+                    date.format(&self.config.display.date_format)
+                ))
+                .bold();
+                if *date == self.today {
+                    header = header.green();
+                }
+                items.push(ratatui::widgets::ListItem::new(header));
+            }
+            let text = match item {
+                AgendaItem::Event(ev) => {
+                    let start = ev.0.start.as_ref().and_then(|s| s.date_time);
+                    let end = ev.0.end.as_ref().and_then(|e| e.date_time);
+                    let when = match (start, end) {
+                        (Some(start), Some(end)) => format!(
+                            "{}–{}",
+                            start.with_timezone(&self.app_tz).format(time_format),
+                            end.with_timezone(&self.app_tz).format(time_format)
+                        ),
+                        _ => self.locale.t("All day").to_string(),
+                    };
+                    format!("  {when:<13} {}", bidi::display(&self.display_title(ev)))
+                }
+                AgendaItem::Task((task, _)) => format!(
+                    "  {:<13} {}",
+                    "☐",
+                    bidi::display(task.title.as_deref().unwrap_or_default())
+                ),
+            };
+            let mut row = ratatui::widgets::ListItem::new(text);
+            if matches!(item, AgendaItem::Event((ev, _)) if self.ended_earlier_today(ev)) {
+                row = row.dark_gray();
+            }
+            if i == selected {
+                selected_row = items.len();
+                row = row.bg(self.theme.selected).fg(self.theme.selected_text);
+            }
+            items.push(row);
         }
+        let mut state = ratatui::widgets::ListState::default().with_selected(Some(selected_row));
+        if self.config.display.fade_past {
+            *state.offset_mut() =
+                centered_offset(selected_row, items.len(), area.height.saturating_sub(2));
+        }
+        ratatui::prelude::StatefulWidget::render(
+            ratatui::widgets::List::new(items).block(
+                Block::bordered()
+                    .title(format!(
+                        "{} ({} {})",
+                        self.locale.t("Agenda"),
+                        self.config.display.agenda_days,
+                        self.locale.t("days")
+                    ))
+                    .title_bottom("Enter: go to day"),
+            ),
+            area,
+            buf,
+            &mut state,
+        );
     }
 
-    fn selected_task_index(&self) -> Option<usize> {
-        if self.tasks_cache.is_empty() {
-            return None;
+    // Half an hour per row from the scrolled-to hour; events are blocks as long as they last,
+    // overlapping ones side by side
+    fn render_day_timeline(&self, area: Rect, buf: &mut Buffer) {
+        let date = self.current_date;
+        let block = Block::bordered().title(format!(
+            "{} {}",
+            self.locale.t("Day"),
+            date.format(&self.config.display.date_format)
+        ));
+        let inner = block.inner(area);
+        Clear.render(area, buf);
+        block.render(area, buf);
+        if inner.height < 3 {
+            return;
+        }
+
+        let [gutter, lanes_area] = Layout::new(
+            Direction::Horizontal,
+            [Constraint::Length(6), Constraint::Fill(1)],
+        )
+        .areas(inner);
+        Span::raw(self.locale.t("All day")).dark_gray().render(
+            Rect {
+                height: 1,
+                ..gutter
+            },
+            buf,
+        );
+        let all_day: Vec<String> = self
+            .events_on(date)
+            .filter(|(ev, _)| ev.start.as_ref().is_some_and(|s| s.date.is_some()))
+            .map(|ev| self.display_title(ev))
+            .collect();
+        Span::raw(bidi::truncate_display(
+            &all_day.join(", "),
+            lanes_area.width as usize,
+        ))
+        .render(
+            Rect {
+                height: 1,
+                ..lanes_area
+            },
+            buf,
+        );
+
+        let first = date
+            .and_hms_opt(self.cursor_line.min(23) as u32, 0, 0)
+            .unwrap();
+        let day_end = date.and_hms_opt(0, 0, 0).unwrap() + chrono::Duration::days(1);
+        let rows = ((inner.height - 1) as i64).min((day_end - first).num_minutes() / 30);
+        let row_of = |t: NaiveDateTime| (t - first).num_minutes().div_euclid(30);
+        for r in (0..rows).step_by(2) {
+            Span::raw(
+                (first + chrono::Duration::minutes(r * 30))
+                    .format("%H:%M")
+                    .to_string(),
+            )
+            .dark_gray()
+            .render(
+                Rect {
+                    y: inner.y + 1 + r as u16,
+                    height: 1,
+                    ..gutter
+                },
+                buf,
+            );
+        }
+        let now = Local::now().with_timezone(&self.app_tz).naive_local();
+        if now.date() == date && (0..rows).contains(&row_of(now)) {
+            Span::raw(now.format("%H:%M").to_string()).red().render(
+                Rect {
+                    y: inner.y + 1 + row_of(now) as u16,
+                    height: 1,
+                    ..gutter
+                },
+                buf,
+            );
         }
 
-        let idx = self.cursor_line;
-        if idx < self.tasks_cache.len() {
-            Some(idx)
-        } else {
-            Some(self.tasks_cache.len().saturating_sub(1))
+        // Protected time shaded behind the events, named at its top
+        for (name, start, end) in scheduling::protected_on(&self.config.protected, date) {
+            let top = row_of(start).max(0);
+            let bottom = row_of(end - chrono::Duration::minutes(1)).min(rows - 1);
+            for r in top..=bottom {
+                let text = if r == top {
+                    format!("░ {name} ")
+                } else {
+                    String::new()
+                };
+                Span::raw(format!(
+                    "{text:░<width$}",
+                    width = lanes_area.width as usize
+                ))
+                .dark_gray()
+                .render(
+                    Rect {
+                        y: inner.y + 1 + r as u16,
+                        height: 1,
+                        ..lanes_area
+                    },
+                    buf,
+                );
+            }
         }
-    }
 
-    fn selected_event(&self) -> Option<&(api::Event, String)> {
-        let idx = self.selected_event_index()?;
-        self.current_day_events().get(idx)
-    }
+        // Each event takes the first lane free by its start
+        let mut timed: Vec<(&(api::Event, String), NaiveDateTime, NaiveDateTime)> = self
+            .events_on(date)
+            .filter(|(ev, _)| events::EventKind::of(ev) != events::EventKind::WorkingLocation)
+            .filter_map(|entry| {
+                let start = entry.0.start.as_ref()?.date_time?;
+                let end = entry
+                    .0
+                    .end
+                    .as_ref()
+                    .and_then(|e| e.date_time)
+                    .unwrap_or(start);
+                Some((
+                    entry,
+                    start.with_timezone(&self.app_tz).naive_local(),
+                    end.with_timezone(&self.app_tz).naive_local().min(day_end),
+                ))
+            })
+            .collect();
+        timed.sort_by_key(|(_, start, end)| (*start, std::cmp::Reverse(*end)));
+        let mut lane_ends: Vec<NaiveDateTime> = Vec::new();
+        let placed: Vec<usize> = timed
+            .iter()
+            .map(|(_, start, end)| {
+                let end = (*end).max(*start + chrono::Duration::minutes(30));
+                match lane_ends.iter().position(|free| free <= start) {
+                    Some(lane) => {
+                        lane_ends[lane] = end;
+                        lane
+                    }
+                    None => {
+                        lane_ends.push(end);
+                        lane_ends.len() - 1
+                    }
+                }
+            })
+            .collect();
+        let lane_width = lanes_area.width / lane_ends.len().max(1) as u16;
+        if lane_width < 2 {
+            return;
+        }
 
-    fn selected_task(&self) -> Option<&(Task, String)> {
-        let idx = self.selected_task_index()?;
-        self.tasks_cache.get(idx)
+        for ((ev, start, end), lane) in timed.iter().zip(placed) {
+            let top = row_of(*start).max(0);
+            let bottom = (row_of(*end - chrono::Duration::minutes(1)) + 1)
+                .max(row_of(*start) + 1)
+                .min(rows);
+            if bottom <= top {
+                continue;
+            }
+            let rect = Rect {
+                x: lanes_area.x + lane as u16 * lane_width,
+                y: inner.y + 1 + top as u16,
+                width: lane_width - 1,
+                height: (bottom - top) as u16,
+            };
+            let width = rect.width as usize;
+            let ended = self.ended_earlier_today(&ev.0);
+            let style = if ended {
+                Style::new().fg(Color::DarkGray)
+            } else {
+                Style::new()
+                    .bg(Color::DarkGray)
+                    .fg(match events::EventKind::of(&ev.0) {
+                        events::EventKind::Normal => Color::White,
+                        kind => kind.color(),
+                    })
+            };
+            let title = bidi::truncate_display(&self.display_title(ev), width);
+            let times = format!("{}–{}", start.format("%H:%M"), end.format("%H:%M"));
+            let times = if ended {
+                Line::raw(times)
+            } else {
+                Line::raw(times).gray()
+            };
+            Paragraph::new(vec![Line::raw(title), times])
+                .style(style)
+                .render(rect, buf);
+        }
     }
 
-    fn start_background_refresh(&mut self) {
-        self.start_background_event_fetch();
-        self.start_background_task_fetch();
-        self.start_background_weather_fetch();
+    // Yellow baseline on the cell's last row, as long as the day is light
+    fn render_daylight_bar(&self, date: NaiveDate, area: Rect, buf: &mut Buffer) {
+        let Some((lat, lon)) = self.location() else {
+            return;
+        };
+        if !self.config.display.daylight_bar || area.height < 3 || area.width < 2 {
+            return;
+        }
+        // Inside the left and bottom borders
+        let width = area.width - 1;
+        let y = area.y + area.height - 2;
+        let lit = (sun::daylight_fraction(date, lat, lon) * width as f64).round() as u16;
+        for x in area.x + 1..area.x + 1 + lit {
+            let cell = &mut buf[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_symbol("▁").set_fg(Color::Yellow);
+            }
+        }
     }
 
     fn start_background_event_fetch(&mut self) {
@@ -693,10 +4578,17 @@ impl App {
             self.events_update_rx = Some(rx);
             self.refreshing_status = ("Refreshing".to_string(), StatusColor::Green);
             let offset = self.app_tz.clone();
-            tokio::spawn(async move {
-                if let Some(new_events) = App::fetch_events(offset, &hub).await {
+            let horizon_days = self.config.cache.horizon_days;
+            wakeup::spawn(async move {
+                let fetched = App::fetch_events(offset, &hub)
+                    .instrument(tracing::info_span!("refresh_events"))
+                    .await;
+                if let Some((mut new_events, calendars)) = fetched {
+                    tracing::info!(days = new_events.len(), "events refreshed");
+                    file_writing::archive_old_events(&mut new_events, horizon_days);
                     file_writing::save_events_cache(&new_events);
-                    let _ = tx.send(new_events).await;
+                    file_writing::save_calendars_cache(&calendars);
+                    let _ = tx.send((new_events, calendars)).await;
                 }
             });
         }
@@ -706,8 +4598,12 @@ impl App {
             let (tx, rx) = tokio::sync::mpsc::channel(1);
             self.tasks_update_rx = Some(rx);
             self.refreshing_status = ("Refreshing".to_string(), StatusColor::Green);
-            tokio::spawn(async move {
-                if let Some(mut new_tasks) = App::fetch_tasks(&hub).await {
+            wakeup::spawn(async move {
+                let fetched = App::fetch_tasks(&hub)
+                    .instrument(tracing::info_span!("refresh_tasks"))
+                    .await;
+                if let Some(mut new_tasks) = fetched {
+                    tracing::info!(tasks = new_tasks.len(), "tasks refreshed");
                     new_tasks.sort_unstable_by_key(|t| {
                         (
                             t.0.status.clone().unwrap_or("".to_string()),
@@ -720,45 +4616,259 @@ impl App {
             });
         }
     }
+    fn weather_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.weather_ttl_mins * 60)
+    }
+
+    // Older than the TTL; the cached copy keeps showing until a fetch succeeds
+    fn weather_stale(&self) -> bool {
+        // The sample forecast stands in for the whole demo
+        if self.demo {
+            return false;
+        }
+        self.weather_fetched_at
+            .is_none_or(|t| (Local::now() - t).to_std().unwrap_or_default() >= self.weather_ttl())
+    }
+
     fn start_background_weather_fetch(&mut self) {
-        if let Some(config::Config {
+        let config::Config {
             api_key,
             city,
             country,
-        }) = &self.config
-        {
+            ..
+        } = &self.config;
+        if self.weather_stale() {
+            self.last_weather_fetch = Some(std::time::Instant::now());
             let (tx, rx) = tokio::sync::mpsc::channel(1);
             self.weather_rx = Some(rx);
             let a = api_key.clone();
             let c = city.clone();
             let co = country.clone();
-            tokio::spawn(async move {
-                if let Some(current_weather) =
-                    weather::fetch_weather(&a, c.to_string(), co.to_string()).await
-                {
-                    let _ = tx.send(current_weather).await;
+            let coords = self.config.latitude.zip(self.config.longitude);
+            let feedback_tx = self.change_feedback_tx.clone();
+            let air_quality = self.config.air_quality;
+            wakeup::spawn(async move {
+                // Without an OpenWeatherMap key wttr.in fills in
+                let fetched = if a.is_empty() {
+                    wttr::fetch(&c, &co, coords, net::timeout())
+                        .instrument(tracing::info_span!("fetch_wttr"))
+                        .await
+                } else {
+                    weather::fetch_weather(&a, c, co, coords, net::timeout())
+                        .instrument(tracing::info_span!("fetch_weather"))
+                        .await
+                };
+                match fetched.inspect_err(|e| tracing::warn!("weather: {e}")) {
+                    Ok(response) => {
+                        // A failed AQI lookup only leaves its row out
+                        let air = if air_quality {
+                            weather::fetch_air_quality(response.lat, response.lon, net::timeout())
+                                .await
+                                .inspect_err(|e| tracing::warn!("air quality: {e}"))
+                                .ok()
+                        } else {
+                            None
+                        };
+                        let fetched = weather::CachedWeather {
+                            fetched_at: Local::now(),
+                            response,
+                            air,
+                        };
+                        weather::save_cached(&fetched);
+                        let _ = tx.send(fetched).await;
+                    }
+                    // The last weather stays up; the status line says why it isn't fresh
+                    Err(e) => {
+                        let message = match e {
+                            error::Error::Api { status: 401, .. } => {
+                                "Weather API key rejected".to_string()
+                            }
+                            e => format!("Weather: {e}"),
+                        };
+                        if let Some(feedback_tx) = feedback_tx {
+                            let _ = feedback_tx.send((message, StatusColor::Red)).await;
+                        }
+                    }
                 }
             });
         }
     }
 
     fn check_updates(&mut self) {
+        if let Some(rx) = &mut self.news_rx {
+            while let Ok(news) = rx.try_recv() {
+                match news {
+                    update::News::Available(tag) => {
+                        self.changing_status = (
+                            format!("calpersonal {tag} is out; run `calpersonal update`"),
+                            StatusColor::Green,
+                        )
+                    }
+                    update::News::Changelog(tag, notes) => self.changelog = Some((tag, notes, 0)),
+                }
+            }
+        }
+
         if let Some(rx) = &mut self.events_update_rx {
-            if let Ok(new_cache) = rx.try_recv() {
-                self.events_cache = new_cache;
+            if let Ok((new_cache, calendars)) = rx.try_recv() {
+                let items = webhooks::events(&new_cache);
+                webhooks::send(
+                    &self.config.webhooks,
+                    webhooks::changes(&self.webhook_items.0, &items),
+                );
+                self.webhook_items.0 = items;
+                let (merged, conflicts) = merge::merge(new_cache, &self.pending_edits, self.app_tz);
+                self.events_cache = merged;
+                self.apply_feed_events();
+                self.apply_private_events();
+                self.apply_birthday_events();
+                if !conflicts.is_empty() {
+                    self.pending_edits
+                        .retain(|edit| !conflicts.iter().any(|c| c.edit_id == edit.id));
+                    self.conflicts.extend(conflicts);
+                    self.changing_status = (
+                        format!("{} conflicting edits", self.conflicts.len()),
+                        StatusColor::Red,
+                    );
+                    self.app_layout = MainArea::Conflicts;
+                }
+                self.calendars_cache = calendars;
                 self.refreshing_status = ("".to_string(), StatusColor::White);
+                self.scripts.clear_titles();
+                self.scripts.hook("refresh", "");
+                self.focus_pending_event();
             }
         }
         if let Some(rx) = &mut self.tasks_update_rx {
             if let Ok(new_cache) = rx.try_recv() {
+                let items = webhooks::tasks(&new_cache);
+                webhooks::send(
+                    &self.config.webhooks,
+                    webhooks::changes(&self.webhook_items.1, &items),
+                );
+                self.webhook_items.1 = items;
                 self.tasks_cache = new_cache;
+                streaks::record(&self.tasks_cache);
+                self.apply_issue_tasks();
                 self.refreshing_status = ("".to_string(), StatusColor::White);
             }
         }
 
+        if let Some(rx) = &mut self.issues_rx
+            && let Ok(results) = rx.try_recv()
+        {
+            for (source_id, result) in results {
+                match result {
+                    Ok(tasks) => {
+                        self.issue_tasks.retain(|(_, id)| *id != source_id);
+                        self.issue_tasks.extend(tasks);
+                    }
+                    // Keep showing the last good copy
+                    Err(e) => self.changing_status = (e, StatusColor::Red),
+                }
+            }
+            self.apply_issue_tasks();
+            self.issues_rx = None;
+        }
+
+        while let Ok(edit_id) = self.edit_ack_rx.try_recv() {
+            self.pending_edits.retain(|edit| edit.id != edit_id);
+        }
+
+        if let Some(rx) = &mut self.acl_rx
+            && let Ok((calendar_id, rules)) = rx.try_recv()
+            // The cursor may have moved on to another calendar while this was fetched
+            && self.selected_calendar_id() == Some(calendar_id)
+        {
+            self.acl_rules = rules;
+        }
+
+        if let Some(rx) = &mut self.slots_rx
+            && let Ok((slots, unavailable)) = rx.try_recv()
+        {
+            // Calendars Google couldn't answer for are left out of the search, not taken as free
+            let skipped = if unavailable.is_empty() {
+                String::new()
+            } else {
+                format!("Not checked: {}", unavailable.join(", "))
+            };
+            if slots.is_empty() {
+                let status = if skipped.is_empty() {
+                    "No free slots".to_string()
+                } else {
+                    format!("No free slots. {skipped}")
+                };
+                self.changing_status = (status, StatusColor::Red);
+            } else {
+                let color = if skipped.is_empty() {
+                    StatusColor::White
+                } else {
+                    StatusColor::Yellow
+                };
+                self.changing_status = (skipped, color);
+                self.meeting_slots = slots;
+                self.cursor_line = 0;
+                self.app_layout = MainArea::Slots;
+            }
+        }
+
+        if let Some(rx) = &mut self.birthdays_rx
+            && let Ok(result) = rx.try_recv()
+        {
+            match result {
+                Ok(birthdays) => {
+                    self.birthdays = birthdays;
+                    self.apply_birthday_events();
+                }
+                // Keep showing the last good copy
+                Err(e) => self.changing_status = (e, StatusColor::Red),
+            }
+            self.birthdays_rx = None;
+        }
+
+        if let Some(rx) = &mut self.feeds_rx
+            && let Ok(results) = rx.try_recv()
+        {
+            for (feed_id, result) in results {
+                match result {
+                    Ok(events) => {
+                        self.feed_events.insert(feed_id, events);
+                    }
+                    // Keep showing the last good copy
+                    Err(e) => self.changing_status = (e, StatusColor::Red),
+                }
+            }
+            self.apply_feed_events();
+            self.feeds_rx = None;
+        }
+
         if let Some(rx) = &mut self.weather_rx {
             if let Ok(w) = rx.try_recv() {
-                self.onecall_weather = Some(w);
+                self.onecall_weather = Some(w.response);
+                self.air_quality = w.air;
+                self.weather_fetched_at = Some(w.fetched_at);
+            }
+        }
+
+        for command in self.dbus.take_commands() {
+            match command {
+                dbus::Command::AddEvent(text) => {
+                    self.scripts.hook("create", &text);
+                    self.create_event_in_background(text);
+                }
+            }
+        }
+
+        for effect in self.scripts.take_effects() {
+            match effect {
+                scripting::Effect::Status(text) => {
+                    self.changing_status = (text, StatusColor::White);
+                }
+                scripting::Effect::Input(text) => {
+                    self.input_buffer = text;
+                    self.input_target = InputTarget::TaskOrEvent;
+                    self.create_task_or_event();
+                }
             }
         }
 
@@ -769,6 +4879,10 @@ impl App {
             }
         }
 
+        if error::take_reauth() {
+            self.reauthenticate();
+        }
+
         if let Some(rx) = &mut self.calendar_hub_rx {
             if let Ok(hub) = rx.try_recv() {
                 self.event_hub = hub;
@@ -785,6 +4899,7 @@ impl App {
                 self.task_hub = hub;
                 if self.task_hub.is_some() {
                     self.start_background_task_fetch();
+                    self.start_imap_watcher();
                 }
                 self.update_auth_status();
                 self.tasks_hub_rx = None;
@@ -792,63 +4907,461 @@ impl App {
         }
     }
 
-    fn update_auth_status(&mut self) {
-        if self.event_hub.is_some() || self.task_hub.is_some() {
-            self.auth_status = AuthStatus::Online;
-        } else {
-            self.auth_status = AuthStatus::Offline;
-        }
-    }
-
-    fn delete_selected_event(&mut self) {
-        let Some(event) = self.selected_event().cloned() else {
-            return;
-        };
-
-        let Some(event_id) = event.0.id else {
+    fn check_reminders(&mut self) {
+        let now = Local::now().fixed_offset();
+        let default_minutes: HashMap<String, i32> = self
+            .calendars_cache
+            .keys()
+            .filter_map(|id| Some((id.clone(), self.calendar_override(id)?.reminder_minutes?)))
+            .collect();
+        let due = reminders::due_reminders(
+            &self.events_cache,
+            &self.calendars_cache,
+            &default_minutes,
+            self.app_tz,
+            self.last_reminder_check,
+            now,
+        );
+        let alarms = reminders::due_alarms(
+            &self.events_cache,
+            self.app_tz,
+            self.last_reminder_check,
+            now,
+            |event| self.is_critical(event),
+        );
+        let local_due = reminders::due_local(&self.local_reminders, self.last_reminder_check, now);
+        self.last_reminder_check = now;
+
+        if let Some(reminder) = due.last() {
+            self.changing_status = (
+                format!("Reminder: {} in {} min", reminder.summary, reminder.minutes),
+                StatusColor::Yellow,
+            );
+        }
+        if let Some(reminder) = local_due.last() {
+            self.changing_status = (format!("Reminder: {}", reminder.text), StatusColor::Yellow);
+        }
+
+        let (woken, still_snoozed) = std::mem::take(&mut self.snoozed)
+            .into_iter()
+            .partition(|(at, _)| *at <= now);
+        self.snoozed = still_snoozed;
+        let ringing: Vec<reminders::Alarm> = alarms
+            .into_iter()
+            .chain(woken.into_iter().map(|(_, alarm)| alarm))
+            .collect();
+        if let Some(alarm) = ringing.last() {
+            self.changing_status = (format!("Alarm: {}", alarm.summary), StatusColor::Red);
+            // The terminal bell, for whoever is in earshot but not looking
+            use io::Write;
+            let _ = io::stdout().write_all(b"\x07");
+            let _ = io::stdout().flush();
+        }
+        self.alarms.extend(ringing);
+    }
+
+    // Starred or tagged events, per [alarms]
+    fn is_critical(&self, event: &api::Event) -> bool {
+        let alarms = &self.config.alarms;
+        if alarms.starred && self.is_starred(event) {
+            return true;
+        }
+        let title = event.summary.as_deref().unwrap_or_default().to_lowercase();
+        alarms
+            .keywords
+            .iter()
+            .any(|k| !k.is_empty() && title.contains(&k.to_lowercase()))
+    }
+
+    // The alarm takes every key until it is dismissed or snoozed
+    fn alarm_handle_key_event(&mut self, key_event: KeyEvent) {
+        let Some(alarm) = self.alarms.front().cloned() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') => {
+                self.alarms.pop_front();
+                self.changing_status = ("Dismissed".to_string(), StatusColor::Green);
+            }
+            KeyCode::Char('s') | KeyCode::Char('z') => {
+                let minutes = self.config.alarms.snooze_minutes.max(1);
+                self.alarms.pop_front();
+                self.snoozed.push((
+                    Local::now().fixed_offset() + chrono::Duration::minutes(minutes),
+                    alarm,
+                ));
+                self.changing_status = (format!("Snoozed for {minutes} min"), StatusColor::Green);
+            }
+            _ => {}
+        }
+    }
+
+    // Full screen, flashing twice a second, over everything else
+    fn render_alarm(&self, alarm: &reminders::Alarm, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let lit = Local::now().timestamp_subsec_millis() < 500;
+        let (fg, bg) = if lit {
+            (Color::White, Color::Red)
+        } else {
+            (Color::Red, Color::Reset)
+        };
+        let mut lines = vec![
+            Line::from(self.locale.t("Alarm")).bold(),
+            Line::from(""),
+            Line::from(alarm.summary.clone()).bold(),
+            Line::from(format!(
+                "{} {}",
+                self.locale.t("Starts"),
+                alarm.start.format(self.config.display.time_format())
+            )),
+        ];
+        if let Some(location) = &alarm.location {
+            lines.push(Line::from(location.clone()));
+        }
+        if self.alarms.len() > 1 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("+{}", self.alarms.len() - 1)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Enter: dismiss  s: snooze {} min",
+            self.config.alarms.snooze_minutes.max(1)
+        )));
+        let height = lines.len() as u16;
+        let block = Block::bordered().style(Style::default().fg(fg).bg(bg));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(lines)
+            .alignment(ratatui::layout::Alignment::Center)
+            .render(
+                inner.centered(Constraint::Fill(1), Constraint::Length(height)),
+                buf,
+            );
+    }
+
+    // A 401 or invalid_grant: forget the tokens and sign in from scratch. Network failures
+    // never get here, so going offline keeps the refresh token
+    fn reauthenticate(&mut self) {
+        if matches!(self.auth_status, AuthStatus::Authenticating) {
+            return;
+        }
+        tracing::warn!("token refused or revoked, signing in again");
+        calendar_auth::forget_token();
+        tasks_auth::forget_token();
+        self.event_hub = None;
+        self.task_hub = None;
+        self.auth_status = AuthStatus::Authenticating;
+        self.changing_status = ("Signing in again".to_string(), StatusColor::Yellow);
+
+        let (calendar_tx, calendar_rx) = tokio::sync::oneshot::channel();
+        let (tasks_tx, tasks_rx) = tokio::sync::oneshot::channel();
+        self.calendar_hub_rx = Some(calendar_rx);
+        self.tasks_hub_rx = Some(tasks_rx);
+        let (network, secret) = (self.config.network.clone(), self.client_secret.clone());
+        let contacts = self.config.birthdays.contacts;
+        let auth_config = self.config.auth.clone();
+        wakeup::spawn(async move {
+            let hub = calendar_auth::get_calendar_hub(network, secret, auth_config, contacts).await;
+            let _ = calendar_tx.send(
+                hub.inspect_err(|e| tracing::error!("calendar sign-in: {e}"))
+                    .ok(),
+            );
+        });
+        let (network, secret) = (self.config.network.clone(), self.client_secret.clone());
+        let auth_config = self.config.auth.clone();
+        wakeup::spawn(async move {
+            let hub = tasks_auth::get_tasks_hub(network, secret, auth_config).await;
+            let _ = tasks_tx.send(
+                hub.inspect_err(|e| tracing::error!("tasks sign-in: {e}"))
+                    .ok(),
+            );
+        });
+    }
+
+    fn update_auth_status(&mut self) {
+        if self.event_hub.is_some() || self.task_hub.is_some() {
+            self.auth_status = AuthStatus::Online;
+        } else {
+            self.auth_status = AuthStatus::Offline;
+        }
+    }
+
+    // Shows an event change right away; it is replayed over refreshes until acknowledged
+    fn begin_edit(
+        &mut self,
+        calendar_id: String,
+        change: merge::LocalChange,
+        base_updated: Option<DateTime<Utc>>,
+    ) -> u64 {
+        let edit = merge::PendingEdit {
+            id: self.next_edit_id,
+            calendar_id,
+            change,
+            base_updated,
+        };
+        self.next_edit_id += 1;
+        let days = self.day_index.touched_by(&edit.change, self.app_tz);
+        merge::apply(&mut self.events_cache, &edit, self.app_tz);
+        for date in days {
+            self.day_index
+                .update_day(&self.events_cache, date, self.app_tz);
+        }
+        self.pending_edits.push(edit);
+        self.next_edit_id - 1
+    }
+
+    // Pushes the local version of the first conflict back over the remote one
+    fn keep_local_in_conflict(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        if self.conflicts.is_empty() {
+            return;
+        }
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::Red);
+            return;
+        };
+        let conflict = self.conflicts.remove(0);
+        let Some(event_id) = conflict.local.id.clone() else {
+            return;
+        };
+        if self.conflicts.is_empty() {
+            self.app_layout = MainArea::Calendar;
+        }
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Keeping local version".to_string(), StatusColor::Yellow);
+        let patch = api::Event {
+            summary: conflict.local.summary.clone(),
+            start: conflict.local.start.clone(),
+            end: conflict.local.end.clone(),
+            ..Default::default()
+        };
+        wakeup::spawn(async move {
+            let result = hub
+                .events()
+                .patch(patch, &conflict.calendar_id, &event_id)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "resolve_conflict",
+                &format!("{}/{event_id}", conflict.calendar_id),
+                audit::payload(&conflict.remote),
+                audit::payload(&conflict.local),
+                &result.as_ref().map(|_| ()),
+            );
+            let msg = match result {
+                Ok(_) => ("Local version kept".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    fn discard_local_in_conflict(&mut self) {
+        if self.conflicts.is_empty() {
+            return;
+        }
+        self.conflicts.remove(0);
+        if self.conflicts.is_empty() {
+            self.app_layout = MainArea::Calendar;
+        }
+    }
+
+    fn delete_selected_event(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        let Some(event) = self.selected_event().cloned() else {
+            return;
+        };
+        if vault::is_private(&event.1) {
+            self.private_events.retain(|e| e.id != event.0.id);
+            self.save_private_events();
+            return;
+        }
+        if let Some(reason) = self.read_only_reason(&event.1) {
+            self.changing_status = (reason.to_string(), StatusColor::Red);
+            return;
+        }
+
+        let Some(event_id) = event.0.id.clone() else {
+            return;
+        };
+
+        let Some(hub) = self.event_hub.as_ref().cloned() else {
+            self.changing_status = ("Offline".to_string(), StatusColor::White);
+            return;
+        };
+        self.scripts
+            .hook("delete", event.0.summary.as_deref().unwrap_or_default());
+
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+        self.changing_status = ("Deleting".to_string(), StatusColor::Yellow);
+
+        let edit_id = self.begin_edit(
+            event.1.clone(),
+            merge::LocalChange::Delete(event_id.clone()),
+            event.0.updated,
+        );
+        let ack_tx = self.edit_ack_tx.clone();
+        // Spawn background deletion
+        wakeup::spawn(async move {
+            let result = hub
+                .events()
+                .delete(&event.1, &event_id)
+                .add_scope(calendar_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "delete_event",
+                &format!("{}/{event_id}", event.1),
+                audit::payload(&event.0),
+                None,
+                &result.as_ref().map(|_| ()),
+            );
+            if result.is_ok() {
+                trash::push(vec![trash::Trashed::Event(Box::new(event.0), event.1)]).await;
+            }
+            let _ = ack_tx.send(edit_id).await;
+
+            let msg = match result {
+                Ok(_) => ("Event Deleted!".to_string(), StatusColor::Green),
+                Err(e) => error::Error::from(e).status(),
+            };
+            let _ = tx.send(msg).await;
+        });
+    }
+
+    // Re-inserts a deleted event or task as a new item
+    fn restore_selected_trash_item(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
+        if self.trash_items.is_empty() {
+            return;
+        }
+        let idx = self.cursor_line.min(self.trash_items.len() - 1);
+        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
+
+        match self.trash_items[idx].item.clone() {
+            trash::Trashed::Event(event, calendar_id) => {
+                let Some(hub) = self.event_hub.as_ref().cloned() else {
+                    self.changing_status = ("Offline".to_string(), StatusColor::Red);
+                    return;
+                };
+                let removed = self.trash_items.remove(idx);
+                let (deleted_at, id) = (removed.deleted_at, removed.id().map(String::from));
+                self.changing_status = ("Restoring".to_string(), StatusColor::Yellow);
+                wakeup::spawn(async move {
+                    let result = hub
+                        .events()
+                        .insert(trash::fresh_event(&event), &calendar_id)
+                        .add_scope(calendar_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
+                        .doit()
+                        .await;
+                    audit::record(
+                        "restore_event",
+                        &calendar_id,
+                        None,
+                        result.as_ref().ok().and_then(|(_, e)| audit::payload(e)),
+                        &result.as_ref().map(|_| ()),
+                    );
+                    let msg = match result {
+                        Ok(_) => {
+                            trash::remove(deleted_at, id).await;
+                            ("Event restored!".to_string(), StatusColor::Green)
+                        }
+                        Err(e) => error::Error::from(e).status(),
+                    };
+                    let _ = tx.send(msg).await;
+                });
+            }
+            trash::Trashed::Task(task, tasklist_id) => {
+                let Some(hub) = self.task_hub.as_ref().cloned() else {
+                    self.changing_status = ("Offline".to_string(), StatusColor::Red);
+                    return;
+                };
+                let removed = self.trash_items.remove(idx);
+                let (deleted_at, id) = (removed.deleted_at, removed.id().map(String::from));
+                self.changing_status = ("Restoring".to_string(), StatusColor::Yellow);
+                wakeup::spawn(async move {
+                    let result = hub
+                        .tasks()
+                        .insert(trash::fresh_task(&task), &tasklist_id)
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
+                        .doit()
+                        .await;
+                    audit::record(
+                        "restore_task",
+                        &tasklist_id,
+                        None,
+                        result.as_ref().ok().and_then(|(_, t)| audit::payload(t)),
+                        &result.as_ref().map(|_| ()),
+                    );
+                    let msg = match result {
+                        Ok(_) => {
+                            trash::remove(deleted_at, id).await;
+                            ("Task restored!".to_string(), StatusColor::Green)
+                        }
+                        Err(e) => error::Error::from(e).status(),
+                    };
+                    let _ = tx.send(msg).await;
+                });
+            }
+        }
+    }
+
+    fn delete_selected_task(&mut self) {
+        if self.refuse_access(config::Access::Events) {
             return;
-        };
-
-        let Some(hub) = self.event_hub.as_ref().cloned() else {
-            self.changing_status = ("Offline".to_string(), StatusColor::White);
+        }
+        if self.selected_task_is_read_only() {
             return;
-        };
-
-        let tx = self.change_feedback_tx.as_ref().unwrap().clone();
-        self.changing_status = ("Deleting".to_string(), StatusColor::Yellow);
-
-        // Spawn background deletion
-        tokio::spawn(async move {
-            let result = hub.events().delete(&event.1, &event_id).doit().await;
-
-            let msg = match result {
-                Ok(_) => ("Event Deleted!".to_string(), StatusColor::Green),
-                Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
-            };
-            let _ = tx.send(msg).await;
-        });
-    }
-
-    fn delete_selected_task(&mut self) {
+        }
         let Some(task) = self.selected_task().cloned() else {
             return;
         };
-        let Some(task_id) = task.0.id else {
+        let Some(task_id) = task.0.id.clone() else {
             return;
         };
         let Some(hub) = self.task_hub.as_ref().cloned() else {
             self.changing_status = ("Offline".to_string(), StatusColor::White);
             return;
         };
+        self.scripts
+            .hook("delete", task.0.title.as_deref().unwrap_or_default());
 
         let tx = self.change_feedback_tx.as_ref().unwrap().clone();
         self.changing_status = ("Deleting task...".to_string(), StatusColor::Yellow);
 
-        tokio::spawn(async move {
-            let result = hub.tasks().delete(&task.1, &task_id).doit().await;
+        wakeup::spawn(async move {
+            let result = hub
+                .tasks()
+                .delete(&task.1, &task_id)
+                .add_scope(tasks_auth::write_scope())
+                .delegate(&mut net::Middleware::default())
+                .doit()
+                .await;
+            audit::record(
+                "delete_task",
+                &format!("{}/{task_id}", task.1),
+                audit::payload(&task.0),
+                None,
+                &result.as_ref().map(|_| ()),
+            );
+            if result.is_ok() {
+                trash::push(vec![trash::Trashed::Task(Box::new(task.0), task.1)]).await;
+            }
             let msg = match result {
                 Ok(_) => ("Task deleted!".to_string(), StatusColor::Green),
-                Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                Err(e) => error::Error::from(e).status(),
             };
             let _ = tx.send(msg).await.ok();
         });
@@ -856,27 +5369,42 @@ impl App {
 
     async fn fetch_events(
         app_tz: FixedOffset,
-        hub: &CalendarHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>,
-    ) -> Option<HashMap<NaiveDate, Vec<(api::Event, String)>>> {
-        let calendars = match hub.calendar_list().list().doit().await {
+        hub: &CalendarHub<net::Connector>,
+    ) -> Option<(
+        HashMap<NaiveDate, Vec<(api::Event, String)>>,
+        HashMap<String, api::CalendarListEntry>,
+    )> {
+        let calendars = match hub
+            .calendar_list()
+            .list()
+            .add_scope(calendar_auth::read_scope())
+            .delegate(&mut net::Middleware::default())
+            .doit()
+            .await
+        {
             Ok((_, calendar_ids)) => calendar_ids.items.unwrap_or_default(),
             Err(e) => {
-                eprintln!("Failed to fetch calendars: {e:?}");
+                tracing::error!("fetching calendars: {}", error::Error::from(e));
                 return None;
             }
         };
 
         let mut map: HashMap<NaiveDate, Vec<(api::Event, String)>> = HashMap::new();
+        let mut calendar_map: HashMap<String, api::CalendarListEntry> = HashMap::new();
 
         for entry in calendars {
-            if let Some(id) = entry.id {
+            if let Some(id) = entry.id.clone() {
                 let re_encoded_id = urlencoding::encode(&id);
-                match hub
-                    .events()
-                    .list(&re_encoded_id)
-                    .add_scope(google_calendar3::api::Scope::Full)
-                    .single_events(true)
-                    .order_by("startTime")
+                let request = events::EVENT_TYPES.iter().fold(
+                    hub.events()
+                        .list(&re_encoded_id)
+                        .single_events(true)
+                        .order_by("startTime"),
+                    |request, event_type| request.add_event_types(event_type),
+                );
+                match request
+                    .add_scope(calendar_auth::read_scope())
+                    .delegate(&mut net::Middleware::default())
                     .doit()
                     .await
                 {
@@ -899,50 +5427,140 @@ impl App {
                                 if let Some(start_date) = start_date_and_event {
                                     map.entry(start_date)
                                         .or_default()
-                                        .push((event, re_encoded_id.to_string().clone()));
+                                        .push((slim::event(event), re_encoded_id.to_string()));
                                 }
                             }
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to fetch events: {e:?}");
+                        tracing::error!(
+                            calendar = id,
+                            "fetching events: {}",
+                            error::Error::from(e)
+                        );
                     }
                 }
+                calendar_map.insert(re_encoded_id.to_string(), entry);
             }
         }
-        Some(map)
+        Some((map, calendar_map))
     }
 
-    async fn fetch_tasks(
-        hub: &TasksHub<hyper_rustls::HttpsConnector<connect::HttpConnector>>,
-    ) -> Option<Vec<(Task, String)>> {
-        let tasklists = match hub.tasklists().list().doit().await {
+    async fn fetch_tasks(hub: &TasksHub<net::Connector>) -> Option<Vec<(Task, String)>> {
+        let tasklists = match hub
+            .tasklists()
+            .list()
+            .add_scope(tasks_auth::read_scope())
+            .delegate(&mut net::Middleware::default())
+            .doit()
+            .await
+        {
             Ok((_, tasks_list)) => tasks_list.items.unwrap_or_default(),
             Err(e) => {
-                eprintln!("Failed to fetch tasklists: {e:?}");
+                tracing::error!("fetching tasklists: {}", error::Error::from(e));
                 return None;
             }
         };
         let mut all_tasks = Vec::new();
         for tasklist in tasklists {
             if let Some(tasklist_id) = tasklist.id {
-                match hub.tasks().list(&tasklist_id).doit().await {
+                match hub
+                    .tasks()
+                    .list(&tasklist_id)
+                    .add_scope(tasks_auth::read_scope())
+                    .delegate(&mut net::Middleware::default())
+                    .doit()
+                    .await
+                {
                     Ok((_, tasks)) => {
                         if let Some(items) = tasks.items {
-                            let tasks_with_list: Vec<(Task, String)> = items
-                                .iter()
-                                .map(|t| (t.clone(), tasklist_id.clone()))
-                                .collect();
-                            all_tasks.extend(tasks_with_list);
+                            all_tasks.extend(
+                                items
+                                    .into_iter()
+                                    .map(|t| (slim::task(t), tasklist_id.clone())),
+                            );
                         }
                     }
-                    Err(e) => eprintln!("Failed to fetch tasks for list {tasklist_id}: {e:?}"),
+                    Err(e) => tracing::error!(
+                        tasklist = tasklist_id,
+                        "fetching tasks: {}",
+                        error::Error::from(e)
+                    ),
                 }
             }
         }
         Some(all_tasks)
     }
 
+    // Status messages and logged warnings and errors, oldest first
+    fn log_messages(&self) -> Vec<(DateTime<Local>, &'static str, String, Color)> {
+        let mut messages: Vec<_> = self
+            .status_history
+            .iter()
+            .map(|(at, text, color)| {
+                (
+                    *at,
+                    "",
+                    self.locale.t(text).to_string(),
+                    color.color(&self.theme),
+                )
+            })
+            .chain(
+                logging::messages()
+                    .into_iter()
+                    .map(|(at, level, text)| match level {
+                        tracing::Level::ERROR => (at, "error", text, Color::Red),
+                        _ => (at, "warn", text, Color::Yellow),
+                    }),
+            )
+            .collect();
+        messages.sort_by_key(|(at, ..)| *at);
+        messages
+    }
+
+    fn render_message_log(&self, area: Rect, scroll: u16, buf: &mut Buffer) {
+        let area = area.centered(Constraint::Percentage(90), Constraint::Percentage(80));
+        Clear.render(area, buf);
+        let messages = self.log_messages();
+        let visible = area.height.saturating_sub(2) as usize;
+        // Scrolling stops at the oldest message
+        let end = messages
+            .len()
+            .saturating_sub(scroll as usize)
+            .max(visible.min(messages.len()));
+        let lines: Vec<Line> = messages[end.saturating_sub(visible)..end]
+            .iter()
+            .map(|(at, level, text, color)| {
+                Line::from(vec![
+                    Span::raw(format!(" {} ", at.format("%a %H:%M:%S"))).dim(),
+                    Span::raw(format!("{level:<6}")).fg(*color).bold(),
+                    Span::raw(text.clone()).fg(*color),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title(self.locale.t("Messages"))
+                    .title_bottom("j/k: scroll  Esc: close"),
+            )
+            .render(area, buf);
+    }
+
+    // The help overlay takes every key until it is closed
+    fn help_handle_key_event(&mut self, key_event: KeyEvent, scroll: u16) {
+        let down = |by: u16| Some((scroll + by).min(help::line_count()));
+        self.help_scroll = match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => down(1),
+            KeyCode::Char('k') | KeyCode::Up => Some(scroll.saturating_sub(1)),
+            KeyCode::PageDown | KeyCode::Char(' ') => down(10),
+            KeyCode::PageUp => Some(scroll.saturating_sub(10)),
+            KeyCode::Char('g') | KeyCode::Home => Some(0),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => None,
+            _ => Some(scroll),
+        };
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') => self.exit(),
@@ -976,31 +5594,224 @@ impl App {
                 MainArea::Events => {
                     self.delete_selected_event();
                 }
+                MainArea::Conflicts => self.discard_local_in_conflict(),
+                MainArea::Memos => self.delete_selected_memo(),
+                MainArea::Reminders => self.delete_selected_local_reminder(),
                 _ => {}
             },
             KeyCode::Enter => match self.app_layout {
                 MainArea::Tasks(false) => {
                     self.app_layout = MainArea::Tasks(true);
                 }
+                MainArea::Calendars => self.start_background_acl_fetch(),
+                MainArea::Slots => self.create_meeting_from_selected_slot(),
+                MainArea::Plan => self.book_plan(),
+                MainArea::Review => self.advance_review(),
+                MainArea::Trash => self.restore_selected_trash_item(),
+                MainArea::Starred => self.open_selected_starred(),
+                MainArea::Invitations => self.open_selected_invitation(),
+                MainArea::Birthdays => self.open_selected_birthday(),
+                MainArea::Agenda => self.open_selected_agenda_item(),
+                MainArea::Conflicts => self.keep_local_in_conflict(),
+                MainArea::Events => self.event_details = self.selected_event().is_some(),
+                _ => {}
+            },
+            KeyCode::Char('c') => match self.app_layout {
+                MainArea::Events => self.copy_selected_event(),
+                MainArea::Report => self.copy_meeting_report(),
                 _ => {}
             },
+            KeyCode::Char('U') => {
+                if let MainArea::Events = self.app_layout {
+                    self.copy_selected_event_link()
+                }
+            }
+            KeyCode::Char('M') if !self.refuse_access(config::Access::Events) => {
+                self.input_target = InputTarget::Meeting;
+                self.inputting = true
+            }
+            KeyCode::Char('C') => self.toggle_calendars_visibility(),
+            KeyCode::Char('A') => self.toggle_audit_visibility(),
+            KeyCode::Char('X') => self.toggle_trash_visibility(),
+            KeyCode::Char('s') => {
+                if let MainArea::Calendars = self.app_layout
+                    && !self.refuse_access(config::Access::Full)
+                {
+                    self.input_target = InputTarget::ShareCalendar;
+                    self.inputting = true
+                }
+            }
             KeyCode::Char('E') => self.toggle_event_visibility(),
+            KeyCode::Char('z') => self.toggle_zoom(),
+            KeyCode::Char('*') => self.toggle_star(),
+            KeyCode::Char('S') => self.toggle_starred_visibility(),
+            KeyCode::Char('P') => self.toggle_private_events(),
+            KeyCode::Char('K') => self.toggle_compare_visibility(),
+            KeyCode::Char('v') => self.toggle_day_visibility(),
+            KeyCode::Char('g') => self.toggle_agenda_visibility(),
+            KeyCode::Char('m') => self.toggle_memos_visibility(),
+            KeyCode::Char('t') if matches!(self.app_layout, MainArea::Memos) => {
+                self.convert_selected_memo(true)
+            }
+            KeyCode::Char('e') if matches!(self.app_layout, MainArea::Memos) => {
+                self.convert_selected_memo(false)
+            }
+            KeyCode::Char('a') if matches!(self.app_layout, MainArea::Invitations) => {
+                self.respond_to_selected_invitation("accepted")
+            }
+            KeyCode::Char('t') if matches!(self.app_layout, MainArea::Invitations) => {
+                self.respond_to_selected_invitation("tentative")
+            }
+            KeyCode::Char('d') if matches!(self.app_layout, MainArea::Invitations) => {
+                self.respond_to_selected_invitation("declined")
+            }
+            KeyCode::Char('I') => self.toggle_invitations_visibility(),
+            KeyCode::Char('B') => self.toggle_birthdays_visibility(),
+            KeyCode::Char('J') => self.toggle_free_busy_visibility(),
+            KeyCode::Char('H') => self.show_status_history = !self.show_status_history,
+            KeyCode::Char('!') => self.message_log = Some(0),
+            KeyCode::Char('?') => self.help_scroll = Some(0),
+            KeyCode::Char('x') if matches!(self.app_layout, MainArea::Calendar) => {
+                self.range_start = match self.range_start {
+                    Some(_) => None,
+                    None => Some(self.current_date),
+                }
+            }
+            KeyCode::Char(']') if matches!(self.app_layout, MainArea::Calendar) => {
+                self.scroll_cell(1)
+            }
+            KeyCode::Char('[') if matches!(self.app_layout, MainArea::Calendar) => {
+                self.scroll_cell(-1)
+            }
+            KeyCode::Char('V') => self.config.display.week_split = !self.config.display.week_split,
+            KeyCode::Char('|') => self.config.display.day_panel = !self.config.display.day_panel,
+            KeyCode::Char('{') => self.resize_pane(Some(PANE_SIZE_STEP as i16)),
+            KeyCode::Char('}') => self.resize_pane(Some(-(PANE_SIZE_STEP as i16))),
+            KeyCode::Char('=') => self.resize_pane(None),
+            // With the day panel open, straight to that event in the Events list
+            KeyCode::Char(digit @ '1'..='9')
+                if self.config.display.day_panel
+                    && matches!(self.app_layout, MainArea::Calendar) =>
+            {
+                let index = digit as usize - '1' as usize;
+                if index < self.events_on(self.current_date).count() {
+                    self.toggle_event_visibility();
+                    self.cursor_line = index;
+                }
+            }
+            KeyCode::Char('N')
+                if matches!(self.app_layout, MainArea::Calendar | MainArea::Events) =>
+            {
+                self.note_to_edit = Some(self.current_date)
+            }
+            KeyCode::Char('f') if matches!(self.app_layout, MainArea::Events) => {
+                self.cycle_calendar_filter()
+            }
+            KeyCode::Char('r') if matches!(self.app_layout, MainArea::Events) => {
+                self.cycle_response_filter()
+            }
+            KeyCode::Char('/') if matches!(self.app_layout, MainArea::Events) => {
+                self.input_target = InputTarget::EventFilter;
+                self.inputting = true
+            }
+            KeyCode::Char('/') => {
+                self.input_target = InputTarget::Search;
+                self.inputting = true;
+                self.app_layout = MainArea::Search;
+                self.cursor_line = 0;
+            }
+            KeyCode::Char(digit @ '1'..='9') if matches!(self.app_layout, MainArea::Events) => {
+                let index = digit as usize - '1' as usize;
+                if index < self.current_day_events().len() {
+                    self.cursor_line = index;
+                }
+            }
+            KeyCode::Char('F') if matches!(self.app_layout, MainArea::Events) => {
+                self.event_filter = events::EventFilter::default();
+                self.cursor_line = 0;
+            }
             KeyCode::Char('T') => self.toggle_tasks_visibility(),
             KeyCode::Char('t') => self.current_date = self.today,
-            KeyCode::Char('R') => self.needs_refresh = true,
-            KeyCode::Char('o') => self.inputting = true,
+            KeyCode::Char('R') => {
+                // Manual refreshes skip the quota back-off
+                self.start_background_refresh();
+                self.needs_refresh = false;
+                self.last_refresh = std::time::Instant::now();
+            }
+            KeyCode::Char('o') if !self.refuse_access(config::Access::Events) => {
+                self.inputting = true
+            }
+            KeyCode::Char('O') => self.open_selected_in_browser(),
+            KeyCode::Char(':') => {
+                self.input_target = InputTarget::Command;
+                self.inputting = true
+            }
             KeyCode::Char('a') => self.add_or_update_event(),
+            KeyCode::Char(' ') if matches!(self.app_layout, MainArea::Review) => {
+                if let Some(review) = &mut self.review {
+                    review.cycle(self.cursor_line);
+                }
+            }
+            KeyCode::Char(' ') if matches!(self.app_layout, MainArea::Report) => {
+                self.report_period = self.report_period.toggled()
+            }
             KeyCode::Char(' ') => self.toggle_task_completed(),
+            KeyCode::Char('G') => self.start_review(),
             KeyCode::Char('L') => self.clear_completed_tasks(),
             KeyCode::Char('W') => self.toggle_weather(),
+            KeyCode::Char('w') if !self.refuse_access(config::Access::Events) => {
+                self.input_target = InputTarget::WorkingLocation;
+                self.inputting = true
+            }
             _ => {}
         }
     }
 
+    // Rendering clamps the offset to what the cell can show
+    fn scroll_cell(&mut self, by: isize) {
+        let date = self.current_date;
+        let lines = self.events_on(date).count()
+            + self
+                .continuing_events(date..=date)
+                .get(&date)
+                .map_or(0, Vec::len);
+        let offset = if self.cell_scroll.0 == date {
+            self.cell_scroll.1
+        } else {
+            0
+        };
+        self.cell_scroll = (
+            date,
+            offset
+                .saturating_add_signed(by)
+                .min(lines.saturating_sub(1)),
+        );
+    }
+
     fn toggle_weather(&mut self) {
         match self.app_layout {
             MainArea::Weather => self.app_layout = MainArea::Calendar,
-            MainArea::Calendar | MainArea::Tasks(_) | MainArea::Events => {
+            MainArea::Calendar
+            | MainArea::Tasks(_)
+            | MainArea::Events
+            | MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Compare
+            | MainArea::Day
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders
+            | MainArea::FreeBusy => {
                 self.weather_day = 1;
                 self.app_layout = MainArea::Weather
             }
@@ -1014,6 +5825,25 @@ impl App {
                     self.weather_day += 1
                 }
             }
+            MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders => {}
+            MainArea::Compare => self.compare_weeks = self.compare_weeks.saturating_sub(1).max(1),
+            MainArea::Day | MainArea::FreeBusy => {
+                self.current_date = self.current_date + Days::new(7)
+            }
             MainArea::Calendar | MainArea::Tasks(_) | MainArea::Events => {
                 self.current_date = self
                     .current_date
@@ -1030,6 +5860,25 @@ impl App {
                     self.weather_day -= 1
                 }
             }
+            MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders => {}
+            MainArea::Compare => self.compare_weeks += 1,
+            MainArea::Day | MainArea::FreeBusy => {
+                self.current_date = self.current_date - Days::new(7)
+            }
             MainArea::Calendar | MainArea::Tasks(_) | MainArea::Events => {
                 self.current_date = self
                     .current_date
@@ -1040,19 +5889,25 @@ impl App {
     }
 
     fn toggle_task_completed(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
         match self.app_layout {
             MainArea::Tasks(_) => {
+                if self.selected_task_is_read_only() {
+                    return;
+                }
                 let Some(task) = self.selected_task().cloned() else {
                     return;
                 };
-                let Some(task_id) = task.0.id else {
+                let Some(task_id) = task.0.id.clone() else {
                     return;
                 };
                 let Some(hub) = self.task_hub.as_ref().cloned() else {
                     self.changing_status = ("Offline".to_string(), StatusColor::White);
                     return;
                 };
-                let Some(completed_status) = task.0.status else {
+                let Some(completed_status) = task.0.status.clone() else {
                     return;
                 };
                 let new_completed = match completed_status.as_str() {
@@ -1060,25 +5915,39 @@ impl App {
                         status: Some("needsAction".to_string()),
                         ..Default::default()
                     },
-                    "needsAction" => Task {
-                        status: Some("completed".to_string()),
-                        ..Default::default()
-                    },
+                    "needsAction" => {
+                        self.scripts
+                            .hook("complete", task.0.title.as_deref().unwrap_or_default());
+                        Task {
+                            status: Some("completed".to_string()),
+                            ..Default::default()
+                        }
+                    }
                     _ => Task::default(),
                 };
 
                 let tx = self.change_feedback_tx.as_ref().unwrap().clone();
                 self.changing_status = ("Toggling...".to_string(), StatusColor::Yellow);
 
-                tokio::spawn(async move {
+                wakeup::spawn(async move {
+                    let after = audit::payload(&new_completed);
                     let result = hub
                         .tasks()
                         .patch(new_completed, &task.1, &task_id)
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
                         .doit()
                         .await;
+                    audit::record(
+                        "toggle_task",
+                        &format!("{}/{task_id}", task.1),
+                        audit::payload(&task.0),
+                        after,
+                        &result.as_ref().map(|_| ()),
+                    );
                     let msg = match result {
                         Ok(_) => ("Completed".to_string(), StatusColor::Green),
-                        Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                        Err(e) => error::Error::from(e).status(),
                     };
                     let _ = tx.send(msg).await.ok();
                 });
@@ -1088,8 +5957,14 @@ impl App {
     }
 
     fn clear_completed_tasks(&mut self) {
+        if self.refuse_access(config::Access::Events) {
+            return;
+        }
         match self.app_layout {
             MainArea::Tasks(_) => {
+                if self.selected_task_is_read_only() {
+                    return;
+                }
                 let Some(task) = self.selected_task().cloned() else {
                     return;
                 };
@@ -1099,12 +5974,35 @@ impl App {
                 };
                 let tx = self.change_feedback_tx.as_ref().unwrap().clone();
                 self.changing_status = ("Clearing...".to_string(), StatusColor::Yellow);
-
-                tokio::spawn(async move {
-                    let result = hub.tasks().clear(&task.1).doit().await;
+                // Trashed like single deletions, so `L` can be undone from the trash view
+                let cleared: Vec<_> = self
+                    .tasks_cache
+                    .iter()
+                    .filter(|(t, list)| *list == task.1 && t.status.as_deref() == Some("completed"))
+                    .map(|(t, list)| trash::Trashed::Task(Box::new(t.clone()), list.clone()))
+                    .collect();
+
+                wakeup::spawn(async move {
+                    let result = hub
+                        .tasks()
+                        .clear(&task.1)
+                        .add_scope(tasks_auth::write_scope())
+                        .delegate(&mut net::Middleware::default())
+                        .doit()
+                        .await;
+                    audit::record(
+                        "clear_completed_tasks",
+                        &task.1,
+                        None,
+                        None,
+                        &result.as_ref().map(|_| ()),
+                    );
                     let msg = match result {
-                        Ok(_) => ("Cleared".to_string(), StatusColor::Green),
-                        Err(e) => (format!("Failed: {e}").to_string(), StatusColor::Red),
+                        Ok(_) => {
+                            trash::push(cleared).await;
+                            ("Cleared".to_string(), StatusColor::Green)
+                        }
+                        Err(e) => error::Error::from(e).status(),
                     };
                     let _ = tx.send(msg).await.ok();
                 });
@@ -1114,9 +6012,19 @@ impl App {
     }
 
     fn add_or_update_event(&mut self) {
+        // Memos and reminders stay on this machine
+        if !matches!(self.app_layout, MainArea::Memos | MainArea::Reminders)
+            && self.refuse_access(config::Access::Events)
+        {
+            return;
+        }
         self.updating_event_or_task = true;
         match self.app_layout {
             MainArea::Tasks(_) => {
+                if self.selected_task_is_read_only() {
+                    self.updating_event_or_task = false;
+                    return;
+                }
                 if let Some(selected_task) = self.selected_task() {
                     self.input_buffer = selected_task.0.title.as_ref().unwrap().to_string();
                     self.cursor_index = self.char_count();
@@ -1124,14 +6032,72 @@ impl App {
                     return;
                 }
             }
+            // Quick-add: the popup has no calendar to go back to
+            MainArea::Events if self.popup => {}
             MainArea::Events => {
                 if let Some(selected_event) = self.selected_event() {
+                    if vault::is_private(&selected_event.1) {
+                        self.updating_event_or_task = false;
+                        self.changing_status = (
+                            "Private events can't be edited; delete and re-add".to_string(),
+                            StatusColor::Red,
+                        );
+                        return;
+                    }
+                    if let Some(reason) = self.read_only_reason(&selected_event.1) {
+                        self.updating_event_or_task = false;
+                        self.changing_status = (reason.to_string(), StatusColor::Red);
+                        return;
+                    }
                     self.input_buffer = selected_event.0.summary.as_ref().unwrap().to_string();
                     self.cursor_index = self.char_count();
                     self.inputting = true;
                     return;
                 }
             }
+            MainArea::Calendars => {
+                self.updating_event_or_task = false;
+                if self.refuse_access(config::Access::Full) {
+                    return;
+                }
+                self.input_target = InputTarget::NewCalendar;
+                self.inputting = true;
+                return;
+            }
+            MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Compare
+            | MainArea::Day
+            | MainArea::Agenda
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::FreeBusy => return,
+            MainArea::Reminders => {
+                self.updating_event_or_task = false;
+                self.input_target = InputTarget::Command;
+                self.input_buffer = "remind ".to_string();
+                self.cursor_index = self.input_buffer.chars().count();
+                self.inputting = true;
+                return;
+            }
+            MainArea::Memos => {
+                self.updating_event_or_task = false;
+                self.input_target = InputTarget::Memo;
+                self.inputting = true;
+                return;
+            }
+            MainArea::Calendar if self.range_start.is_some() => {
+                self.updating_event_or_task = false;
+                self.start_range_event();
+                return;
+            }
             MainArea::Calendar | MainArea::Weather => {}
         }
         // 'a' adds event when on calendar
@@ -1139,9 +6105,77 @@ impl App {
         self.inputting = true
     }
 
+    // The marked days in order, inclusive
+    fn selected_range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let start = self.range_start?;
+        Some((start.min(self.current_date), start.max(self.current_date)))
+    }
+
+    // Input filled in with the marked days as an all-day range; the end typed is the day after
+    // the last one, as Google counts it
+    fn start_range_event(&mut self) {
+        let Some((first, last)) = self.selected_range() else {
+            return;
+        };
+        self.range_start = None;
+        self.input_buffer = format!(
+            "{} - {} ",
+            first.format("%Y/%-m/%-d"),
+            (last + Days::new(1)).format("%Y/%-m/%-d")
+        );
+        self.cursor_index = self.char_count();
+        self.inputting = true;
+    }
+
     fn exit(&mut self) {
+        if self.event_details {
+            self.event_details = false;
+            return;
+        }
+        if self.range_start.take().is_some() {
+            return;
+        }
+        if let MainArea::Events = self.app_layout {
+            self.event_filter = events::EventFilter::default();
+        }
         match self.app_layout {
-            MainArea::Events | MainArea::Weather => {
+            MainArea::Events if self.popup => {
+                self.exit = true;
+            }
+            MainArea::Events if self.zoomed => {
+                self.zoomed = false;
+                self.app_layout = MainArea::Calendar;
+            }
+            MainArea::Events
+            | MainArea::Weather
+            | MainArea::Calendars
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Compare
+            | MainArea::Day
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders
+            | MainArea::FreeBusy => {
+                self.app_layout = MainArea::Calendar;
+            }
+            MainArea::Slots => {
+                self.meeting_slots.clear();
+                self.meeting_draft = None;
+                self.app_layout = MainArea::Calendar;
+            }
+            MainArea::Plan => {
+                self.plan_blocks.clear();
+                self.app_layout = MainArea::Calendar;
+            }
+            MainArea::Review => {
+                self.review = None;
                 self.app_layout = MainArea::Calendar;
             }
             MainArea::Tasks(true) => {
@@ -1161,7 +6195,28 @@ impl App {
             MainArea::Calendar | MainArea::Events => {
                 self.current_date = self.current_date.succ_opt().unwrap();
             }
-            MainArea::Weather => {}
+            MainArea::Compare => self.current_date = self.current_date + Days::new(7),
+            MainArea::Day | MainArea::FreeBusy => {
+                self.current_date = self.current_date + Days::new(1)
+            }
+            MainArea::Report => {
+                self.current_date = self.report_period.shift(self.current_date, true)
+            }
+            MainArea::Weather
+            | MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders => {}
         }
     }
 
@@ -1173,21 +6228,62 @@ impl App {
             MainArea::Calendar | MainArea::Events => {
                 self.current_date = self.current_date.pred_opt().unwrap();
             }
-            MainArea::Weather => {}
+            MainArea::Compare => self.current_date = self.current_date - Days::new(7),
+            MainArea::Day | MainArea::FreeBusy => {
+                self.current_date = self.current_date - Days::new(1)
+            }
+            MainArea::Report => {
+                self.current_date = self.report_period.shift(self.current_date, false)
+            }
+            MainArea::Weather
+            | MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders => {}
         }
     }
 
     fn move_up(&mut self) {
         match self.app_layout {
-            MainArea::Events | MainArea::Tasks(_) => {
+            MainArea::Events
+            | MainArea::Tasks(_)
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Starred
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders => {
                 if self.cursor_line > 0 {
                     self.cursor_line = self.cursor_line - 1;
                 }
             }
-            MainArea::Calendar => {
+            MainArea::FreeBusy => self.current_date = self.current_date - Days::new(1),
+            MainArea::Calendars => {
+                if self.cursor_line > 0 {
+                    self.cursor_line -= 1;
+                    self.acl_rules.clear();
+                }
+            }
+            MainArea::Calendar | MainArea::Compare => {
                 self.current_date = self.current_date.checked_sub_days(Days::new(7)).unwrap();
             }
-            MainArea::Weather => {}
+            MainArea::Day => self.cursor_line = self.cursor_line.saturating_sub(1),
+            MainArea::Weather | MainArea::Conflicts | MainArea::Report | MainArea::Search => {}
         }
     }
 
@@ -1203,10 +6299,78 @@ impl App {
                     self.cursor_line = self.cursor_line + 1;
                 }
             }
-            MainArea::Calendar => {
+            MainArea::Slots => {
+                if self.cursor_line + 1 < self.meeting_slots.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Plan => {
+                if self.cursor_line + 1 < self.plan_blocks.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Review => {
+                if self
+                    .review
+                    .as_ref()
+                    .is_some_and(|r| self.cursor_line + 1 < r.len())
+                {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Audit => {
+                if self.cursor_line + 1 < self.audit_entries.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Trash => {
+                if self.cursor_line + 1 < self.trash_items.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Starred => {
+                if self.cursor_line + 1 < self.starred_events().len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Agenda => {
+                if self.cursor_line + 1 < self.agenda().len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Memos => {
+                if self.cursor_line + 1 < self.memos.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Invitations => {
+                if self.cursor_line + 1 < self.invitations().len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Birthdays => {
+                if self.cursor_line + 1 < self.upcoming_birthdays().len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Reminders => {
+                if self.cursor_line + 1 < self.local_reminders.len() {
+                    self.cursor_line += 1;
+                }
+            }
+            MainArea::Conflicts => {}
+            MainArea::Calendars => {
+                if self.cursor_line + 1 < self.calendars_cache.len() {
+                    self.cursor_line += 1;
+                    self.acl_rules.clear();
+                }
+            }
+            MainArea::Calendar | MainArea::Compare => {
                 self.current_date = self.current_date.checked_add_days(Days::new(7)).unwrap();
             }
-            MainArea::Weather => {}
+            MainArea::Day => self.cursor_line = (self.cursor_line + 1).min(23),
+            MainArea::FreeBusy => self.current_date = self.current_date + Days::new(1),
+            MainArea::Weather | MainArea::Report | MainArea::Search => {}
         }
     }
 
@@ -1215,6 +6379,138 @@ impl App {
             MainArea::Events => MainArea::Calendar,
             _ => MainArea::Events,
         };
+        self.event_details = false;
+        self.zoomed = false;
+        self.event_filter = events::EventFilter::default();
+        self.cursor_line = self.next_event_line();
+    }
+
+    // Calendar -> zoomed day -> back to the calendar; the normal day box zooms in place
+    fn toggle_zoom(&mut self) {
+        match self.app_layout {
+            MainArea::Calendar => {
+                self.app_layout = MainArea::Events;
+                self.cursor_line = self.next_event_line();
+                self.zoomed = true;
+            }
+            MainArea::Events if self.zoomed && !self.popup => {
+                self.app_layout = MainArea::Calendar;
+                self.zoomed = false;
+                self.event_filter = events::EventFilter::default();
+            }
+            MainArea::Events => self.zoomed = true,
+            _ => {}
+        }
+    }
+    fn toggle_calendars_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Calendars => MainArea::Calendar,
+            _ => MainArea::Calendars,
+        };
+        self.acl_rules.clear();
+        self.cursor_line = 0;
+    }
+    fn toggle_audit_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Audit => MainArea::Calendar,
+            _ => {
+                self.audit_entries = audit::load_recent(200);
+                MainArea::Audit
+            }
+        };
+        self.cursor_line = 0;
+    }
+    fn toggle_compare_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Compare => MainArea::Calendar,
+            _ => {
+                self.archive = file_writing::load_archive();
+                MainArea::Compare
+            }
+        };
+        self.cursor_line = 0;
+    }
+    // Scrolled so the day's first event, or 08:00, is at the top; today with
+    // display.fade_past, so the current time is mid-way down
+    fn toggle_day_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Day => {
+                self.cursor_line = 0;
+                MainArea::Calendar
+            }
+            _ if self.config.display.fade_past && self.current_date == self.today => {
+                // Two rows an hour, under the title, status and hint lines, the borders and
+                // the all-day row
+                let rows = crossterm::terminal::size().map_or(24, |(_, rows)| rows);
+                let hours = rows.saturating_sub(6) as usize / 2;
+                let now = Local::now().with_timezone(&self.app_tz).hour() as usize;
+                self.cursor_line = now.saturating_sub(hours / 2);
+                MainArea::Day
+            }
+            _ => {
+                self.cursor_line = self
+                    .events_on(self.current_date)
+                    .filter_map(|(ev, _)| ev.start.as_ref()?.date_time)
+                    .map(|start| start.with_timezone(&self.app_tz).hour() as usize)
+                    .min()
+                    .unwrap_or(8)
+                    .min(8);
+                MainArea::Day
+            }
+        };
+    }
+    fn toggle_memos_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Memos => MainArea::Calendar,
+            _ => MainArea::Memos,
+        };
+        self.cursor_line = 0;
+    }
+    fn toggle_invitations_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Invitations => MainArea::Calendar,
+            _ => MainArea::Invitations,
+        };
+        self.cursor_line = 0;
+    }
+    fn toggle_agenda_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Agenda => MainArea::Calendar,
+            _ => MainArea::Agenda,
+        };
+        self.cursor_line = self.next_agenda_line();
+    }
+    // Widens (positive) or narrows the side pane and remembers it; None goes back to the config
+    fn resize_pane(&mut self, by: Option<i16>) {
+        let size = match by {
+            Some(by) => (self.pane_size as i16 + by)
+                .clamp(PANE_SIZE_MIN as i16, PANE_SIZE_MAX as i16) as u16,
+            None => self
+                .config
+                .display
+                .pane_size
+                .clamp(PANE_SIZE_MIN, PANE_SIZE_MAX),
+        };
+        self.pane_size = size;
+        file_writing::save_pane_size(by.map(|_| size));
+        self.changing_status = (format!("Side pane {size}%"), StatusColor::White);
+    }
+
+    fn toggle_starred_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Starred => MainArea::Calendar,
+            _ => MainArea::Starred,
+        };
+        self.cursor_line = 0;
+    }
+    fn toggle_trash_visibility(&mut self) {
+        self.app_layout = match self.app_layout {
+            MainArea::Trash => MainArea::Calendar,
+            _ => {
+                self.trash_items = trash::load();
+                MainArea::Trash
+            }
+        };
         self.cursor_line = 0;
     }
     fn toggle_tasks_visibility(&mut self) {
@@ -1228,27 +6524,121 @@ impl App {
 
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let countdowns: Vec<&config::Countdown> = self
+            .config
+            .countdowns
+            .iter()
+            .filter(|c| c.date >= self.today)
+            .collect();
+        let clock_height = if self.config.world_clock.is_empty() && countdowns.is_empty() {
+            0
+        } else {
+            1
+        };
+        let [clock_area, main_area] = Layout::new(
+            Direction::Vertical,
+            [Constraint::Length(clock_height), Constraint::Fill(1)],
+        )
+        .areas(area);
         let main_chunks = Layout::new(
             Direction::Vertical,
             [
                 Constraint::Length(1),
                 Constraint::Fill(1),
                 Constraint::Length(1),
+                Constraint::Length(self.config.display.hints as u16),
             ],
         )
-        .split(area);
+        .split(main_area);
+
+        // Hint bar
+        if self.config.display.hints {
+            Line::from(format!(" {}", self.hints()))
+                .dim()
+                .render(main_chunks[3], buf);
+        }
+
+        // World clock strip, with a day offset where the date differs from here,
+        // followed by a chip per upcoming countdown
+        if clock_height > 0 {
+            let now = Utc::now();
+            let today = now.with_timezone(&self.app_tz).date_naive();
+            let mut spans = Vec::new();
+            for clock in &self.config.world_clock {
+                let there = now.with_timezone(&clock.tz);
+                let days = (there.date_naive() - today).num_days();
+                let offset = match days {
+                    0 => String::new(),
+                    d => format!(" ({d:+})"),
+                };
+                spans.push(Span::raw(format!("{} ", clock.name)).dark_gray());
+                spans.push(Span::raw(format!(
+                    "{}{offset}   ",
+                    there.format(self.config.display.time_format())
+                )));
+            }
+            for countdown in &countdowns {
+                let days = (countdown.date - self.today).num_days();
+                let left = match days {
+                    0 => self.locale.t("today").to_string(),
+                    d => format!("{d}d"),
+                };
+                spans.push(
+                    Span::raw(format!(" {} {left} ", countdown.name))
+                        .black()
+                        .on_magenta(),
+                );
+                spans.push(Span::raw(" "));
+            }
+            Line::from(spans).centered().render(clock_area, buf);
+        }
+
+        let (content_area, panel_area) = panels::split(
+            main_chunks[1],
+            self.config.panels.slot,
+            self.config.panels.size,
+            !self.panels.is_empty(),
+        );
+        if let Some(panel_area) = panel_area {
+            panels::render_all(&self.panels, self.config.panels.slot, panel_area, buf);
+        }
 
         let main_area = match self.app_layout {
+            MainArea::Calendar if self.config.display.day_panel => Layout::new(
+                Direction::Horizontal,
+                Constraint::from_percentages([100 - self.pane_size, self.pane_size]),
+            )
+            .split(content_area),
             MainArea::Tasks(_) => Layout::new(
                 Direction::Horizontal,
-                Constraint::from_percentages([70, 30]),
+                Constraint::from_percentages([100 - self.pane_size, self.pane_size]),
             )
-            .split(main_chunks[1]),
-            MainArea::Calendar | MainArea::Events | MainArea::Weather => Layout::new(
+            .split(content_area),
+            MainArea::Calendar
+            | MainArea::Events
+            | MainArea::Weather
+            | MainArea::Calendars
+            | MainArea::Slots
+            | MainArea::Plan
+            | MainArea::Review
+            | MainArea::Audit
+            | MainArea::Trash
+            | MainArea::Conflicts
+            | MainArea::Starred
+            | MainArea::Compare
+            | MainArea::Day
+            | MainArea::Agenda
+            | MainArea::Memos
+            | MainArea::Report
+            | MainArea::Search
+            | MainArea::Invitations
+            | MainArea::Birthdays
+            | MainArea::Reminders
+            | MainArea::FreeBusy => Layout::new(
                 Direction::Horizontal,
                 Constraint::from_percentages([100, 0]),
             )
-            .split(main_chunks[1]),
+            .split(content_area),
         };
 
         // Title area
@@ -1259,32 +6649,72 @@ impl Widget for &App {
         .split(main_chunks[0]);
 
         // Title
-        Paragraph::new(self.current_date.format("%Y %B").to_string())
-            .centered()
-            .style(Modifier::BOLD)
-            .render(title_area[1], buf);
+        let title_format = if self.popup {
+            &self.config.display.date_format
+        } else {
+            &self.config.display.month_format
+        };
+        Paragraph::new(
+            self.current_date
+                .format(
+                    &self
+                        .locale
+                        .localize_format(title_format, &self.current_date),
+                )
+                .to_string(),
+        )
+        .centered()
+        .style(Modifier::BOLD)
+        .render(title_area[1], buf);
+
+        // Current conditions, once a fetch or the cache has them
+        if self.config.display.header_weather
+            && let Some(weather) = &self.onecall_weather
+        {
+            let glyph = weather
+                .current
+                .weather
+                .first()
+                .map_or("", |w| weather::glyph(&w.icon));
+            let mut now =
+                Line::raw(format!("{glyph} {:.0}°C", weather.current.temp)).right_aligned();
+            if self.weather_stale() {
+                now = now.dim();
+            }
+            now.render(title_area[1], buf);
+        }
 
         // Refreshing status
         let status_area = title_area[0].inner(ratatui::layout::Margin {
             vertical: 0,
             horizontal: 1,
         });
-        let status = Paragraph::new(self.refreshing_status.clone().0).style(Modifier::BOLD);
-        match self.refreshing_status.clone().1 {
-            StatusColor::Green => status.green().render(status_area, buf),
-            StatusColor::Yellow => status.yellow().render(status_area, buf),
-            StatusColor::Red => status.red().render(status_area, buf),
-            _ => status.render(status_area, buf),
+        let status = Paragraph::new(self.locale.t(&self.refreshing_status.0)).style(Modifier::BOLD);
+        match self.refreshing_status.1 {
+            StatusColor::White => status.render(status_area, buf),
+            ref color => status.fg(color.color(&self.theme)).render(status_area, buf),
         }
 
         // Online status
         let auth_status = match self.auth_status {
-            AuthStatus::Authenticating => "Authenticating".yellow(),
-            AuthStatus::Online => "Online".green(),
-            AuthStatus::Offline => "Offline".dim(),
+            AuthStatus::Authenticating => self.locale.t("Authenticating").yellow(),
+            AuthStatus::Online => self.locale.t("Online").green(),
+            AuthStatus::Offline => self.locale.t("Offline").dim(),
+        };
+
+        // API usage meter: five cells for the busier of the per-minute and per-day quotas
+        let load = usage::load(&self.config.quota);
+        let filled = ((load * 5.0).ceil() as usize).min(5);
+        let meter = format!("{}{}", "▰".repeat(filled), "▱".repeat(5 - filled));
+        let meter = if load >= self.config.quota.warn_at {
+            meter.red()
+        } else if load >= 0.5 {
+            meter.yellow()
+        } else {
+            meter.dim()
         };
 
-        Paragraph::new(auth_status.into_right_aligned_line()).render(
+        Paragraph::new(Line::from(vec![meter, " ".into(), auth_status]).right_aligned()).render(
             title_area[2].inner(ratatui::layout::Margin {
                 vertical: 0,
                 horizontal: 1,
@@ -1292,8 +6722,19 @@ impl Widget for &App {
             buf,
         );
 
-        // Calendar area
-        let calendar_area = main_area[0];
+        let series_titles = events::series_titles(&self.events_cache);
+
+        // Calendar area, with the selected week's timeline below it in split mode
+        let calendar_area = if self.config.display.week_split {
+            let [grid_area, week_area] =
+                Layout::new(Direction::Vertical, Constraint::from_percentages([55, 45]))
+                    .areas(main_area[0]);
+            let week_area = self.with_mini_months(week_area, buf);
+            self.render_week_timeline(week_area, buf);
+            grid_area
+        } else {
+            main_area[0]
+        };
         let (drawn_dates, number_of_rows) = self.generate_calendar_grid();
         let height = (calendar_area.height as usize) / (number_of_rows);
 
@@ -1331,12 +6772,12 @@ impl Widget for &App {
             bottom_right: symbols::line::NORMAL.vertical_left,
             ..symbols::border::PLAIN
         };
-        let weekdays = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+        let weekdays = self.locale.weekdays();
         for (i, &day) in weekdays.iter().enumerate() {
             let cell_border = Block::default();
             if i == 0 {
                 // Sunday
-                let name = Text::styled(day, Color::Red);
+                let name = Text::styled(day, self.theme.sunday);
                 let cell = Paragraph::new(name).centered();
                 let day_block = cell_border
                     .borders(Borders::BOTTOM | Borders::TOP | Borders::LEFT)
@@ -1344,7 +6785,7 @@ impl Widget for &App {
                 cell.block(day_block).render(weekday_cols[i], buf)
             } else if i == 6 {
                 // Saturday
-                let name = Text::styled(day, Color::Blue);
+                let name = Text::styled(day, self.theme.saturday);
                 let cell = Paragraph::new(name).centered();
                 let day_block = cell_border
                     .borders(Borders::ALL)
@@ -1362,6 +6803,8 @@ impl Widget for &App {
 
         // Days Area
         let cursor_date = self.current_date.day();
+        let continuing =
+            self.continuing_events(drawn_dates[0][0].0..=drawn_dates[number_of_rows - 1][6].0);
 
         for (row_index, row_chunk) in calendar_rows[1..(number_of_rows + 1)].iter().enumerate() {
             let horizontal_chunks = Layout::default()
@@ -1376,50 +6819,198 @@ impl Widget for &App {
                 let current_date = current_cell.0.day();
                 let is_cursor_here = cursor_date == current_date && current_cell.1;
                 let focus_on_calendar = matches!(self.app_layout, MainArea::Calendar);
-                let day = if is_cursor_here && focus_on_calendar {
-                    ratatui::widgets::ListItem::new(format!("{}{:<30}", current_date, " "))
-                        .on_dark_gray()
+                let location = self
+                    .working_location_event(current_cell.0)
+                    .and_then(|(event, _)| events::working_location_label(event))
+                    .map(|label| format!(" @{label}"))
+                    .unwrap_or_default();
+                let mut markers = if self.config.display.moon_phase {
+                    format!(" {}", moon::phase_glyph(current_cell.0))
                 } else {
-                    ratatui::widgets::ListItem::new(format!("{}", current_date))
+                    String::new()
                 };
-
-                let empty_vec = &vec![];
-                let today_events = self.events_cache.get(&current_cell.0).unwrap_or(empty_vec);
-
-                let mut items: Vec<ratatui::widgets::ListItem> = if today_events.is_empty() {
-                    vec![]
+                if self.note_dates.contains(&current_cell.0) {
+                    markers.push_str(" ✎");
+                }
+                if self.over_capacity(current_cell.0) {
+                    let load = self.day_load(current_cell.0);
+                    markers.push_str(&format!(" ⚠{}h", (load.num_minutes() + 59) / 60));
+                }
+                let cursor = is_cursor_here && focus_on_calendar;
+                let in_range = current_cell.1
+                    && self
+                        .selected_range()
+                        .is_some_and(|(first, last)| (first..=last).contains(&current_cell.0));
+                // Highlighted across the cell's width
+                let location = if in_range || cursor {
+                    format!("{location:<30}")
                 } else {
-                    today_events
-                        .iter()
-                        .map(|ev| {
-                            let title = ev.0.summary.as_deref().unwrap_or("Untitled");
-                            let time =
-                                ev.0.start
-                                    .as_ref()
-                                    .and_then(|s| s.date_time)
-                                    .map(|dt| {
-                                        dt.with_timezone(&self.app_tz).format("%H:%M ").to_string()
-                                    })
-                                    .unwrap_or("".to_string());
-                            let e = if current_cell.1 {
-                                Text::raw(format!("{time}{title}"))
+                    location
+                };
+                let mut day =
+                    ratatui::widgets::ListItem::new(format!("{current_date}{markers}{location}"));
+                if let Some(background) =
+                    self.day_number_background(current_cell.0, current_cell.1, cursor)
+                {
+                    day = day.bg(background);
+                }
+                if let Some(color) = self.day_number_color(current_cell.0, current_cell.1) {
+                    day = day.fg(color);
+                }
+
+                let mut items: Vec<ratatui::widgets::ListItem> = self
+                    .events_on(current_cell.0)
+                    .filter(|ev| events::EventKind::of(&ev.0) != events::EventKind::WorkingLocation)
+                    .map(|ev| {
+                        let mut title = format!(
+                            "{}{}",
+                            events::response_marker(&ev.0),
+                            self.display_title(ev)
+                        );
+                        // Modified instance of a recurring series
+                        if events::exception_note(
+                            &ev.0,
+                            &series_titles,
+                            self.app_tz,
+                            &self.config.display,
+                        )
+                        .is_some()
+                        {
+                            title.push('*');
+                        }
+                        let time =
+                            ev.0.start
+                                .as_ref()
+                                .and_then(|s| s.date_time)
+                                .map(|dt| {
+                                    format!(
+                                        "{} ",
+                                        dt.with_timezone(&self.app_tz)
+                                            .format(self.config.display.time_format())
+                                    )
+                                })
+                                .unwrap_or("".to_string());
+                        let kind = events::EventKind::of(&ev.0);
+                        if kind.is_background() {
+                            // Shaded span across the whole cell width
+                            let label = format!("{} ", kind.label());
+                            let title = bidi::truncate_display(
+                                &title,
+                                (cell_chunk.width as usize).saturating_sub(label.len()),
+                            );
+                            let span = format!(
+                                "{:<width$}",
+                                format!("{label}{title}"),
+                                width = cell_chunk.width as usize
+                            );
+                            let item = ratatui::widgets::ListItem::new(span).bg(Color::DarkGray);
+                            return if current_cell.1 {
+                                item.fg(kind.color())
                             } else {
-                                Text::raw(format!("{time}{title}")).dark_gray()
+                                item.fg(Color::Black)
                             };
-                            ratatui::widgets::ListItem::new(e)
-                        })
-                        .collect()
-                };
+                        }
+                        // ▸ when it carries on into the next day
+                        let more = if events::all_day_span(&ev.0) > 1 {
+                            "▸"
+                        } else {
+                            ""
+                        };
+                        let title = bidi::truncate_display(
+                            &title,
+                            (cell_chunk.width as usize)
+                                .saturating_sub(time.len() + more.chars().count()),
+                        );
+                        let e = if current_cell.1 {
+                            let text = Text::raw(format!("{time}{title}{more}"));
+                            match self.calendar_color(&ev.1) {
+                                Some(color) => text.fg(color),
+                                None => text,
+                            }
+                        } else {
+                            Text::raw(format!("{time}{title}{more}")).dark_gray()
+                        };
+                        ratatui::widgets::ListItem::new(
+                            e.add_modifier(events::response_modifier(&ev.0)),
+                        )
+                    })
+                    .collect();
+                // Days after the first of a multi-day event: ◂ title, ▸ again if it goes on
+                let carried =
+                    continuing
+                        .get(&current_cell.0)
+                        .into_iter()
+                        .flatten()
+                        .map(|(ev, goes_on)| {
+                            let more = if *goes_on { "▸" } else { "" };
+                            let title = bidi::truncate_display(
+                                &format!(
+                                    "{}{}",
+                                    events::response_marker(&ev.0),
+                                    self.display_title(ev)
+                                ),
+                                (cell_chunk.width as usize)
+                                    .saturating_sub(1 + more.chars().count()),
+                            );
+                            let text = Text::raw(format!("◂{title}{more}"));
+                            let text = if !current_cell.1 {
+                                text.dark_gray()
+                            } else {
+                                match self.calendar_color(&ev.1) {
+                                    Some(color) => text.fg(color),
+                                    None => text,
+                                }
+                            };
+                            ratatui::widgets::ListItem::new(
+                                text.add_modifier(events::response_modifier(&ev.0)),
+                            )
+                        });
+                items.splice(0..0, carried);
+                // Too narrow for titles to read: a dot an event instead
+                if (cell_chunk.width as usize) < DENSE_CELL_WIDTH && !items.is_empty() {
+                    let calendars: Vec<&str> = continuing
+                        .get(&current_cell.0)
+                        .into_iter()
+                        .flatten()
+                        .map(|(ev, _)| ev.1.as_str())
+                        .chain(
+                            self.events_on(current_cell.0)
+                                .filter(|ev| {
+                                    events::EventKind::of(&ev.0)
+                                        != events::EventKind::WorkingLocation
+                                })
+                                .map(|ev| ev.1.as_str()),
+                        )
+                        .collect();
+                    items = vec![ratatui::widgets::ListItem::new(self.density_line(
+                        &calendars,
+                        current_cell.1,
+                        cell_chunk.width.saturating_sub(2) as usize,
+                    ))];
+                }
+                // More than fit: a window of them and a line counting the rest
+                let room = cell_chunk.height.saturating_sub(2) as usize;
+                if room > 0 && items.len() > room {
+                    let shown = room - 1;
+                    let offset = if current_cell.0 == self.cell_scroll.0 && current_cell.1 {
+                        self.cell_scroll.1.min(items.len() - shown)
+                    } else {
+                        0
+                    };
+                    let below = items.len() - offset - shown;
+                    items = items.into_iter().skip(offset).take(shown).collect();
+                    let mut more = Vec::new();
+                    if offset > 0 {
+                        more.push(format!("↑{offset}"));
+                    }
+                    if below > 0 {
+                        more.push(format!("+{below} {}", self.locale.t("more")));
+                    }
+                    items.push(ratatui::widgets::ListItem::new(more.join(" ")).dark_gray());
+                }
 
                 if col_index == 0 {
                     // Sunday
-                    let day = if current_cell.2 {
-                        day.green()
-                    } else if current_cell.1 {
-                        day.red()
-                    } else {
-                        day.dark_gray()
-                    };
                     items.insert(0, day);
                     let cell = ratatui::widgets::List::new(items);
                     let day_block = cell_border.borders(Borders::BOTTOM | Borders::LEFT);
@@ -1428,16 +7019,10 @@ impl Widget for &App {
                     } else {
                         day_block.border_set(left_bottom_border)
                     };
-                    cell.block(day_block).render(*cell_chunk, buf)
+                    cell.block(day_block).render(*cell_chunk, buf);
+                    self.render_daylight_bar(current_cell.0, *cell_chunk, buf);
                 } else if col_index == 6 {
                     // Saturday
-                    let day = if current_cell.2 {
-                        day.green()
-                    } else if current_cell.1 {
-                        day.blue()
-                    } else {
-                        day.dark_gray()
-                    };
                     items.insert(0, day);
                     let cell = ratatui::widgets::List::new(items);
                     let day_block =
@@ -1447,16 +7032,10 @@ impl Widget for &App {
                     } else {
                         day_block.border_set(right_bottom_border)
                     };
-                    cell.block(day_block).render(*cell_chunk, buf)
+                    cell.block(day_block).render(*cell_chunk, buf);
+                    self.render_daylight_bar(current_cell.0, *cell_chunk, buf);
                 } else {
                     // Weekdays
-                    let day = if current_cell.2 {
-                        day.green()
-                    } else if current_cell.1 {
-                        day
-                    } else {
-                        day.dark_gray()
-                    };
                     items.insert(0, day);
                     let cell = ratatui::widgets::List::new(items);
                     let day_block = cell_border.borders(Borders::BOTTOM | Borders::LEFT);
@@ -1466,94 +7045,109 @@ impl Widget for &App {
                     } else {
                         day_block.border_set(left_bottom_border_cross)
                     };
-                    cell.block(day_block).render(*cell_chunk, buf)
+                    cell.block(day_block).render(*cell_chunk, buf);
+                    self.render_daylight_bar(current_cell.0, *cell_chunk, buf);
                 }
             }
         }
 
         match self.app_layout {
             MainArea::Events => {
-                let event_area_horizontal = Layout::new(
-                    Direction::Vertical,
-                    Constraint::from_percentages([16, 68, 16]),
-                )
-                .split(main_area[0]);
-                let event_area = Layout::new(
-                    Direction::Horizontal,
-                    Constraint::from_percentages([20, 60, 20]),
-                )
-                .split(event_area_horizontal[1]);
-                Clear::default().render(event_area[1], buf);
-
-                let empty_vec = &vec![];
-                let today_events = self
-                    .events_cache
-                    .get(&self.current_date)
-                    .unwrap_or(empty_vec);
-
-                let items: Vec<ratatui::widgets::ListItem> = if today_events.is_empty() {
-                    Vec::new()
+                let event_area = if self.popup || self.zoomed {
+                    main_area[0]
                 } else {
-                    today_events
-                        .iter()
-                        .enumerate()
-                        .map(|(i, ev)| {
-                            let title = ev.0.summary.as_deref().unwrap_or("Untitled");
-                            let start_time =
-                                ev.0.start
-                                    .as_ref()
-                                    .and_then(|s| s.date_time)
-                                    .map(|dt| {
-                                        dt.with_timezone(&self.app_tz).format(" %H:%M ").to_string()
-                                    })
-                                    .unwrap_or(" ".to_string());
-                            let end_time =
-                                ev.0.end
-                                    .as_ref()
-                                    .and_then(|s| s.date_time)
-                                    .map(|dt| {
-                                        dt.with_timezone(&self.app_tz)
-                                            .format("- %H:%M ")
-                                            .to_string()
-                                    })
-                                    .unwrap_or("".to_string());
-                            let mut item = ratatui::widgets::ListItem::new(format!(
-                                "{start_time}{end_time}{title}"
-                            ));
-                            if Some(i) == self.selected_event_index() {
-                                item = item.bg(Color::DarkGray).fg(Color::White);
-                            };
-                            item
-                        })
-                        .collect()
+                    centered_popup(main_area[0])
                 };
+                Clear::default().render(event_area, buf);
 
-                ratatui::widgets::List::new(items)
-                    .block(Block::bordered().title("Events"))
-                    .render(event_area[1], buf);
+                let today_events = self.current_day_events();
+
+                let selected = self.selected_event_index();
+                let items: Vec<ratatui::widgets::ListItem> = today_events
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ev)| self.event_list_item(i, ev, &series_titles, Some(i) == selected))
+                    .collect();
+
+                let mut title = match self
+                    .working_location_event(self.current_date)
+                    .and_then(|(event, _)| events::working_location_label(event))
+                {
+                    Some(label) => format!("{} @{label}", self.locale.t("Events")),
+                    None => self.locale.t("Events").to_string(),
+                };
+                title.push_str(&self.event_filter_label());
+                let mut block = Block::bordered().title(title);
+                if let Some(note) = notes::read(self.current_date)
+                    && let Some(line) = note.lines().find(|l| !l.trim().is_empty())
+                {
+                    block =
+                        block.title_bottom(Line::from(format!("✎ {}", line.trim())).left_aligned());
+                }
+                if let Some(sun) = self.sun_label(self.current_date) {
+                    block = block.title_bottom(Line::from(sun).right_aligned());
+                }
+                // Scrolls to keep the selection in view when the day doesn't fit
+                let mut state = ratatui::widgets::ListState::default()
+                    .with_selected(self.selected_event_index());
+                if self.config.display.fade_past
+                    && let Some(selected) = self.selected_event_index()
+                {
+                    *state.offset_mut() = centered_offset(
+                        selected,
+                        today_events.len(),
+                        event_area.height.saturating_sub(2),
+                    );
+                }
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(block),
+                    event_area,
+                    buf,
+                    &mut state,
+                );
+                if self.event_details {
+                    self.render_event_details(main_area[0], buf);
+                }
             }
 
             MainArea::Tasks(notes_visible) => {
-                let tasks = self.tasks_cache.clone();
                 let items: Vec<Span> = {
-                    tasks
+                    self.tasks_cache
                         .iter()
                         .enumerate()
                         .map(|(i, ev)| {
                             let title = ev.0.title.as_deref().unwrap_or("Untitled");
-                            let time = match ev.0.due.as_deref() {
-                                Some(duedate) => match DateTime::parse_from_rfc3339(duedate) {
-                                    Ok(e) => e.date_naive().format("%Y/%m/%d ").to_string(),
-                                    Err(_) => "".to_string(),
-                                },
-                                None => "".to_string(),
-                            };
+                            let time =
+                                match ev.0.due.as_deref() {
+                                    Some(duedate) => match DateTime::parse_from_rfc3339(duedate) {
+                                        Ok(e) => format!(
+                                            "{} ",
+                                            e.date_naive().format(&self.locale.localize_format(
+                                                &self.config.display.date_format,
+                                                &e
+                                            ))
+                                        ),
+                                        Err(_) => "".to_string(),
+                                    },
+                                    None => "".to_string(),
+                                };
+                            let title = bidi::display(title);
+                            let badge = issues::badge(&ev.1);
+                            let overdue =
+                                ev.0.due
+                                    .as_deref()
+                                    .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                                    .is_some_and(|d| d.date_naive() < Local::now().date_naive());
                             let mut item = match ev.0.completed {
-                                Some(_) => Span::raw(format!("{time}{title}")).dark_gray(),
-                                None => Span::raw(format!("{time}{title}")),
+                                Some(_) => {
+                                    Span::raw(format!("{time}{badge}{title}")).fg(self.theme.dimmed)
+                                }
+                                None if overdue => Span::raw(format!("{time}{badge}{title}"))
+                                    .fg(self.theme.overdue),
+                                None => Span::raw(format!("{time}{badge}{title}")),
                             };
                             if Some(i) == self.selected_task_index() {
-                                item = item.bg(Color::DarkGray).fg(Color::White);
+                                item = item.bg(self.theme.selected).fg(self.theme.selected_text);
                             };
                             item
                         })
@@ -1561,7 +7155,9 @@ impl Widget for &App {
                 };
 
                 ratatui::widgets::List::new(items)
-                    .block(Block::bordered().title("Tasks".bold().into_centered_line()))
+                    .block(
+                        Block::bordered().title(self.locale.t("Tasks").bold().into_centered_line()),
+                    )
                     .render(
                         main_area[1].inner(ratatui::layout::Margin {
                             vertical: 1,
@@ -1571,37 +7167,32 @@ impl Widget for &App {
                     );
 
                 if notes_visible && let Some(selected_task) = self.selected_task() {
-                    let task_area_horizontal = Layout::new(
-                        Direction::Vertical,
-                        Constraint::from_percentages([16, 68, 16]),
-                    )
-                    .split(main_area[0]);
-                    let task_area = Layout::new(
-                        Direction::Horizontal,
-                        Constraint::from_percentages([20, 60, 20]),
-                    )
-                    .split(task_area_horizontal[1]);
-                    Clear::default().render(task_area[1], buf);
+                    let task_area = centered_popup(main_area[0]);
+                    Clear::default().render(task_area, buf);
 
-                    let task_notes = selected_task.0.notes.clone().unwrap_or("".to_string());
+                    let task_notes = selected_task.0.notes.as_deref().unwrap_or("");
 
-                    let task_title = selected_task.0.title.clone().unwrap_or("".to_string());
+                    let task_title = bidi::display(selected_task.0.title.as_deref().unwrap_or(""));
 
                     Paragraph::new(task_notes)
                         .wrap(ratatui::widgets::Wrap { trim: true })
                         .block(Block::bordered().title(task_title))
-                        .render(task_area[1], buf);
+                        .render(task_area, buf);
                 };
             }
             MainArea::Weather => {
                 let weather_area =
-                    main_area[0].centered(Constraint::Length(98), Constraint::Length(30));
+                    main_area[0].centered(Constraint::Length(98), Constraint::Length(38));
 
                 Clear::default().render(weather_area, buf);
 
                 let drawing_weather_area = Layout::new(
                     Direction::Vertical,
-                    [Constraint::Fill(1), Constraint::Fill(1)],
+                    [
+                        Constraint::Fill(1),
+                        Constraint::Fill(1),
+                        Constraint::Length(6),
+                    ],
                 )
                 .split(weather_area);
 
@@ -1642,23 +7233,41 @@ impl Widget for &App {
                     let general_weather = weather.main.clone();
                     let icon = weather::get_weather_icon(weather.icon.clone());
 
-                    let text = vec![
-                        Line::raw(format!("Current Weather"))
+                    let row = |label: &str, value: String| {
+                        Line::raw(format!("{:<15}┃  {value}", self.locale.t(label)))
+                    };
+                    // Say how old the numbers are once they're past the TTL, e.g. offline
+                    let age = match self.weather_fetched_at {
+                        Some(t) if self.weather_stale() => Line::raw(format!(
+                            "{} {}",
+                            self.locale.t("as of"),
+                            t.format(self.config.display.time_format())
+                        ))
+                        .centered()
+                        .dim(),
+                        _ => Line::raw(""),
+                    };
+                    let mut text = vec![
+                        Line::raw(self.locale.t("Current Weather"))
                             .centered()
                             .yellow()
                             .bold()
                             .italic(),
-                        Line::raw(format!("")),
-                        Line::raw(format!("Weather        ┃  {general_weather}")),
-                        Line::raw(format!("Temperature    ┃  {temperature}°C")),
-                        Line::raw(format!("Feels Like     ┃  {feels_like}°C")),
-                        Line::raw(format!("Humidity       ┃  {humidity}%")),
-                        Line::raw(format!("Precipitation  ┃  {precip} mm")),
-                        Line::raw(format!("Cloud cover    ┃  {clouds}%")),
-                        Line::raw(format!("Wind           ┃  {wind} m/s")),
-                        Line::raw(format!("Pressure       ┃  {pressure} hPa")),
-                        Line::raw(format!("UV Index       ┃  {uvi}")),
+                        age,
+                        row("Weather", general_weather),
+                        row("Temperature", format!("{temperature}°C")),
+                        row("Feels Like", format!("{feels_like}°C")),
+                        row("Humidity", format!("{humidity}%")),
+                        row("Precipitation", format!("{precip} mm")),
+                        row("Cloud cover", format!("{clouds}%")),
+                        row("Wind", format!("{wind} m/s")),
+                        row("Pressure", format!("{pressure} hPa")),
+                        row("UV Index", format!("{uvi}")),
                     ];
+                    if let Some(air) = &self.air_quality {
+                        let (level, color) = weather::aqi_level(air.us_aqi);
+                        text.push(row("Air quality", format!("{} {level}", air.us_aqi)).fg(color));
+                    }
 
                     ratatui::widgets::Paragraph::new(icon)
                         .block(Block::new().padding(ratatui::widgets::Padding {
@@ -1678,7 +7287,11 @@ impl Widget for &App {
                         }))
                         .render(current_weather_area[1], buf);
 
-                    fn render_weather<'a>(w: &weather::DailyWeather, title: &str) -> Vec<Line<'a>> {
+                    fn render_weather<'a>(
+                        w: &weather::DailyWeather,
+                        title: &str,
+                        locale: &i18n::Locale,
+                    ) -> Vec<Line<'a>> {
                         let forecasted_weather = w.weather[0].main.clone();
                         let temp_max = w.temp.max;
                         let temp_min = w.temp.min;
@@ -1692,28 +7305,32 @@ impl Widget for &App {
                             .as_ref()
                             .unwrap_or_else(|| w.snow.as_ref().unwrap_or(&0.0));
 
+                        let row = |label: &str, value: String| {
+                            Line::raw(format!("{:<15}┃  {value}", locale.t(label)))
+                        };
                         vec![
-                            Line::raw(format!("{title}"))
+                            Line::raw(title.to_string())
                                 .centered()
                                 .yellow()
                                 .bold()
                                 .italic(),
-                            Line::raw(format!("")),
-                            Line::raw(format!("Weather        ┃  {forecasted_weather}")),
-                            Line::raw(format!("Low            ┃  {temp_min}°C")),
-                            Line::raw(format!("High           ┃  {temp_max}°C")),
-                            Line::raw(format!("Humidity       ┃  {humidity}%")),
-                            Line::raw(format!("Precipitation  ┃  {precip} mm")),
-                            Line::raw(format!("Chance of rain ┃  {pop}%")),
-                            Line::raw(format!("Wind           ┃  {wind} m/s")),
-                            Line::raw(format!("Pressure       ┃  {pressure} hPa")),
-                            Line::raw(format!("UV Index       ┃  {uvi}")),
+                            Line::raw(""),
+                            row("Weather", forecasted_weather),
+                            row("Low", format!("{temp_min}°C")),
+                            row("High", format!("{temp_max}°C")),
+                            row("Humidity", format!("{humidity}%")),
+                            row("Precipitation", format!("{precip} mm")),
+                            row("Chance of rain", format!("{pop}%")),
+                            row("Wind", format!("{wind} m/s")),
+                            row("Pressure", format!("{pressure} hPa")),
+                            row("UV Index", format!("{uvi}")),
                         ]
                     }
 
                     ratatui::widgets::Paragraph::new(render_weather(
                         &current_weather.daily[0],
-                        "Today's Forecast",
+                        self.locale.t("Today's Forecast"),
+                        &self.locale,
                     ))
                     .block(Block::new().padding(ratatui::widgets::Padding {
                         right: 5,
@@ -1729,14 +7346,16 @@ impl Widget for &App {
                     )
                     .split(drawing_weather_area[1]);
 
+                    let forecast_day = self
+                        .today
+                        .checked_add_days(Days::new(self.weather_day.try_into().unwrap()))
+                        .unwrap();
                     ratatui::widgets::Paragraph::new(render_weather(
                         &current_weather.daily[self.weather_day],
-                        &self
-                            .today
-                            .checked_add_days(Days::new(self.weather_day.try_into().unwrap()))
-                            .unwrap()
-                            .format("%A, %B %d")
+                        &forecast_day
+                            .format(&self.locale.localize_format("%A, %B %d", &forecast_day))
                             .to_string(),
+                        &self.locale,
                     ))
                     .block(Block::bordered().padding(ratatui::widgets::Padding {
                         right: 10,
@@ -1746,14 +7365,13 @@ impl Widget for &App {
                     }))
                     .render(forecast_area[0], buf);
 
+                    let next_forecast_day = forecast_day.succ_opt().unwrap();
                     ratatui::widgets::Paragraph::new(render_weather(
                         &current_weather.daily[self.weather_day + 1],
-                        &self
-                            .today
-                            .checked_add_days(Days::new((self.weather_day + 1).try_into().unwrap()))
-                            .unwrap()
-                            .format("%A, %B %d")
+                        &next_forecast_day
+                            .format(&self.locale.localize_format("%A, %B %d", &next_forecast_day))
                             .to_string(),
+                        &self.locale,
                     ))
                     .block(Block::bordered().padding(ratatui::widgets::Padding {
                         right: 10,
@@ -1762,17 +7380,755 @@ impl Widget for &App {
                         bottom: 1,
                     }))
                     .render(forecast_area[1], buf);
+
+                    self.render_hourly(current_weather, forecast_day, drawing_weather_area[2], buf);
                 };
             }
+            MainArea::Calendars => {
+                let calendars_area = centered_popup(main_area[0]);
+                Clear.render(calendars_area, buf);
+
+                let panes = Layout::new(
+                    Direction::Horizontal,
+                    Constraint::from_percentages([50, 50]),
+                )
+                .split(calendars_area);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.calendars_cache.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .sorted_calendars()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (id, entry))| {
+                        let name = self.calendar_name(id);
+                        let role = entry.access_role.as_deref().unwrap_or("");
+                        let mut item = ratatui::widgets::ListItem::new(format!(" {name} ({role})"));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                ratatui::widgets::List::new(items)
+                    .block(Block::bordered().title(self.locale.t("Calendars")))
+                    .render(panes[0], buf);
+
+                let rules: Vec<ratatui::widgets::ListItem> = self
+                    .acl_rules
+                    .iter()
+                    .map(|rule| {
+                        let role = rule.role.as_deref().unwrap_or("");
+                        let who = rule
+                            .scope
+                            .as_ref()
+                            .and_then(|s| s.value.as_deref().or(s.type_.as_deref()))
+                            .unwrap_or("");
+                        ratatui::widgets::ListItem::new(format!(" {role:<15}{who}"))
+                    })
+                    .collect();
+                ratatui::widgets::List::new(rules)
+                    .block(
+                        Block::bordered()
+                            .title(self.locale.t("Sharing"))
+                            .title_bottom("Enter: load  a: new  s: share"),
+                    )
+                    .render(panes[1], buf);
+            }
+            MainArea::Slots => {
+                let slots_area = centered_popup(main_area[0]);
+                Clear.render(slots_area, buf);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.meeting_slots.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .meeting_slots
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (start, end))| {
+                        let mut item = ratatui::widgets::ListItem::new(format!(
+                            " {} - {}",
+                            start.format(&self.locale.localize_format(
+                                &format!(
+                                    "%a {} {}",
+                                    self.config.display.date_format,
+                                    self.config.display.time_format()
+                                ),
+                                start
+                            )),
+                            end.format(self.config.display.time_format())
+                        ));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let title = self
+                    .meeting_draft
+                    .as_ref()
+                    .map(|(attendees, title)| format!("{title} with {}", attendees.join(", ")))
+                    .unwrap_or_default();
+                ratatui::widgets::List::new(items)
+                    .block(
+                        Block::bordered()
+                            .title(title)
+                            .title_bottom("Enter: create and invite"),
+                    )
+                    .render(slots_area, buf);
+            }
+            MainArea::Review => {
+                let review_area = centered_popup(main_area[0]);
+                Clear.render(review_area, buf);
+
+                if let Some(review) = &self.review {
+                    let selected = self.cursor_line.min(review.len().saturating_sub(1));
+                    let lines: Vec<String> = if review.step == review::Step::NextWeek {
+                        review
+                            .next_week
+                            .iter()
+                            .map(|(date, ev)| {
+                                let time = ev
+                                    .start
+                                    .as_ref()
+                                    .and_then(|s| s.date_time)
+                                    .map(|dt| {
+                                        dt.with_timezone(&self.app_tz)
+                                            .format(self.config.display.time_format())
+                                            .to_string()
+                                    })
+                                    .unwrap_or_default();
+                                format!(
+                                    " {} {time:<7}{}",
+                                    date.format(&self.locale.localize_format("%a %d", date)),
+                                    bidi::display(ev.summary.as_deref().unwrap_or("(no title)"))
+                                )
+                            })
+                            .collect()
+                    } else {
+                        review
+                            .tasks()
+                            .iter()
+                            .map(|(task, _)| {
+                                let due = task
+                                    .due
+                                    .as_deref()
+                                    .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                                    .map(|d| {
+                                        format!("{} ", d.format(&self.config.display.date_format))
+                                    })
+                                    .unwrap_or_default();
+                                format!(
+                                    " [{:<11}] {due}{}",
+                                    self.locale.t(review.decision(task).label()),
+                                    bidi::display(task.title.as_deref().unwrap_or("Untitled"))
+                                )
+                            })
+                            .collect()
+                    };
+                    let items: Vec<ratatui::widgets::ListItem> = lines
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, line)| {
+                            let item = ratatui::widgets::ListItem::new(line);
+                            if i == selected {
+                                item.bg(self.theme.selected).fg(self.theme.selected_text)
+                            } else {
+                                item
+                            }
+                        })
+                        .collect();
+                    let step = match review.step {
+                        review::Step::Overdue => 1,
+                        review::Step::NextWeek => 2,
+                        review::Step::Unscheduled => 3,
+                    };
+                    let hint = match review.step {
+                        review::Step::NextWeek => "Enter: next",
+                        review::Step::Overdue => "space: reschedule  Enter: next",
+                        review::Step::Unscheduled => "space: schedule  Enter: apply",
+                    };
+                    let mut state =
+                        ratatui::widgets::ListState::default().with_selected(Some(selected));
+                    ratatui::prelude::StatefulWidget::render(
+                        ratatui::widgets::List::new(items).block(
+                            Block::bordered()
+                                .title(format!(
+                                    "{} {step}/3: {}",
+                                    self.locale.t("Weekly review"),
+                                    self.locale.t(review.step.title())
+                                ))
+                                .title_bottom(hint),
+                        ),
+                        review_area,
+                        buf,
+                        &mut state,
+                    );
+                }
+            }
+            MainArea::Plan => {
+                let plan_area = centered_popup(main_area[0]);
+                Clear.render(plan_area, buf);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.plan_blocks.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .plan_blocks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (title, (start, end)))| {
+                        let mut item = ratatui::widgets::ListItem::new(format!(
+                            " {} - {}  {}",
+                            start.format(&self.locale.localize_format(
+                                &format!(
+                                    "%a {} {}",
+                                    self.config.display.date_format,
+                                    self.config.display.time_format()
+                                ),
+                                start
+                            )),
+                            end.format(self.config.display.time_format()),
+                            bidi::display(title)
+                        ));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                ratatui::widgets::List::new(items)
+                    .block(
+                        Block::bordered()
+                            .title(self.locale.t("Plan"))
+                            .title_bottom("Enter: book all"),
+                    )
+                    .render(plan_area, buf);
+            }
+            MainArea::Audit => {
+                let audit_area = centered_popup(main_area[0]);
+                Clear.render(audit_area, buf);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.audit_entries.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .audit_entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, entry)| {
+                        let mut item = ratatui::widgets::ListItem::new(format!(
+                            " {} {:<22}{}",
+                            entry.at.format(&format!(
+                                "{} {}",
+                                self.config.display.date_format,
+                                self.config.display.time_format()
+                            )),
+                            entry.action,
+                            entry.title()
+                        ));
+                        if entry.result != "ok" {
+                            item = item.red();
+                        }
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let footer = self
+                    .audit_entries
+                    .get(selected)
+                    .map(|entry| format!("{} {}", entry.target, entry.result))
+                    .unwrap_or_default();
+                ratatui::widgets::List::new(items)
+                    .block(
+                        Block::bordered()
+                            .title(self.locale.t("Audit log"))
+                            .title_bottom(footer),
+                    )
+                    .render(audit_area, buf);
+            }
+            MainArea::Trash => {
+                let trash_area = centered_popup(main_area[0]);
+                Clear.render(trash_area, buf);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.trash_items.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .trash_items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        let kind = match item.item {
+                            trash::Trashed::Event(..) => self.locale.t("Event"),
+                            trash::Trashed::Task(..) => self.locale.t("Task"),
+                        };
+                        let mut item = ratatui::widgets::ListItem::new(format!(
+                            " {} {kind:<10}{}",
+                            item.deleted_at.format(&self.config.display.date_format),
+                            bidi::display(item.title())
+                        ));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                ratatui::widgets::List::new(items)
+                    .block(
+                        Block::bordered()
+                            .title(self.locale.t("Trash"))
+                            .title_bottom("Enter: restore"),
+                    )
+                    .render(trash_area, buf);
+            }
+            MainArea::Compare => {
+                let compare_area = Layout::new(
+                    Direction::Vertical,
+                    Constraint::from_percentages([8, 84, 8]),
+                )
+                .split(main_area[0]);
+                let compare_area = Layout::new(
+                    Direction::Horizontal,
+                    Constraint::from_percentages([5, 90, 5]),
+                )
+                .split(compare_area[1]);
+                self.render_compare(compare_area[1], buf);
+            }
+            MainArea::Invitations => {
+                let invitation_area = centered_popup(main_area[0]);
+                Clear.render(invitation_area, buf);
+
+                let invitations = self.invitations();
+                let selected = self.cursor_line.min(invitations.len().saturating_sub(1));
+                let time_format = self.config.display.time_format();
+                let items: Vec<ratatui::widgets::ListItem> = invitations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (date, ev))| {
+                        let time = ev.0.start.as_ref().and_then(|s| s.date_time).map_or(
+                            String::new(),
+                            |start| {
+                                start
+                                    .with_timezone(&self.app_tz)
+                                    .format(time_format)
+                                    .to_string()
+                            },
+                        );
+                        let from =
+                            ev.0.organizer
+                                .as_ref()
+                                .and_then(|o| o.display_name.clone().or(o.email.clone()))
+                                .map_or(String::new(), |o| format!("  ({o})"));
+                        let mut item = ratatui::widgets::ListItem::new(Line::from(vec![
+                            Span::raw(format!(
+                                " {} {time:<8} ",
+                                date.format(&self.config.display.date_format)
+                            ))
+                            .dark_gray(),
+                            Span::raw(bidi::display(&self.display_title(ev))),
+                            Span::raw(from).dark_gray(),
+                        ]));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(self.locale.t("Invitations"))
+                            .title_bottom("a: accept  t: maybe  d: decline  Enter: go to day"),
+                    ),
+                    invitation_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::Search => {
+                let search_area = centered_popup(main_area[0]);
+                Clear.render(search_area, buf);
+
+                let results = self.search_results();
+                let selected = self.cursor_line.min(results.len().saturating_sub(1));
+                let time_format = self.config.display.time_format();
+                let items: Vec<ratatui::widgets::ListItem> = results
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (date, ev))| {
+                        let time = ev.0.start.as_ref().and_then(|s| s.date_time).map_or(
+                            String::new(),
+                            |start| {
+                                start
+                                    .with_timezone(&self.app_tz)
+                                    .format(time_format)
+                                    .to_string()
+                            },
+                        );
+                        let mut item = ratatui::widgets::ListItem::new(Line::from(vec![
+                            Span::raw(format!(
+                                " {} {time:<8} ",
+                                date.format(&self.config.display.date_format)
+                            ))
+                            .dark_gray(),
+                            Span::raw(bidi::display(&self.display_title(ev))),
+                        ]));
+                        if let Some(color) = self.calendar_color(&ev.1) {
+                            item = item.fg(color);
+                        }
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(format!("{} ({})", self.locale.t("Search"), results.len()))
+                            .title_bottom("↑/↓: select  Enter: go to day"),
+                    ),
+                    search_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::Report => {
+                let report_area = centered_popup(main_area[0]);
+                Clear.render(report_area, buf);
+
+                let report = self.meeting_report();
+                let date_format = &self.config.display.date_format;
+                let mut text: Vec<Line> = report
+                    .rows(date_format)
+                    .into_iter()
+                    .map(|(label, value)| {
+                        Line::from(vec![
+                            Span::raw(format!(" {:<22}", self.locale.t(label))).bold(),
+                            Span::raw(value),
+                        ])
+                    })
+                    .collect();
+                if !report.attendees.is_empty() {
+                    text.push(Line::raw(""));
+                    text.push(
+                        Line::raw(format!(" {}", self.locale.t("Most frequent attendees"))).bold(),
+                    );
+                    for (name, count) in &report.attendees {
+                        text.push(Line::raw(format!("   {count:>3}  {}", bidi::display(name))));
+                    }
+                }
+                Paragraph::new(text)
+                    .block(
+                        Block::bordered()
+                            .title(report.title(date_format))
+                            .title_bottom("space: week/month  c: copy as Markdown"),
+                    )
+                    .render(report_area, buf);
+            }
+            MainArea::Memos => {
+                let memos_area = centered_popup(main_area[0]);
+                Clear.render(memos_area, buf);
+
+                let selected = self.cursor_line.min(self.memos.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .memos
+                    .iter()
+                    .enumerate()
+                    .map(|(i, memo)| {
+                        let mut item = ratatui::widgets::ListItem::new(Line::from(vec![
+                            Span::raw(format!(
+                                " {} ",
+                                memo.created_at.format(&self.config.display.date_format)
+                            ))
+                            .dark_gray(),
+                            Span::raw(bidi::display(&memo.text)),
+                        ]));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(self.locale.t("Memos"))
+                            .title_bottom("a: add  t: to task  e: to event  D: delete"),
+                    ),
+                    memos_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::Agenda => {
+                let agenda_area = Layout::new(
+                    Direction::Vertical,
+                    Constraint::from_percentages([8, 84, 8]),
+                )
+                .split(main_area[0]);
+                let agenda_area = Layout::new(
+                    Direction::Horizontal,
+                    Constraint::from_percentages([15, 70, 15]),
+                )
+                .split(agenda_area[1]);
+                Clear.render(agenda_area[1], buf);
+                self.render_agenda(agenda_area[1], buf);
+            }
+            MainArea::Day => {
+                let day_area = Layout::new(
+                    Direction::Horizontal,
+                    Constraint::from_percentages([5, 90, 5]),
+                )
+                .split(main_area[0]);
+                let timeline_area = self.with_mini_months(day_area[1], buf);
+                self.render_day_timeline(timeline_area, buf);
+            }
+            MainArea::Starred => {
+                let starred_area = centered_popup(main_area[0]);
+                Clear.render(starred_area, buf);
+
+                let starred = self.starred_events();
+                let selected = self.cursor_line.min(starred.len().saturating_sub(1));
+                let items: Vec<ratatui::widgets::ListItem> = starred
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (date, ev))| {
+                        let past = *date < self.today;
+                        let mut item = ratatui::widgets::ListItem::new(format!(
+                            " {} {}",
+                            date.format(&self.config.display.date_format),
+                            bidi::display(&self.display_title(ev))
+                        ));
+                        if past {
+                            item = item.dark_gray();
+                        }
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(self.locale.t("Starred"))
+                            .title_bottom("Enter: go to day  *: unstar"),
+                    ),
+                    starred_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::Conflicts => {
+                let conflict_area = centered_popup(main_area[0]);
+                Clear.render(conflict_area, buf);
+
+                if let Some(conflict) = self.conflicts.first() {
+                    let mut text = vec![Line::raw(format!(" {}", self.locale.t("Mine"))).bold()];
+                    for line in
+                        events::summary_text(&conflict.local, self.app_tz, &self.config.display)
+                            .lines()
+                    {
+                        text.push(Line::raw(format!("   {}", bidi::display(line))));
+                    }
+                    text.push(Line::raw(""));
+                    text.push(Line::raw(format!(" {}", self.locale.t("Theirs"))).bold());
+                    for line in
+                        events::summary_text(&conflict.remote, self.app_tz, &self.config.display)
+                            .lines()
+                    {
+                        text.push(Line::raw(format!("   {}", bidi::display(line))));
+                    }
+                    Paragraph::new(text)
+                        .block(
+                            Block::bordered()
+                                .title(format!(
+                                    "{} 1/{}",
+                                    self.locale.t("Conflict"),
+                                    self.conflicts.len()
+                                ))
+                                .title_bottom("Enter: keep mine  D: keep theirs"),
+                        )
+                        .render(conflict_area, buf);
+                }
+            }
+            MainArea::Birthdays => {
+                let birthday_area = centered_popup(main_area[0]);
+                Clear.render(birthday_area, buf);
+
+                let upcoming = self.upcoming_birthdays();
+                let selected = self.cursor_line.min(upcoming.len().saturating_sub(1));
+                let color = self
+                    .calendar_color(birthdays::CALENDAR)
+                    .unwrap_or(Color::Magenta);
+                let items: Vec<ratatui::widgets::ListItem> = upcoming
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (date, birthday))| {
+                        let when = match (*date - self.today).num_days() {
+                            0 => self.locale.t("today").to_string(),
+                            1 => self.locale.t("tomorrow").to_string(),
+                            days => format!("{days}d"),
+                        };
+                        let age = birthday
+                            .age_on(*date)
+                            .map_or(String::new(), |age| format!("  turns {age}"));
+                        let mut item = ratatui::widgets::ListItem::new(Line::from(vec![
+                            Span::raw(format!(
+                                " {} {when:>5}  ",
+                                date.format(&self.config.display.date_format)
+                            ))
+                            .dark_gray(),
+                            Span::raw(bidi::display(&birthday.name)).fg(color),
+                            Span::raw(age).dark_gray(),
+                        ]));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(self.locale.t("Birthdays"))
+                            .title_bottom("Enter: go to day  B: close"),
+                    ),
+                    birthday_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::Reminders => {
+                let reminders_area = centered_popup(main_area[0]);
+                Clear.render(reminders_area, buf);
+
+                let selected = self
+                    .cursor_line
+                    .min(self.local_reminders.len().saturating_sub(1));
+                let time_format = self.config.display.time_format();
+                let items: Vec<ratatui::widgets::ListItem> = self
+                    .local_reminders
+                    .iter()
+                    .enumerate()
+                    .map(|(i, reminder)| {
+                        let mut item = ratatui::widgets::ListItem::new(Line::from(vec![
+                            Span::raw(format!(" {:<8} ", reminder.time.format(time_format)))
+                                .dark_gray(),
+                            Span::raw(bidi::display(&reminder.text)),
+                            Span::raw(format!("  {}", reminder.repeat.label())).dark_gray(),
+                        ]));
+                        if i == selected {
+                            item = item.bg(self.theme.selected).fg(self.theme.selected_text);
+                        }
+                        item
+                    })
+                    .collect();
+                let mut state =
+                    ratatui::widgets::ListState::default().with_selected(Some(selected));
+                ratatui::prelude::StatefulWidget::render(
+                    ratatui::widgets::List::new(items).block(
+                        Block::bordered()
+                            .title(self.locale.t("Reminders"))
+                            .title_bottom("a: add  D: delete  Esc: back"),
+                    ),
+                    reminders_area,
+                    buf,
+                    &mut state,
+                );
+            }
+            MainArea::FreeBusy => {
+                let free_busy_area = Layout::new(
+                    Direction::Horizontal,
+                    Constraint::from_percentages([5, 90, 5]),
+                )
+                .split(main_area[0]);
+                Clear.render(free_busy_area[1], buf);
+                self.render_free_busy(free_busy_area[1], buf);
+            }
+            MainArea::Calendar if self.config.display.day_panel => {
+                self.render_day_panel(main_area[1], &series_titles, buf)
+            }
             MainArea::Calendar => {}
         }
 
+        // Status history, just above the bottom line
+        if self.show_status_history {
+            let shown = self.status_history.len().clamp(1, 8) as u16;
+            let area = main_chunks[1];
+            let height = (shown + 2).min(area.height);
+            let history_area = Rect {
+                y: area.y + area.height - height,
+                height,
+                ..area
+            };
+            Clear.render(history_area, buf);
+            let items: Vec<ratatui::widgets::ListItem> = self
+                .status_history
+                .iter()
+                .rev()
+                .take(shown as usize)
+                .rev()
+                .map(|(at, text, color)| {
+                    ratatui::widgets::ListItem::new(format!(
+                        " {} {}",
+                        at.format("%H:%M:%S"),
+                        self.locale.t(text)
+                    ))
+                    .fg(color.color(&self.theme))
+                })
+                .collect();
+            ratatui::widgets::List::new(items)
+                .block(Block::bordered().title(self.locale.t("Status history")))
+                .render(history_area, buf);
+        }
+
+        if let Some(scroll) = self.message_log {
+            self.render_message_log(main_chunks[0], scroll, buf);
+        }
+        if let Some(scroll) = self.help_scroll {
+            self.render_help(main_chunks[0], scroll, buf);
+        }
+        if let Some((tag, notes, scroll)) = &self.changelog {
+            let area = main_chunks[0].centered(Constraint::Max(72), Constraint::Percentage(80));
+            Clear.render(area, buf);
+            // Stop once the last line reaches the bottom
+            let visible = area.height.saturating_sub(2);
+            let scroll = (*scroll).min((notes.lines().count() as u16).saturating_sub(visible));
+            Paragraph::new(notes.as_str())
+                .wrap(ratatui::widgets::Wrap { trim: false })
+                .scroll((scroll, 0))
+                .block(
+                    Block::bordered()
+                        .title(format!("{} {tag}", self.locale.t("What's new in")))
+                        .title_bottom("j/k: scroll  Esc: close"),
+                )
+                .render(area, buf);
+        }
+
         // Bottom Area
 
+        let prompt = Span::raw(format!(" {}: ", self.locale.t(self.input_prompt())));
         let bottom_area = Layout::new(
             Direction::Horizontal,
             [
-                Constraint::Length(8),
+                Constraint::Length((prompt.width() as u16).max(8)),
                 Constraint::Fill(1),
                 Constraint::Fill(1),
             ],
@@ -1780,7 +8136,7 @@ impl Widget for &App {
         .split(main_chunks[2]);
 
         // Changing status
-        let status = Paragraph::new(self.changing_status.clone().0)
+        let status = Paragraph::new(self.locale.t(&self.changing_status.0))
             .alignment(ratatui::layout::Alignment::Right)
             .style(Modifier::BOLD);
         let status_area = bottom_area[2].inner(ratatui::layout::Margin {
@@ -1788,52 +8144,172 @@ impl Widget for &App {
             horizontal: 1,
         });
 
-        match self.changing_status.clone().1 {
-            StatusColor::Green => status.green().render(status_area, buf),
-            StatusColor::Yellow => status.yellow().render(status_area, buf),
-            StatusColor::Red => status.red().render(status_area, buf),
-            _ => status.render(status_area, buf),
+        match self.changing_status.1 {
+            StatusColor::White => status.render(status_area, buf),
+            ref color => status.fg(color.color(&self.theme)).render(status_area, buf),
         }
 
         // Text input area
 
         if self.inputting {
-            if let MainArea::Tasks(_) = self.app_layout {
-                Paragraph::new(" Tasks: ").render(bottom_area[0], buf)
-            } else {
-                Paragraph::new(" Event: ").render(bottom_area[0], buf)
-            }
+            Paragraph::new(prompt).render(bottom_area[0], buf);
 
-            let char_at_cursor = if let Some(ch) = self.input_buffer.chars().nth(self.cursor_index)
-            {
-                Span::raw(ch.to_string()).on_white().black()
+            // Draw in visual order, highlighting wherever the logical cursor lands
+            let masked = matches!(self.input_target, InputTarget::Passphrase);
+            let chars: Vec<char> = if masked {
+                self.input_buffer.chars().map(|_| '•').collect()
             } else {
-                Span::raw("█".to_string())
+                self.input_buffer.chars().collect()
             };
-            let left: String = self.input_buffer.chars().take(self.cursor_index).collect();
-            let right: String = self
-                .input_buffer
-                .chars()
-                .skip(self.cursor_index + 1)
+            let mut spans: Vec<Span> = bidi::visual_order(&self.input_buffer)
+                .into_iter()
+                .map(|i| {
+                    if i == self.cursor_index {
+                        Span::raw(chars[i].to_string()).on_white().black()
+                    } else {
+                        Span::raw(chars[i].to_string())
+                    }
+                })
                 .collect();
+            if self.cursor_index >= chars.len() {
+                if bidi::is_rtl_text(&self.input_buffer) {
+                    spans.insert(0, Span::raw("█"));
+                } else {
+                    spans.push(Span::raw("█"));
+                }
+            }
+            ratatui::text::Line::from(spans).render(bottom_area[1], buf)
+        }
 
-            let input_left = Span::raw(left);
-            let input_right = Span::raw(right);
-            ratatui::text::Line::from(vec![input_left, char_at_cursor, input_right])
-                .render(bottom_area[1], buf)
+        if let Some(alarm) = self.alarms.front() {
+            self.render_alarm(alarm, area, buf);
         }
     }
 }
 
+// The middle of `area`, where the list popups open over the calendar
+fn centered_popup(area: Rect) -> Rect {
+    let rows = Layout::new(
+        Direction::Vertical,
+        Constraint::from_percentages([16, 68, 16]),
+    )
+    .split(area);
+    Layout::new(
+        Direction::Horizontal,
+        Constraint::from_percentages([20, 60, 20]),
+    )
+    .split(rows[1])[1]
+}
+
+// List offset that puts `selected` mid-way down `height` rows, as far as the list allows
+fn centered_offset(selected: usize, len: usize, height: u16) -> usize {
+    let height = height as usize;
+    selected
+        .saturating_sub(height / 2)
+        .min(len.saturating_sub(height))
+}
+
+// What to read out as the input changes: the typed character, the finished word on a space,
+// or the whole buffer after deletions, pastes and edits before the end
+fn input_echo(before: &str, after: &str) -> String {
+    match after.strip_prefix(before) {
+        Some(" ") => after
+            .split_whitespace()
+            .last()
+            .unwrap_or("space")
+            .to_string(),
+        Some(typed) if typed.chars().count() == 1 => typed.to_string(),
+        _ => after.to_string(),
+    }
+}
+
+fn batch_summary(verb: &str, done: usize, total: usize, what: &str) -> (String, StatusColor) {
+    let color = if done == total {
+        StatusColor::Green
+    } else {
+        StatusColor::Red
+    };
+    (format!("{verb} {done}/{total} {what}"), color)
+}
+
+// Leaves the terminal usable however the app ends: a normal exit, a panic or a signal
+// Every way out goes through here, so the demo directory goes too
+fn restore_terminal() {
+    demo::cleanup();
+    let _ = crossterm::execute!(
+        io::stdout(),
+        crossterm::event::DisableBracketedPaste,
+        crossterm::event::DisableMouseCapture
+    );
+    let _ = crossterm::terminal::disable_raw_mode();
+    ratatui::restore();
+}
+
+fn install_restore_handlers() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        tracing::error!("panic: {info}");
+        hook(info);
+    }));
+    #[cfg(unix)]
+    tokio::spawn(async {
+        use tokio::signal::unix::{SignalKind, signal};
+        let (Ok(mut term), Ok(mut hup), Ok(mut int)) = (
+            signal(SignalKind::terminate()),
+            signal(SignalKind::hangup()),
+            signal(SignalKind::interrupt()),
+        ) else {
+            return;
+        };
+        // Shell convention: 128 + the signal number
+        let code = tokio::select! {
+            _ = term.recv() => 143,
+            _ = hup.recv() => 129,
+            _ = int.recv() => 130,
+        };
+        tracing::info!(code, "exiting on signal");
+        restore_terminal();
+        std::process::exit(code);
+    });
+    suspend::listen();
+}
+
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
-    rustls::crypto::aws_lc_rs::default_provider()
-        .install_default()
-        .expect("Failed to install awc_ls_rs crypto provider");
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("widget") => return widget::run(&args[2..]),
+        Some("update") => return update::run(&args[2..]).await,
+        Some("report") => return report::run(&args[2..]),
+        Some("mcp") => return mcp::run().await,
+        Some("--version" | "-V") => {
+            println!("calpersonal {}", update::VERSION);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let mut builder = App::builder();
+    if let Some(id) = deep_link::from_args(&args) {
+        builder = builder.focus_event(id);
+    }
+    let mut calendar_init = builder.build().await;
+    if calendar_init.config.display.screen_reader {
+        crossterm::terminal::enable_raw_mode()?;
+        install_restore_handlers();
+        let res = calendar_init.run_linear().await;
+        demo::cleanup();
+        crossterm::terminal::disable_raw_mode()?;
+        return res;
+    }
 
     let mut terminal = ratatui::init();
-    let mut calendar_init = App::new().await;
-    let res = calendar_init.run(&mut terminal);
-    ratatui::restore();
+    install_restore_handlers();
+    crossterm::execute!(io::stdout(), crossterm::event::EnableBracketedPaste)?;
+    let res = calendar_init.run(&mut terminal).await;
+    restore_terminal();
     res
 }
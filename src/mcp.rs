@@ -0,0 +1,607 @@
+use crate::config::{self, Access, Config};
+use crate::{
+    audit, calendar_auth, error, events, file_writing, net, scheduling, secrets, tasks_auth, update,
+};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use google_calendar3::{CalendarHub, api};
+use google_tasks1::TasksHub;
+use google_tasks1::api::Task;
+use serde_json::{Value, json};
+use std::io;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+// `calpersonal mcp`: the agenda, free slots, new events and finishing tasks as Model Context
+// Protocol tools, one JSON-RPC message per line on stdin and stdout, so an assistant can
+// schedule through the same sign-in, caches and [auth] access as the TUI.
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+const DEFAULT_DAYS: u64 = 7;
+const MAX_DAYS: u64 = 31;
+
+fn tools() -> Value {
+    json!([
+        {
+            "name": "list_agenda",
+            "description": "Events on every calendar calpersonal shows, as of its last sync, plus open tasks due by the end of the range",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "date": { "type": "string", "description": "First day, YYYY-MM-DD; today when left out" },
+                    "days": { "type": "integer", "minimum": 1, "maximum": MAX_DAYS, "description": "How many days, 7 when left out" }
+                }
+            }
+        },
+        {
+            "name": "find_free_slot",
+            "description": "Weekday slots in working hours (9:00-18:00) where the primary calendar and any attendees are free, leaving protected time alone. Calendars that couldn't be checked are listed under `unchecked`",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "duration_minutes": { "type": "integer", "minimum": 5 },
+                    "days": { "type": "integer", "minimum": 1, "maximum": MAX_DAYS, "description": "How far ahead to look, 7 when left out" },
+                    "attendees": { "type": "array", "items": { "type": "string" }, "description": "Email addresses whose calendars must be free too" }
+                },
+                "required": ["duration_minutes"]
+            }
+        },
+        {
+            "name": "create_event",
+            "description": "Adds an event. Times are RFC 3339 or YYYY-MM-DDTHH:MM in local time; a plain YYYY-MM-DD makes an all-day event, with end as its last day",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "start": { "type": "string" },
+                    "end": { "type": "string", "description": "Required for timed events" },
+                    "calendar": { "type": "string", "description": "Calendar id, primary when left out" },
+                    "location": { "type": "string" },
+                    "description": { "type": "string" }
+                },
+                "required": ["title", "start"]
+            }
+        },
+        {
+            "name": "complete_task",
+            "description": "Marks an open task completed",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task": { "type": "string", "description": "The task's id, or its title" }
+                },
+                "required": ["task"]
+            }
+        }
+    ])
+}
+
+// A whole day, or a moment: RFC 3339, else local time without an offset
+enum When {
+    Day(NaiveDate),
+    Time(DateTime<Utc>),
+}
+
+fn parse_when(value: &str) -> Option<When> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(When::Day(date));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(When::Time(time.to_utc()));
+    }
+    ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .and_then(|time| time.and_local_timezone(Local).latest())
+        .map(|time| When::Time(time.to_utc()))
+}
+
+fn event_time(time: &Option<api::EventDateTime>) -> Option<String> {
+    let time = time.as_ref()?;
+    time.date_time
+        .map(|dt| dt.with_timezone(&Local).to_rfc3339())
+        .or_else(|| time.date.map(|d| d.to_string()))
+}
+
+fn days_arg(args: &Value) -> Result<u64, String> {
+    match args.get("days") {
+        None | Some(Value::Null) => Ok(DEFAULT_DAYS),
+        Some(days) => days
+            .as_u64()
+            .filter(|days| (1..=MAX_DAYS).contains(days))
+            .ok_or(format!("days must be between 1 and {MAX_DAYS}")),
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, name: &str) -> Option<&'a str> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn list_agenda(args: &Value) -> Result<Value, String> {
+    let from = match str_arg(args, "date") {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Expected date as YYYY-MM-DD, got {date:?}"))?,
+        None => Local::now().date_naive(),
+    };
+    let to = from + Duration::days(days_arg(args)? as i64);
+    let range_start = from.and_hms_opt(0, 0, 0).unwrap();
+    let range_end = to.and_hms_opt(0, 0, 0).unwrap();
+
+    let calendars = file_writing::load_calendars_cache();
+    let calendar_name = |id: &str| {
+        calendars
+            .get(id)
+            .and_then(|c| c.summary_override.clone().or(c.summary.clone()))
+            .unwrap_or(id.to_string())
+    };
+    let cache = file_writing::load_events_cache();
+    let mut seen = std::collections::HashSet::new();
+    let mut events: Vec<(String, Value)> = cache
+        .values()
+        .flatten()
+        .filter(|(ev, _)| ev.status.as_deref() != Some("cancelled"))
+        .filter(|(ev, calendar)| seen.insert((ev.id.clone(), calendar.clone())))
+        .filter(|(ev, _)| {
+            let (Some(start), Some(end)) = (ev.start.as_ref(), ev.end.as_ref()) else {
+                return false;
+            };
+            match (start.date_time, end.date_time, start.date, end.date) {
+                (Some(start), Some(end), _, _) => {
+                    start.with_timezone(&Local).naive_local() < range_end
+                        && end.with_timezone(&Local).naive_local() > range_start
+                }
+                (_, _, Some(start), Some(end)) => start < to && end > from,
+                _ => false,
+            }
+        })
+        .map(|(ev, calendar)| {
+            let start = event_time(&ev.start).unwrap_or_default();
+            let item = json!({
+                "id": ev.id,
+                "title": ev.summary,
+                "start": start,
+                "end": event_time(&ev.end),
+                "all_day": ev.start.as_ref().is_some_and(|s| s.date_time.is_none()),
+                "calendar": calendar_name(calendar),
+                "location": ev.location,
+                "response": events::response_status(ev),
+            });
+            (start, item)
+        })
+        .collect();
+    events.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let last_day = (to - Duration::days(1)).to_string();
+    let tasks: Vec<Value> = file_writing::load_tasks_cache()
+        .iter()
+        .filter(|(task, _)| task.status.as_deref() != Some("completed"))
+        .filter(|(task, _)| {
+            task.due
+                .as_deref()
+                .and_then(|d| d.get(..10))
+                .is_some_and(|d| d <= last_day.as_str())
+        })
+        .map(|(task, list)| {
+            json!({
+                "id": task.id,
+                "title": task.title,
+                "due": task.due.as_deref().and_then(|d| d.get(..10)),
+                "list": list,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "from": from.to_string(),
+        "to": last_day,
+        "events": events.into_iter().map(|(_, item)| item).collect::<Vec<_>>(),
+        "tasks": tasks,
+    }))
+}
+
+// The task named by id, else by title: exactly, then as a part of it
+fn find_task<'a>(tasks: &'a [(Task, String)], wanted: &str) -> Result<&'a (Task, String), String> {
+    if let Some(task) = tasks.iter().find(|(t, _)| t.id.as_deref() == Some(wanted)) {
+        return Ok(task);
+    }
+    let needle = wanted.to_lowercase();
+    let open: Vec<_> = tasks
+        .iter()
+        .filter(|(t, _)| t.status.as_deref() != Some("completed"))
+        .collect();
+    let title = |t: &Task| t.title.as_deref().unwrap_or_default().to_lowercase();
+    let exact: Vec<_> = open.iter().filter(|(t, _)| title(t) == needle).collect();
+    let matches = if exact.is_empty() {
+        open.iter()
+            .filter(|(t, _)| title(t).contains(&needle))
+            .collect()
+    } else {
+        exact
+    };
+    match matches.as_slice() {
+        [task] => Ok(task),
+        [] => Err(format!("No open task matches {wanted:?}")),
+        several => Err(format!(
+            "{wanted:?} matches {} tasks ({}); pass the id instead",
+            several.len(),
+            several
+                .iter()
+                .filter_map(|(t, _)| t.title.as_deref())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+struct Server {
+    config: Config,
+    // Why the config didn't load; tools answer with it until it's fixed and the server restarted
+    config_error: Option<String>,
+    client_secret: Option<String>,
+    calendar_hub: Option<CalendarHub<net::Connector>>,
+    tasks_hub: Option<TasksHub<net::Connector>>,
+}
+
+impl Server {
+    fn refuse_writes(&self) -> Result<(), String> {
+        match calendar_auth::access() {
+            Access::ReadOnly => {
+                Err("calpersonal is set to read-only access ([auth] access)".to_string())
+            }
+            Access::Full | Access::Events => Ok(()),
+        }
+    }
+
+    // Feeds, birthdays and [calendars] read_only are off limits here as in the TUI
+    fn refuse_calendar(&self, calendar: &str) -> Result<(), String> {
+        let calendars = file_writing::load_calendars_cache();
+        let known = calendars.iter().find(|(_, c)| {
+            c.id.as_deref() == Some(calendar) || (calendar == "primary" && c.primary == Some(true))
+        });
+        let (id, name) = match known {
+            Some((id, c)) => (id.as_str(), c.summary.as_deref()),
+            None => (calendar, None),
+        };
+        match events::read_only_reason(&self.config, id, name) {
+            Some(reason) => Err(reason.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    // Signs in on first use; the browser flow would write to stdout, so it is left to the TUI
+    async fn calendar_hub(&mut self) -> Result<CalendarHub<net::Connector>, String> {
+        if let Some(hub) = &self.calendar_hub {
+            return Ok(hub.clone());
+        }
+        if !calendar_auth::signed_in(&self.config.auth) {
+            return Err("Not signed in to Google Calendar; run calpersonal once first".to_string());
+        }
+        let hub = calendar_auth::get_calendar_hub(
+            self.config.network.clone(),
+            self.client_secret.clone(),
+            self.config.auth.clone(),
+            self.config.birthdays.contacts,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        self.calendar_hub = Some(hub.clone());
+        Ok(hub)
+    }
+
+    async fn tasks_hub(&mut self) -> Result<TasksHub<net::Connector>, String> {
+        if let Some(hub) = &self.tasks_hub {
+            return Ok(hub.clone());
+        }
+        if !tasks_auth::signed_in(&self.config.auth) {
+            return Err("Not signed in to Google Tasks; run calpersonal once first".to_string());
+        }
+        let hub = tasks_auth::get_tasks_hub(
+            self.config.network.clone(),
+            self.client_secret.clone(),
+            self.config.auth.clone(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        self.tasks_hub = Some(hub.clone());
+        Ok(hub)
+    }
+
+    async fn find_free_slot(&mut self, args: &Value) -> Result<Value, String> {
+        let minutes = args
+            .get("duration_minutes")
+            .and_then(Value::as_i64)
+            .filter(|m| *m > 0)
+            .ok_or("duration_minutes is required")?;
+        let days = days_arg(args)?;
+        let attendees: Vec<String> = args
+            .get("attendees")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(Value::as_str)
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let hub = self.calendar_hub().await?;
+
+        let now = Local::now().fixed_offset();
+        let protected = scheduling::protected_busy(
+            &self.config.protected,
+            now.date_naive(),
+            days,
+            *now.offset(),
+        );
+        let items = attendees
+            .iter()
+            .map(String::as_str)
+            .chain(["primary"])
+            .map(|id| api::FreeBusyRequestItem {
+                id: Some(id.to_string()),
+            })
+            .collect();
+        let request = api::FreeBusyRequest {
+            items: Some(items),
+            time_min: Some(now.to_utc()),
+            time_max: Some((now + Duration::days(days as i64)).to_utc()),
+            ..Default::default()
+        };
+        let (_, response) = hub
+            .freebusy()
+            .query(request)
+            .add_scope(calendar_auth::read_scope())
+            .delegate(&mut net::Middleware::default())
+            .doit()
+            .await
+            .map_err(|e| error::Error::from(e).to_string())?;
+        // Calendars Google couldn't answer for are listed, not taken as free
+        let (mut busy, unavailable) = scheduling::busy_periods(response);
+        busy.extend(protected);
+        let slots: Vec<Value> =
+            scheduling::free_slots(&busy, now, days, Duration::minutes(minutes))
+                .into_iter()
+                .map(|(start, end)| json!({ "start": start.to_rfc3339(), "end": end.to_rfc3339() }))
+                .collect();
+        Ok(json!({ "slots": slots, "unchecked": unavailable }))
+    }
+
+    async fn create_event(&mut self, args: &Value) -> Result<Value, String> {
+        self.refuse_writes()?;
+        let title = str_arg(args, "title").ok_or("title is required")?;
+        let start = str_arg(args, "start").ok_or("start is required")?;
+        let calendar = str_arg(args, "calendar").unwrap_or("primary").to_string();
+        self.refuse_calendar(&calendar)?;
+        let (start, end) = match (parse_when(start), str_arg(args, "end").map(parse_when)) {
+            (Some(When::Day(start)), None) => (start, start),
+            (Some(When::Day(start)), Some(Some(When::Day(end)))) if end >= start => (start, end),
+            (Some(When::Time(start)), Some(Some(When::Time(end)))) if end > start => {
+                let at = |time| api::EventDateTime {
+                    date_time: Some(time),
+                    ..Default::default()
+                };
+                return self
+                    .insert_event(title, at(start), at(end), calendar, args)
+                    .await;
+            }
+            (Some(When::Time(_)), None) => {
+                return Err("end is required for a timed event".to_string());
+            }
+            (None, _) | (_, Some(None)) => {
+                return Err("Times are YYYY-MM-DD, YYYY-MM-DDTHH:MM or RFC 3339".to_string());
+            }
+            _ => return Err("end must come after start, and be the same kind".to_string()),
+        };
+        // Google's all-day end is the day after
+        let on = |date| api::EventDateTime {
+            date: Some(date),
+            ..Default::default()
+        };
+        self.insert_event(
+            title,
+            on(start),
+            on(end + Duration::days(1)),
+            calendar,
+            args,
+        )
+        .await
+    }
+
+    async fn insert_event(
+        &mut self,
+        title: &str,
+        start: api::EventDateTime,
+        end: api::EventDateTime,
+        calendar: String,
+        args: &Value,
+    ) -> Result<Value, String> {
+        let hub = self.calendar_hub().await?;
+        let event = api::Event {
+            summary: Some(title.to_string()),
+            location: str_arg(args, "location").map(String::from),
+            description: str_arg(args, "description").map(String::from),
+            start: Some(start),
+            end: Some(end),
+            ..Default::default()
+        };
+        let sent = audit::payload(&event);
+        let result = hub
+            .events()
+            .insert(event, &calendar)
+            .add_scope(calendar_auth::write_scope())
+            .delegate(&mut net::Middleware::default())
+            .doit()
+            .await;
+        audit::record(
+            "create_event",
+            &calendar,
+            None,
+            result
+                .as_ref()
+                .ok()
+                .and_then(|(_, e)| audit::payload(e))
+                .or(sent),
+            &result.as_ref().map(|_| ()),
+        );
+        let (_, event) = result.map_err(|e| error::Error::from(e).to_string())?;
+        Ok(json!({
+            "id": event.id,
+            "title": event.summary,
+            "start": event_time(&event.start),
+            "end": event_time(&event.end),
+            "link": event.html_link,
+        }))
+    }
+
+    async fn complete_task(&mut self, args: &Value) -> Result<Value, String> {
+        self.refuse_writes()?;
+        let wanted = str_arg(args, "task").ok_or("task is required")?;
+        let mut tasks = file_writing::load_tasks_cache();
+        let (task, list) = find_task(&tasks, wanted)?.clone();
+        let Some(task_id) = task.id.clone() else {
+            return Err("That task has no id yet".to_string());
+        };
+        let hub = self.tasks_hub().await?;
+        let completed = Task {
+            status: Some("completed".to_string()),
+            ..Default::default()
+        };
+        let after = audit::payload(&completed);
+        let result = hub
+            .tasks()
+            .patch(completed, &list, &task_id)
+            .add_scope(tasks_auth::write_scope())
+            .delegate(&mut net::Middleware::default())
+            .doit()
+            .await;
+        audit::record(
+            "complete_task",
+            &format!("{list}/{task_id}"),
+            audit::payload(&task),
+            after,
+            &result.as_ref().map(|_| ()),
+        );
+        result.map_err(|e| error::Error::from(e).to_string())?;
+        // So the widget and the next list_agenda agree before the TUI syncs again
+        for (cached, _) in tasks.iter_mut() {
+            if cached.id.as_deref() == Some(task_id.as_str()) {
+                cached.status = Some("completed".to_string());
+            }
+        }
+        file_writing::save_tasks_cache(&tasks);
+        Ok(json!({ "id": task_id, "title": task.title, "completed": true }))
+    }
+
+    async fn call(&mut self, name: &str, args: &Value) -> Result<Value, String> {
+        match name {
+            "list_agenda" => list_agenda(args),
+            "find_free_slot" => self.find_free_slot(args).await,
+            "create_event" => self.create_event(args).await,
+            "complete_task" => self.complete_task(args).await,
+            _ => Err(format!("Unknown tool {name}")),
+        }
+    }
+
+    // The response to one request, None for notifications
+    async fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned()?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let result = match message.get("method").and_then(Value::as_str) {
+            Some("initialize") => json!({
+                "protocolVersion": params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(PROTOCOL_VERSION),
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "calpersonal", "version": update::VERSION },
+            }),
+            Some("ping") => json!({}),
+            Some("tools/list" | "tools/call") if self.config_error.is_some() => {
+                return Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32603, "message": self.config_error },
+                }));
+            }
+            Some("tools/list") => json!({ "tools": tools() }),
+            Some("tools/call") => {
+                let name = params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let args = params.get("arguments").cloned().unwrap_or(json!({}));
+                tracing::info!(tool = name, "mcp call");
+                // Tool failures go back to the model as text it can act on
+                match self.call(name, &args).await {
+                    Ok(value) => json!({
+                        "content": [{ "type": "text", "text": value.to_string() }],
+                        "structuredContent": value,
+                        "isError": false,
+                    }),
+                    Err(e) => json!({
+                        "content": [{ "type": "text", "text": e }],
+                        "isError": true,
+                    }),
+                }
+            }
+            method => {
+                return Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32601, "message": format!("Unknown method {}", method.unwrap_or_default()) },
+                }));
+            }
+        };
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+}
+
+pub async fn run() -> io::Result<()> {
+    let (config, config_error) = match config::try_parse_config() {
+        Ok(config) => (config, None),
+        Err(e) => (Config::default(), Some(e)),
+    };
+    net::configure(&config.network);
+    // The log file only: stdout carries the protocol
+    crate::logging::init(
+        config.logging.level.parse().unwrap_or(tracing::Level::INFO),
+        config.network.log_requests,
+    );
+    if let Some(e) = &config_error {
+        tracing::error!("{e}");
+    }
+    calendar_auth::set_access(config.auth.access);
+    let client_secret = config.secrets.client_secret.as_ref().and_then(|value| {
+        secrets::resolve(value, &config.secrets.decrypt_command)
+            .map_err(|e| tracing::error!("client secret: {e}"))
+            .ok()
+    });
+    let mut server = Server {
+        config,
+        config_error,
+        client_secret,
+        calendar_hub: None,
+        tasks_hub: None,
+    };
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(message).await,
+            Err(e) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": format!("Parse error: {e}") },
+            })),
+        };
+        if let Some(response) = response {
+            stdout.write_all(format!("{response}\n").as_bytes()).await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::platform;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+// Scratch notes that stay on this machine until turned into a task or event
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Memo {
+    pub created_at: DateTime<Local>,
+    pub text: String,
+}
+
+fn path() -> PathBuf {
+    platform::data_dir().join("memos.json")
+}
+
+// Newest first
+pub fn load() -> Vec<Memo> {
+    match read_to_string(path()) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn save(memos: &[Memo]) {
+    let path = path();
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(memos) {
+        let _ = write(path, json);
+    }
+}
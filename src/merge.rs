@@ -0,0 +1,140 @@
+use crate::reminders::event_start;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use google_calendar3::api;
+use std::collections::HashMap;
+
+pub type EventsCache = HashMap<NaiveDate, Vec<(api::Event, String)>>;
+
+#[derive(Clone)]
+pub enum LocalChange {
+    Create(api::Event),
+    Update(api::Event), // full local copy with the edit applied
+    Delete(String),     // event id
+}
+
+// A change shown in the cache before the API has acknowledged it
+#[derive(Clone)]
+pub struct PendingEdit {
+    pub id: u64,
+    pub calendar_id: String,
+    pub change: LocalChange,
+    pub base_updated: Option<DateTime<Utc>>, // server `updated` the edit was made against
+}
+
+pub struct Conflict {
+    pub edit_id: u64,
+    pub calendar_id: String,
+    pub local: api::Event,
+    pub remote: api::Event,
+}
+
+fn same_content(a: &api::Event, b: &api::Event) -> bool {
+    a.summary == b.summary
+        && a.start.as_ref().map(|s| (s.date, s.date_time))
+            == b.start.as_ref().map(|s| (s.date, s.date_time))
+        && a.end.as_ref().map(|s| (s.date, s.date_time))
+            == b.end.as_ref().map(|s| (s.date, s.date_time))
+}
+
+// What the event will look like once a sparse patch is applied
+pub fn patched(base: &api::Event, patch: &api::Event) -> api::Event {
+    let mut event = base.clone();
+    if patch.summary.is_some() {
+        event.summary = patch.summary.clone();
+    }
+    if patch.start.is_some() {
+        event.start = patch.start.clone();
+        event.end = patch.end.clone();
+    }
+    event
+}
+
+fn find<'a>(cache: &'a EventsCache, event_id: &str) -> Option<&'a api::Event> {
+    cache
+        .values()
+        .flatten()
+        .map(|(event, _)| event)
+        .find(|event| event.id.as_deref() == Some(event_id))
+}
+
+fn remove(cache: &mut EventsCache, event_id: &str) {
+    for events in cache.values_mut() {
+        events.retain(|(event, _)| event.id.as_deref() != Some(event_id));
+    }
+}
+
+pub fn insert(
+    cache: &mut EventsCache,
+    event: api::Event,
+    calendar_id: String,
+    app_tz: FixedOffset,
+) {
+    if let Some(start) = event_start(&event, app_tz) {
+        cache
+            .entry(start.date_naive())
+            .or_default()
+            .push((event, calendar_id));
+    }
+}
+
+// Shows a pending edit in the cache the same way the server will once it lands
+pub fn apply(cache: &mut EventsCache, edit: &PendingEdit, app_tz: FixedOffset) {
+    match &edit.change {
+        LocalChange::Create(event) => {
+            insert(cache, event.clone(), edit.calendar_id.clone(), app_tz);
+        }
+        LocalChange::Update(event) => {
+            if let Some(id) = &event.id {
+                remove(cache, id);
+            }
+            insert(cache, event.clone(), edit.calendar_id.clone(), app_tz);
+        }
+        LocalChange::Delete(id) => remove(cache, id),
+    }
+}
+
+// Replays unacknowledged edits over freshly fetched events. An update whose event
+// changed on the server since the edit was made becomes a conflict instead.
+pub fn merge(
+    mut fetched: EventsCache,
+    pending: &[PendingEdit],
+    app_tz: FixedOffset,
+) -> (EventsCache, Vec<Conflict>) {
+    let mut conflicts = Vec::new();
+
+    for edit in pending {
+        match &edit.change {
+            LocalChange::Create(event) => {
+                let landed = fetched
+                    .values()
+                    .flatten()
+                    .any(|(remote, _)| same_content(remote, event));
+                if !landed {
+                    apply(&mut fetched, edit, app_tz);
+                }
+            }
+            LocalChange::Update(event) => {
+                let Some(id) = &event.id else {
+                    continue;
+                };
+                match find(&fetched, id) {
+                    // Already landed, or nobody else touched it
+                    Some(remote) if same_content(remote, event) => {}
+                    Some(remote) if remote.updated > edit.base_updated => {
+                        conflicts.push(Conflict {
+                            edit_id: edit.id,
+                            calendar_id: edit.calendar_id.clone(),
+                            local: event.clone(),
+                            remote: remote.clone(),
+                        });
+                    }
+                    Some(_) => apply(&mut fetched, edit, app_tz),
+                    // Deleted remotely; keeping the local copy would resurrect it
+                    None => {}
+                }
+            }
+            LocalChange::Delete(_) => apply(&mut fetched, edit, app_tz),
+        }
+    }
+    (fetched, conflicts)
+}
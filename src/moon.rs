@@ -0,0 +1,17 @@
+use chrono::NaiveDate;
+
+const SYNODIC_MONTH: f64 = 29.530588853;
+
+// Age in days since the last new moon, measured from the new moon of 2000-01-06 18:14 UTC
+fn age(date: NaiveDate) -> f64 {
+    let reference = NaiveDate::from_ymd_opt(2000, 1, 6).unwrap();
+    let days = (date - reference).num_days() as f64 + 0.5 - (18.0 + 14.0 / 60.0) / 24.0;
+    days.rem_euclid(SYNODIC_MONTH)
+}
+
+// One of the eight phase glyphs for the moon at noon on `date`
+pub fn phase_glyph(date: NaiveDate) -> &'static str {
+    const GLYPHS: [&str; 8] = ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"];
+    let index = (age(date) / SYNODIC_MONTH * 8.0).round() as usize % 8;
+    GLYPHS[index]
+}
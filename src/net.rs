@@ -0,0 +1,349 @@
+use crate::config::NetworkConfig;
+use crate::usage;
+use google_calendar3::common::{Delegate, MethodInfo, Response, Retry};
+use google_calendar3::hyper::rt::{Read, ReadBufCursor, Write};
+use google_calendar3::hyper::{Method, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::Client;
+use hyper_util::client::legacy::connect::{Connected, Connection, HttpConnector, proxy::Tunnel};
+use hyper_util::client::proxy::matcher::Matcher;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use rustls::pki_types::{CertificateDer, pem::PemObject};
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::time::Sleep;
+use tower_service::Service;
+
+pub type Connector = HttpsConnector<ProxyConnector>;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+const MAX_RETRIES: u32 = 3;
+
+static TIMEOUT_SECS: AtomicU64 = AtomicU64::new(15);
+// For the clients made away from the config: feeds, weather, webhooks and the like
+static NETWORK: OnceLock<NetworkConfig> = OnceLock::new();
+
+// [network], set once at startup
+pub fn configure(network: &NetworkConfig) {
+    TIMEOUT_SECS.store(network.timeout_secs.max(1), Ordering::Relaxed);
+    let _ = NETWORK.set(network.clone());
+}
+
+pub fn timeout() -> Duration {
+    Duration::from_secs(TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+fn timed_out(limit: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("Timed out after {}s", limit.as_secs()),
+    )
+}
+
+// POSTs that only read, so sending them twice is harmless
+const READ_ONLY_POSTS: [&str; 1] = ["calendar.freebusy.query"];
+
+// Hooked into every hub call with `.delegate(&mut net::Middleware::default())`, since the
+// generated hubs only take a retry policy per call: counts the request, logs its timing and
+// retries rate limits, plus server errors and dropped connections when a repeat is harmless.
+// Connections that never opened are retried below, in the connector.
+#[derive(Default)]
+pub struct Middleware {
+    method: &'static str,
+    idempotent: bool,
+    started: Option<Instant>,
+    retries: u32,
+}
+
+impl Middleware {
+    fn retry(&mut self, reason: &str) -> Retry {
+        if self.retries >= MAX_RETRIES {
+            return Retry::Abort;
+        }
+        self.retries += 1;
+        let wait = Duration::from_secs(1 << (self.retries - 1));
+        tracing::debug!(
+            "{} retry {} in {:?}: {reason}",
+            self.method,
+            self.retries,
+            wait
+        );
+        usage::record_call();
+        Retry::After(wait)
+    }
+}
+
+impl Delegate for Middleware {
+    fn begin(&mut self, info: MethodInfo) {
+        self.method = info.id;
+        // GET, or PATCH/PUT/DELETE by id; an insert that reached the server must not run twice
+        self.idempotent = info.http_method != Method::POST || READ_ONLY_POSTS.contains(&info.id);
+        self.started = Some(Instant::now());
+        usage::record_call();
+    }
+
+    fn http_error(&mut self, err: &hyper_util::client::legacy::Error) -> Retry {
+        // The connector already retried connecting; anything later may have been sent
+        if self.idempotent && !err.is_connect() {
+            self.retry(&err.to_string())
+        } else {
+            Retry::Abort
+        }
+    }
+
+    fn http_failure(&mut self, response: &Response, err: Option<&serde_json::Value>) -> Retry {
+        let status = response.status();
+        let rate_limited = err
+            .and_then(|e| e.pointer("/error/errors/0/reason"))
+            .and_then(|r| r.as_str())
+            .is_some_and(|r| r.ends_with("RateLimitExceeded") || r == "rateLimitExceeded");
+        // A 5xx can come after the write was committed, so only rate limits retry inserts
+        if status.as_u16() == 429 || rate_limited || (status.is_server_error() && self.idempotent) {
+            self.retry(status.as_str())
+        } else {
+            Retry::Abort
+        }
+    }
+
+    fn finished(&mut self, is_success: bool) {
+        let elapsed = self.started.map(|s| s.elapsed()).unwrap_or_default();
+        tracing::debug!(
+            "{} {} in {:?}{}",
+            self.method,
+            if is_success { "ok" } else { "failed" },
+            elapsed,
+            match self.retries {
+                0 => String::new(),
+                n => format!(" after {n} retries"),
+            }
+        );
+    }
+}
+
+// Plain TCP, or a CONNECT tunnel through the proxy for hosts the matcher intercepts
+#[derive(Clone)]
+pub struct ProxyConnector {
+    http: HttpConnector,
+    matcher: Arc<Matcher>,
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = TimedIo;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    // A connection that never opened sent nothing, so retrying it is safe for every method
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let mut connector = self.clone();
+        Box::pin(async move {
+            let limit = timeout();
+            let mut attempt = 0;
+            loop {
+                let connected = tokio::time::timeout(limit, connector.connect(dst.clone()))
+                    .await
+                    .unwrap_or_else(|_| Err(timed_out(limit).into()));
+                match connected {
+                    Ok(io) => return Ok(TimedIo::new(io, limit)),
+                    Err(e) if attempt < MAX_RETRIES => {
+                        attempt += 1;
+                        let wait = Duration::from_secs(1 << (attempt - 1));
+                        tracing::debug!("connect to {dst} retry {attempt} in {wait:?}: {e}");
+                        tokio::time::sleep(wait).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        })
+    }
+}
+
+impl ProxyConnector {
+    async fn connect(&mut self, dst: Uri) -> Result<TokioIo<TcpStream>, BoxError> {
+        match self.matcher.intercept(&dst) {
+            Some(intercept) => {
+                let mut tunnel = Tunnel::new(intercept.uri().clone(), self.http.clone());
+                if let Some(auth) = intercept.basic_auth() {
+                    tunnel = tunnel.with_auth(auth.clone());
+                }
+                tunnel.call(dst).await.map_err(Into::into)
+            }
+            None => self.http.call(dst).await.map_err(Into::into),
+        }
+    }
+}
+
+// Every attempt, API calls and token fetches alike, fails once `timeout()` passes without
+// progress, so a hung one comes back as an error the delegate can retry instead of never.
+// Reads are only timed once something has been written: hyper also polls connections idle in
+// its pool, and those must not use up the next request's time.
+pub struct TimedIo {
+    io: TokioIo<TcpStream>,
+    limit: Duration,
+    sent: bool,
+    read_idle: Option<Pin<Box<Sleep>>>,
+    write_idle: Option<Pin<Box<Sleep>>>,
+}
+
+impl TimedIo {
+    fn new(io: TokioIo<TcpStream>, limit: Duration) -> TimedIo {
+        TimedIo {
+            io,
+            limit,
+            sent: false,
+            read_idle: None,
+            write_idle: None,
+        }
+    }
+}
+
+fn poll_idle<T>(
+    idle: &mut Option<Pin<Box<Sleep>>>,
+    limit: Duration,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<T>> {
+    let sleep = idle.get_or_insert_with(|| Box::pin(tokio::time::sleep(limit)));
+    sleep.as_mut().poll(cx).map(|()| {
+        tracing::debug!("no progress after {limit:?}");
+        Err(timed_out(limit))
+    })
+}
+
+impl Read for TimedIo {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Poll::Ready(result) = Pin::new(&mut this.io).poll_read(cx, buf) {
+            this.read_idle = None;
+            return Poll::Ready(result);
+        }
+        if !this.sent {
+            return Poll::Pending;
+        }
+        poll_idle(&mut this.read_idle, this.limit, cx)
+    }
+}
+
+impl Write for TimedIo {
+    // Each write starts the wait for the answer afresh
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if let Poll::Ready(result) = Pin::new(&mut this.io).poll_write(cx, buf) {
+            this.sent = true;
+            this.read_idle = None;
+            this.write_idle = None;
+            return Poll::Ready(result);
+        }
+        poll_idle(&mut this.write_idle, this.limit, cx)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Poll::Ready(result) = Pin::new(&mut this.io).poll_flush(cx) {
+            this.write_idle = None;
+            return Poll::Ready(result);
+        }
+        poll_idle(&mut this.write_idle, this.limit, cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+}
+
+impl Connection for TimedIo {
+    fn connected(&self) -> Connected {
+        self.io.connected()
+    }
+}
+
+// The config proxy wins over HTTPS_PROXY/HTTP_PROXY; NO_PROXY applies to both
+fn proxy_matcher(network: &NetworkConfig) -> Matcher {
+    match &network.proxy {
+        Some(proxy) => Matcher::builder()
+            .all(proxy.clone())
+            .no(std::env::var("NO_PROXY")
+                .or(std::env::var("no_proxy"))
+                .unwrap_or_default())
+            .build(),
+        None => Matcher::from_env(),
+    }
+}
+
+// Native roots plus the optional extra bundle for TLS-intercepting corporate proxies
+pub fn tls_config(network: &NetworkConfig) -> Result<rustls::ClientConfig, BoxError> {
+    let mut roots = rustls::RootCertStore::empty();
+    let (added, _) =
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+    if let Some(path) = &network.ca_bundle {
+        for cert in CertificateDer::pem_file_iter(path)
+            .map_err(|e| format!("Failed to read CA bundle {}: {e}", path.display()))?
+        {
+            roots.add(cert.map_err(|e| format!("Bad certificate in CA bundle: {e}"))?)?;
+        }
+    } else if added == 0 {
+        return Err("No native root certificates found".into());
+    }
+    Ok(rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+// Every reqwest client goes through here for the proxy, CA bundle and timeout; callers add
+// their own user agent or a longer timeout. A bad CA bundle is logged and leaves the
+// native roots.
+pub fn http_client() -> reqwest::ClientBuilder {
+    let mut builder = reqwest::Client::builder().timeout(timeout());
+    let Some(network) = NETWORK.get() else {
+        return builder;
+    };
+    match tls_config(network) {
+        Ok(tls) => builder = builder.use_preconfigured_tls(tls),
+        Err(e) => tracing::warn!("tls: {e}"),
+    }
+    if let Some(proxy) = &network.proxy {
+        match reqwest::Proxy::all(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_env())),
+            Err(e) => tracing::warn!("proxy {proxy:?}: {e}"),
+        }
+    }
+    builder
+}
+
+// What yup_oauth2 signs in and refreshes tokens through, so those go by [network] as well
+pub fn token_client(network: &NetworkConfig) -> Result<Client<Connector, String>, BoxError> {
+    Ok(Client::builder(TokioExecutor::new()).build(build_connector(network)?))
+}
+
+pub fn build_connector(network: &NetworkConfig) -> Result<Connector, BoxError> {
+    let mut http = HttpConnector::new();
+    // HttpConnector won't enforce scheme, but HttpsConnector will
+    http.enforce_http(false);
+
+    Ok(HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config(network)?)
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(ProxyConnector {
+            http,
+            matcher: Arc::new(proxy_matcher(network)),
+        }))
+}
@@ -0,0 +1,50 @@
+use crate::platform;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_file};
+use std::io;
+use std::path::PathBuf;
+
+// One Markdown file per day under ~/.local/share/calpersonal/notes, named YYYY-MM-DD.md
+fn notes_dir() -> PathBuf {
+    platform::data_dir().join("notes")
+}
+
+fn path(date: NaiveDate) -> PathBuf {
+    notes_dir().join(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+// Days that have a note, for the cell markers
+pub fn dates() -> HashSet<NaiveDate> {
+    read_dir(notes_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().into_string().ok()?;
+                    NaiveDate::parse_from_str(name.strip_suffix(".md")?, "%Y-%m-%d").ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn read(date: NaiveDate) -> Option<String> {
+    read_to_string(path(date)).ok()
+}
+
+// Opens the day's note in $VISUAL/$EDITOR and blocks until it exits; the terminal must be
+// handed over first. Notes left empty are removed.
+pub fn edit(date: NaiveDate) -> io::Result<()> {
+    let path = path(date);
+    create_dir_all(notes_dir())?;
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or(platform::DEFAULT_EDITOR.to_string());
+    // Through the shell so editors configured with arguments ("code -w") work
+    platform::shell_with_path(&editor, &path).status()?;
+    if read_to_string(&path).is_ok_and(|text| text.trim().is_empty()) {
+        remove_file(&path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::config::{CommandPanelConfig, PanelSlot};
+use crate::platform;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Paragraph, Widget},
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Extra pane drawn in the panel slot next to the main area.
+// Register built-in ones from config, or others through `App::builder().panel(..)`.
+pub trait Panel: Send {
+    fn title(&self) -> &str;
+
+    // Called every tick; slow work belongs in a background task
+    fn update(&mut self) {}
+
+    fn render(&self, area: Rect, buf: &mut Buffer);
+}
+
+// Shows the output of a shell command, rerun every `interval_secs`
+// (e.g. `curl -s feed | xq ...` for headlines or a stock quote script)
+pub struct CommandPanel {
+    config: CommandPanelConfig,
+    last_run: Option<Instant>,
+    output: Arc<Mutex<Vec<String>>>,
+}
+
+impl CommandPanel {
+    pub fn new(config: CommandPanelConfig) -> CommandPanel {
+        CommandPanel {
+            config,
+            last_run: None,
+            output: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl Panel for CommandPanel {
+    fn title(&self) -> &str {
+        &self.config.name
+    }
+
+    fn update(&mut self) {
+        let interval = Duration::from_secs(self.config.interval_secs);
+        if self.last_run.is_some_and(|t| t.elapsed() < interval) {
+            return;
+        }
+        self.last_run = Some(Instant::now());
+
+        let command = self.config.command.clone();
+        let output = self.output.clone();
+        tokio::spawn(async move {
+            let lines = match tokio::process::Command::from(platform::shell(&command))
+                .output()
+                .await
+            {
+                Ok(out) => String::from_utf8_lossy(&out.stdout)
+                    .lines()
+                    .map(str::to_string)
+                    .collect(),
+                Err(e) => vec![format!("Failed to run: {e}")],
+            };
+            *output.lock().unwrap() = lines;
+        });
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let lines: Vec<Line> = self
+            .output
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|l| Line::raw(l.clone()))
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+// Live tail of the log file, added by --debug
+pub struct LogPanel;
+
+impl Panel for LogPanel {
+    fn title(&self) -> &str {
+        "Log"
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let recent = crate::logging::recent();
+        let lines: Vec<Line> = recent
+            .iter()
+            .skip(recent.len().saturating_sub(area.height as usize))
+            .map(|l| Line::raw(l.clone()))
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+// Splits `area` into the main content and the panel slot
+pub fn split(area: Rect, slot: PanelSlot, size: u16, has_panels: bool) -> (Rect, Option<Rect>) {
+    let direction = match slot {
+        _ if !has_panels => return (area, None),
+        PanelSlot::None => return (area, None),
+        PanelSlot::Right => Direction::Horizontal,
+        PanelSlot::Bottom => Direction::Vertical,
+    };
+    let chunks = Layout::new(
+        direction,
+        [
+            Constraint::Percentage(100 - size.min(90)),
+            Constraint::Percentage(size.min(90)),
+        ],
+    )
+    .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+// Panels share the slot evenly, stacked along its long side
+pub fn render_all(panels: &[Box<dyn Panel>], slot: PanelSlot, area: Rect, buf: &mut Buffer) {
+    let direction = match slot {
+        PanelSlot::Bottom => Direction::Horizontal,
+        _ => Direction::Vertical,
+    };
+    let areas = Layout::new(direction, vec![Constraint::Fill(1); panels.len()]).split(area);
+    for (panel, area) in panels.iter().zip(areas.iter()) {
+        let block = Block::bordered().title(panel.title());
+        let inner = block.inner(*area);
+        block.render(*area, buf);
+        panel.render(inner, buf);
+    }
+}
@@ -1,4 +1,54 @@
-use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use crate::events::EventKind;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// Compiled once: day_load parses every task title on every frame
+static TIME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s").unwrap());
+static DATE_TIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\d{1,2}/\d{1,2})\s+(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s").unwrap()
+});
+static YEAR_DATE_TIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s+(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s").unwrap()
+});
+static DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{1,2}/\d{1,2})\s+-\s+(\d{1,2}/\d{1,2})\s").unwrap());
+static YEAR_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s+-\s+(\d{4}/\d{1,2}/\d{1,2})\s").unwrap());
+static ONLY_DATE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{1,2}/\d{1,2})\s").unwrap());
+static ONLY_YEAR_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s").unwrap());
+static NOTES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\snotes:\s(.+)$").unwrap());
+static DURATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:(\d+)h)?(?:(\d+)m)?$").unwrap());
+
+// Leading `ooo` / `focus` tokens select a special Google event type
+pub fn parse_event_kind(input: &str) -> (EventKind, &str) {
+    let trimmed = input.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("ooo ") {
+        (EventKind::OutOfOffice, rest.trim_start())
+    } else if let Some(rest) = trimmed.strip_prefix("focus ") {
+        (EventKind::FocusTime, rest.trim_start())
+    } else {
+        (EventKind::Normal, input)
+    }
+}
+
+// A trailing `meet` token asks Google to attach a Meet link
+pub fn parse_meet_token(input: &str) -> (&str, bool) {
+    match input.trim_end().strip_suffix(" meet") {
+        Some(rest) => (rest, true),
+        None => (input, false),
+    }
+}
+
+// A trailing "private" keeps the event in the local encrypted store instead of Google
+pub fn parse_private_token(input: &str) -> (&str, bool) {
+    match input.trim_end().strip_suffix(" private") {
+        Some(rest) => (rest, true),
+        None => (input, false),
+    }
+}
 
 pub fn parse_time_range(
     input: &str,
@@ -12,19 +62,7 @@ pub fn parse_time_range(
 ) {
     // Trimming and checking empty is already done
 
-    let time_re = regex::Regex::new(r"^(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s").unwrap();
-    let date_time_re =
-        regex::Regex::new(r"^(\d{1,2}/\d{1,2})\s+(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s").unwrap();
-    let year_date_time_re =
-        regex::Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s+(\d{1,2}:\d{2})\s+-\s+(\d{1,2}:\d{2})\s")
-            .unwrap();
-    let date_re = regex::Regex::new(r"^(\d{1,2}/\d{1,2})\s+-\s+(\d{1,2}/\d{1,2})\s").unwrap();
-    let year_date_re =
-        regex::Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s+-\s+(\d{4}/\d{1,2}/\d{1,2})\s").unwrap();
-    let only_date_re = regex::Regex::new(r"^(\d{1,2}/\d{1,2})\s").unwrap();
-    let only_year_date_re = regex::Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s").unwrap();
-
-    if let Some(caps) = time_re.captures(input) {
+    if let Some(caps) = TIME_RE.captures(input) {
         let start_str = caps.get(1).unwrap().as_str();
         let end_str = caps.get(2).unwrap().as_str();
 
@@ -42,19 +80,19 @@ pub fn parse_time_range(
                 None,
             );
         }
-    } else if let Some(caps) = date_time_re.captures(input) {
+    } else if let Some(caps) = DATE_TIME_RE.captures(input) {
         let current_year = current_date.year().to_string();
-        let event_date = caps.get(1).unwrap().as_str().to_owned();
+        let event_date = caps.get(1).unwrap().as_str();
         let start_str = caps.get(2).unwrap().as_str();
         let end_str = caps.get(3).unwrap().as_str();
 
         if let (Ok(start), Ok(end)) = (
             NaiveDateTime::parse_from_str(
-                &(current_year.clone() + "/" + &event_date + ":" + start_str),
+                &(current_year.clone() + "/" + event_date + ":" + start_str),
                 "%Y/%m/%d:%H:%M",
             ),
             NaiveDateTime::parse_from_str(
-                &(current_year + "/" + &event_date + ":" + end_str),
+                &(current_year + "/" + event_date + ":" + end_str),
                 "%Y/%m/%d:%H:%M",
             ),
         ) {
@@ -62,7 +100,7 @@ pub fn parse_time_range(
             let summary = input[summary_start..].trim().to_string();
             return (summary, Some(start), Some(end), None, None);
         }
-    } else if let Some(caps) = year_date_time_re.captures(input) {
+    } else if let Some(caps) = YEAR_DATE_TIME_RE.captures(input) {
         let event_date = caps.get(1).unwrap().as_str().to_owned();
         let start_str = caps.get(2).unwrap().as_str();
         let end_str = caps.get(3).unwrap().as_str();
@@ -78,7 +116,7 @@ pub fn parse_time_range(
             let summary = input[summary_start..].trim().to_string();
             return (summary, Some(start), Some(end), None, None);
         }
-    } else if let Some(caps) = date_re.captures(input) {
+    } else if let Some(caps) = DATE_RE.captures(input) {
         let current_year = current_date.year().to_string();
         let start_str = caps.get(1).unwrap().as_str();
         let end_str = caps.get(2).unwrap().as_str();
@@ -91,19 +129,19 @@ pub fn parse_time_range(
             let summary = input[summary_start..].trim().to_string();
             return (summary, None, None, Some(start), Some(end));
         }
-    } else if let Some(caps) = year_date_re.captures(input) {
+    } else if let Some(caps) = YEAR_DATE_RE.captures(input) {
         let start_str = caps.get(1).unwrap().as_str();
         let end_str = caps.get(2).unwrap().as_str();
 
         if let (Ok(start), Ok(end)) = (
-            NaiveDate::parse_from_str(&(start_str), "%Y/%-m/%-d"),
-            NaiveDate::parse_from_str(&(end_str), "%Y/%-m/%-d"),
+            NaiveDate::parse_from_str(start_str, "%Y/%-m/%-d"),
+            NaiveDate::parse_from_str(end_str, "%Y/%-m/%-d"),
         ) {
             let summary_start = caps.get(0).unwrap().end();
             let summary = input[summary_start..].trim().to_string();
             return (summary, None, None, Some(start), Some(end));
         }
-    } else if let Some(caps) = only_date_re.captures(input) {
+    } else if let Some(caps) = ONLY_DATE_RE.captures(input) {
         let current_year = current_date.year().to_string();
         let start_str = caps.get(1).unwrap().as_str();
 
@@ -120,10 +158,10 @@ pub fn parse_time_range(
                 Some(start.succ_opt().unwrap()),
             );
         }
-    } else if let Some(caps) = only_year_date_re.captures(input) {
+    } else if let Some(caps) = ONLY_YEAR_DATE_RE.captures(input) {
         let start_str = caps.get(1).unwrap().as_str();
 
-        if let Ok(start) = NaiveDate::parse_from_str(&(start_str), "%Y/%-m/%-d") {
+        if let Ok(start) = NaiveDate::parse_from_str(start_str, "%Y/%-m/%-d") {
             let summary_start = caps.get(0).unwrap().end();
             let summary = input[summary_start..].trim().to_string();
             return (
@@ -143,10 +181,8 @@ pub fn parse_date_and_note(
     input: &str,
     current_year: i32,
 ) -> (String, Option<String>, Option<String>) {
-    let mm_dd_re = regex::Regex::new(r"^(\d{1,2}/\d{1,2})\s").unwrap();
-    let yyyy_mm_dd_re = regex::Regex::new(r"^(\d{4}/\d{1,2}/\d{1,2})\s").unwrap();
     // 2026-01-20T00:00:00.000Z
-    let (title_without_date, due_date) = if let Some(caps) = mm_dd_re.captures(input) {
+    let (title_without_date, due_date) = if let Some(caps) = ONLY_DATE_RE.captures(input) {
         let due_str = caps.get(1).unwrap().as_str();
         if let Ok(due) =
             NaiveDate::parse_from_str(&(current_year.to_string() + due_str), "%Y%-m/%-d")
@@ -160,7 +196,7 @@ pub fn parse_date_and_note(
         } else {
             (input.to_string(), None)
         }
-    } else if let Some(caps) = yyyy_mm_dd_re.captures(input) {
+    } else if let Some(caps) = ONLY_YEAR_DATE_RE.captures(input) {
         let due_str = caps.get(1).unwrap().as_str();
         if let Ok(due) = NaiveDate::parse_from_str(due_str, "%Y/%-m/%-d") {
             let title_start = caps.get(0).unwrap().end();
@@ -176,8 +212,7 @@ pub fn parse_date_and_note(
         (input.to_string(), None)
     };
 
-    let notes_re = regex::Regex::new(r"\snotes:\s(.+)$").unwrap();
-    let (rem, notes) = if let Some(caps) = notes_re.captures(&title_without_date) {
+    let (rem, notes) = if let Some(caps) = NOTES_RE.captures(&title_without_date) {
         let notes_str = caps.get(1).unwrap().as_str();
         let title_end = caps.get(0).unwrap().start();
         let title = title_without_date[..title_end].trim().to_string();
@@ -188,3 +223,48 @@ pub fn parse_date_and_note(
 
     (rem, due_date, notes)
 }
+
+// `30m`, `1h` or `1h30m`
+pub fn parse_duration(token: &str) -> Option<Duration> {
+    let caps = DURATION_RE.captures(token)?;
+    if caps.get(1).is_none() && caps.get(2).is_none() {
+        return None;
+    }
+    let hours: i64 = caps.get(1).map_or(Ok(0), |h| h.as_str().parse()).ok()?;
+    let minutes: i64 = caps.get(2).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+    // `0m` is not a length anything can take
+    Some(Duration::minutes(hours * 60 + minutes)).filter(|d| *d > Duration::zero())
+}
+
+// A `~2h` or `~45m` effort estimate anywhere in a task title, and the title without it
+pub fn parse_effort(title: &str) -> (String, Option<Duration>) {
+    let mut effort = None;
+    let mut rest = Vec::new();
+    for token in title.split_whitespace() {
+        match token.strip_prefix('~').and_then(parse_duration) {
+            Some(d) if effort.is_none() => effort = Some(d),
+            _ => rest.push(token),
+        }
+    }
+    (rest.join(" "), effort)
+}
+
+// Attendee emails, a duration token and the remaining words as the title,
+// e.g. `alice@example.com bob@example.com 45m Design review`
+pub fn parse_meeting_request(input: &str) -> (Vec<String>, Option<Duration>, String) {
+    let mut attendees = Vec::new();
+    let mut duration = None;
+    let mut title = Vec::new();
+    for token in input.split_whitespace() {
+        if token.contains('@') {
+            attendees.push(token.trim_end_matches(',').to_string());
+        } else if duration.is_none()
+            && let Some(d) = parse_duration(token)
+        {
+            duration = Some(d);
+        } else {
+            title.push(token);
+        }
+    }
+    (attendees, duration, title.join(" "))
+}
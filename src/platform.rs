@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+// Set by --demo so nothing reads or writes the real config, cache or data
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn set_root(root: PathBuf) {
+    let _ = ROOT.set(root);
+}
+
+// Linux follows XDG (~/.config and ~/.cache unless overridden), Windows uses %APPDATA% and
+// %LOCALAPPDATA%. macOS keeps the dot-paths terminal tools use instead of ~/Library.
+#[cfg(not(target_os = "macos"))]
+fn base_dirs() -> (Option<PathBuf>, Option<PathBuf>) {
+    (dirs::config_dir(), dirs::cache_dir())
+}
+
+#[cfg(target_os = "macos")]
+fn base_dirs() -> (Option<PathBuf>, Option<PathBuf>) {
+    let home = dirs::home_dir();
+    (
+        home.as_ref().map(|h| h.join(".config")),
+        home.map(|h| h.join(".cache")),
+    )
+}
+
+pub fn config_dir() -> PathBuf {
+    if let Some(root) = ROOT.get() {
+        return root.join("config");
+    }
+    base_dirs()
+        .0
+        .expect("Could not find config directory")
+        .join("calpersonal")
+}
+
+pub fn cache_dir() -> PathBuf {
+    if let Some(root) = ROOT.get() {
+        return root.join("cache");
+    }
+    base_dirs()
+        .1
+        .expect("Could not find cache directory")
+        .join("calpersonal")
+}
+
+// Notes, memos and private events: ~/.local/share, ~/Library/Application Support or %APPDATA%
+pub fn data_dir() -> PathBuf {
+    if let Some(root) = ROOT.get() {
+        return root.join("data");
+    }
+    dirs::data_dir()
+        .expect("Could not find data directory")
+        .join("calpersonal")
+}
+
+// Paths from the config may start at the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .expect("Could not find home directory")
+            .join(rest),
+        None => path.into(),
+    }
+}
+
+// Used when neither $VISUAL nor $EDITOR is set
+pub const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+// A user-configured command line, run through the shell so it can carry its own arguments
+#[cfg(not(windows))]
+pub fn shell(command_line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(command_line);
+    command
+}
+
+// cmd does its own parsing of the line, so it goes over untouched rather than quoted again
+#[cfg(windows)]
+pub fn shell(command_line: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(command_line);
+    command
+}
+
+// `program` with `path` as its last argument, e.g. the note editor or the decrypt command.
+// sh gets the path as $1 so it needs no quoting.
+#[cfg(not(windows))]
+pub fn shell_with_path(program: &str, path: &Path) -> Command {
+    let mut command = shell(&format!("{program} \"$1\""));
+    command.arg("sh").arg(path);
+    command
+}
+
+#[cfg(windows)]
+pub fn shell_with_path(program: &str, path: &Path) -> Command {
+    shell(&format!("{program} \"{}\"", path.display()))
+}
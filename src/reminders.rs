@@ -0,0 +1,167 @@
+use crate::local_reminders::LocalReminder;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use google_calendar3::api;
+use std::collections::{HashMap, HashSet};
+
+// Google caps reminders at four weeks before the event
+const MAX_REMINDER_MINUTES: i64 = 40320;
+
+pub struct DueReminder {
+    pub summary: String,
+    pub minutes: i32,
+}
+
+#[derive(Clone)]
+pub struct Alarm {
+    pub summary: String,
+    pub start: DateTime<FixedOffset>,
+    pub location: Option<String>,
+}
+
+// Popup reminder offsets (minutes before start) the same way Google resolves them:
+// `useDefault` (or no reminders block at all) falls back to the calendar's defaults,
+// or to `default_minutes` when the config sets one for the calendar
+pub fn reminder_minutes(
+    event: &api::Event,
+    calendar: Option<&api::CalendarListEntry>,
+    default_minutes: Option<i32>,
+) -> Vec<i32> {
+    let use_default = event
+        .reminders
+        .as_ref()
+        .map(|r| r.use_default.unwrap_or(false))
+        .unwrap_or(true);
+
+    if use_default && let Some(minutes) = default_minutes {
+        return vec![minutes];
+    }
+    let reminders = if use_default {
+        calendar.and_then(|c| c.default_reminders.as_ref())
+    } else {
+        event.reminders.as_ref().and_then(|r| r.overrides.as_ref())
+    };
+
+    reminders
+        .map(|list| {
+            list.iter()
+                .filter(|r| r.method.as_deref().unwrap_or("popup") == "popup")
+                .filter_map(|r| r.minutes)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn event_start(event: &api::Event, app_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+    let start = event.start.as_ref()?;
+    if let Some(date_time) = start.date_time {
+        Some(date_time.with_timezone(&app_tz))
+    } else {
+        start
+            .date?
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(app_tz)
+            .latest()
+    }
+}
+
+// Reminders whose fire time falls in (since, now]
+pub fn due_reminders(
+    events_cache: &HashMap<NaiveDate, Vec<(api::Event, String)>>,
+    calendars: &HashMap<String, api::CalendarListEntry>,
+    default_minutes: &HashMap<String, i32>, // by calendar id
+    app_tz: FixedOffset,
+    since: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Vec<DueReminder> {
+    let first_day = now.date_naive();
+    let last_day = (now + Duration::minutes(MAX_REMINDER_MINUTES)).date_naive();
+
+    let mut due = Vec::new();
+    for date in first_day.iter_days().take_while(|d| *d <= last_day) {
+        let Some(events) = events_cache.get(&date) else {
+            continue;
+        };
+        for (event, calendar_id) in events {
+            let Some(start) = event_start(event, app_tz) else {
+                continue;
+            };
+            let default_minutes = default_minutes.get(calendar_id).copied();
+            for minutes in reminder_minutes(event, calendars.get(calendar_id), default_minutes) {
+                let fire_at = start - Duration::minutes(minutes as i64);
+                if fire_at > since && fire_at <= now {
+                    due.push(DueReminder {
+                        summary: event.summary.clone().unwrap_or("Untitled".to_string()),
+                        minutes,
+                    });
+                }
+            }
+        }
+    }
+    due
+}
+
+// Timed events picked out by `critical` that start in (since, now]
+pub fn due_alarms(
+    events_cache: &HashMap<NaiveDate, Vec<(api::Event, String)>>,
+    app_tz: FixedOffset,
+    since: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+    critical: impl Fn(&api::Event) -> bool,
+) -> Vec<Alarm> {
+    let mut seen = HashSet::new();
+    let mut due = Vec::new();
+    for date in since
+        .date_naive()
+        .iter_days()
+        .take_while(|d| *d <= now.date_naive())
+    {
+        for (event, _) in events_cache.get(&date).into_iter().flatten() {
+            let Some(start) = event.start.as_ref().and_then(|s| s.date_time) else {
+                continue;
+            };
+            let start = start.with_timezone(&app_tz);
+            // The same event can sit on more than one shown calendar
+            if start <= since
+                || start > now
+                || !critical(event)
+                || !seen.insert(event.i_cal_uid.clone().or(event.id.clone()))
+            {
+                continue;
+            }
+            due.push(Alarm {
+                summary: event.summary.clone().unwrap_or("Untitled".to_string()),
+                start,
+                location: event.location.clone(),
+            });
+        }
+    }
+    due
+}
+
+// Local recurring reminders whose time falls in (since, now], in `now`'s offset
+pub fn due_local(
+    reminders: &[LocalReminder],
+    since: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Vec<&LocalReminder> {
+    let mut due = Vec::new();
+    for date in since
+        .date_naive()
+        .iter_days()
+        .take_while(|d| *d <= now.date_naive())
+    {
+        for reminder in reminders.iter().filter(|r| r.repeat.on(date)) {
+            let Some(at) = date
+                .and_time(reminder.time)
+                .and_local_timezone(*now.offset())
+                .single()
+            else {
+                continue;
+            };
+            if at > since && at <= now {
+                due.push(reminder);
+            }
+        }
+    }
+    due
+}
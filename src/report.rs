@@ -0,0 +1,241 @@
+use crate::{config, events, file_writing};
+use chrono::{DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, TimeZone, Utc};
+use google_calendar3::api;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::ops::RangeInclusive;
+
+// A meeting that starts within this long of the previous one ending continues a streak
+const BACK_TO_BACK_GAP: i64 = 5;
+const TOP_ATTENDEES: usize = 5;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Period {
+    Week,
+    Month,
+}
+
+impl Period {
+    // The Sunday-to-Saturday week or the calendar month holding `date`
+    pub fn range(self, date: NaiveDate) -> RangeInclusive<NaiveDate> {
+        match self {
+            Period::Week => {
+                let start = date - Days::new(date.weekday().num_days_from_sunday() as u64);
+                start..=start + Days::new(6)
+            }
+            Period::Month => {
+                let start = date.with_day(1).unwrap();
+                start..=start + Months::new(1) - Days::new(1)
+            }
+        }
+    }
+
+    pub fn shift(self, date: NaiveDate, forward: bool) -> NaiveDate {
+        match (self, forward) {
+            (Period::Week, true) => date + Days::new(7),
+            (Period::Week, false) => date - Days::new(7),
+            (Period::Month, true) => date + Months::new(1),
+            (Period::Month, false) => date - Months::new(1),
+        }
+    }
+
+    pub fn toggled(self) -> Period {
+        match self {
+            Period::Week => Period::Month,
+            Period::Month => Period::Week,
+        }
+    }
+}
+
+pub struct Report {
+    pub period: Period,
+    pub range: RangeInclusive<NaiveDate>,
+    pub meetings: usize,
+    pub total: Duration, // summed, so overlapping meetings both count
+    pub busiest: Option<(NaiveDate, Duration)>,
+    pub streaks: usize, // runs of two or more back-to-back meetings
+    pub longest_streak: usize,
+    pub attendees: Vec<(String, usize)>,
+}
+
+// Timed events with someone else on the guest list, unless declined
+fn is_meeting(event: &api::Event) -> bool {
+    !events::EventKind::of(event).is_background()
+        && events::response_status(event) != "declined"
+        && event.attendees.as_ref().is_some_and(|attendees| {
+            attendees
+                .iter()
+                .any(|a| a.self_ != Some(true) && a.resource != Some(true))
+        })
+}
+
+fn times(event: &api::Event) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = event.start.as_ref()?.date_time?;
+    let end = event.end.as_ref()?.date_time?;
+    Some((start, end.max(start)))
+}
+
+pub fn build<'a, Tz: TimeZone>(
+    period: Period,
+    date: NaiveDate,
+    events: impl IntoIterator<Item = &'a api::Event>,
+    tz: &Tz,
+) -> Report {
+    let range = period.range(date);
+    let mut meetings: Vec<(&api::Event, DateTime<Utc>, DateTime<Utc>)> = events
+        .into_iter()
+        .filter(|ev| is_meeting(ev))
+        .filter_map(|ev| times(ev).map(|(start, end)| (ev, start, end)))
+        .filter(|(_, start, _)| range.contains(&start.with_timezone(tz).date_naive()))
+        .collect();
+    meetings.sort_by_key(|(_, start, end)| (*start, *end));
+    // The same meeting can sit on more than one shown calendar; without an iCal UID (older
+    // caches) the same title at the same time counts as the same meeting
+    let mut seen = HashSet::new();
+    meetings.retain(|(ev, start, end)| {
+        let key = ev.i_cal_uid.as_ref().or(ev.summary.as_ref());
+        seen.insert((key.cloned(), *start, *end))
+    });
+
+    let mut total = Duration::zero();
+    let mut per_day: HashMap<NaiveDate, Duration> = HashMap::new();
+    let mut streaks = 0;
+    let mut longest_streak = 0;
+    let mut run = 0;
+    let mut run_end: Option<DateTime<Utc>> = None;
+    let mut people: HashMap<String, (String, usize)> = HashMap::new();
+    for (ev, start, end) in &meetings {
+        let length = *end - *start;
+        total += length;
+        *per_day
+            .entry(start.with_timezone(tz).date_naive())
+            .or_default() += length;
+
+        let continues =
+            run_end.is_some_and(|last| *start <= last + Duration::minutes(BACK_TO_BACK_GAP));
+        run = if continues { run + 1 } else { 1 };
+        if run == 2 {
+            streaks += 1;
+        }
+        longest_streak = longest_streak.max(run);
+        run_end = Some(match run_end {
+            Some(last) if continues => last.max(*end),
+            _ => *end,
+        });
+
+        for attendee in ev.attendees.iter().flatten() {
+            if attendee.self_ == Some(true) || attendee.resource == Some(true) {
+                continue;
+            }
+            let Some(email) = attendee.email.as_deref() else {
+                continue;
+            };
+            let name = attendee.display_name.clone().unwrap_or(email.to_string());
+            people.entry(email.to_lowercase()).or_insert((name, 0)).1 += 1;
+        }
+    }
+    let busiest = per_day
+        .into_iter()
+        .max_by_key(|(date, length)| (*length, std::cmp::Reverse(*date)));
+    let mut attendees: Vec<(String, usize)> = people.into_values().collect();
+    attendees.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    attendees.truncate(TOP_ATTENDEES);
+
+    Report {
+        period,
+        range,
+        meetings: meetings.len(),
+        total,
+        busiest,
+        streaks,
+        // A lone meeting is not a streak
+        longest_streak: if longest_streak > 1 {
+            longest_streak
+        } else {
+            0
+        },
+        attendees,
+    }
+}
+
+fn hours(length: Duration) -> String {
+    format!("{}h{:02}m", length.num_hours(), length.num_minutes() % 60)
+}
+
+impl Report {
+    pub fn title(&self, date_format: &str) -> String {
+        match self.period {
+            Period::Week => format!(
+                "Meeting load, week of {}",
+                self.range.start().format(date_format)
+            ),
+            Period::Month => format!("Meeting load, {}", self.range.start().format("%B %Y")),
+        }
+    }
+
+    // (label, value) rows, shared by the view and the Markdown export
+    pub fn rows(&self, date_format: &str) -> Vec<(&'static str, String)> {
+        let days = (*self.range.end() - *self.range.start()).num_days() + 1;
+        let mut rows = vec![
+            ("Meetings", self.meetings.to_string()),
+            ("Meeting hours", hours(self.total)),
+            ("Per day", hours(self.total / days as i32)),
+            ("Back-to-back streaks", self.streaks.to_string()),
+            ("Longest streak", self.longest_streak.to_string()),
+        ];
+        if let Some((date, length)) = self.busiest {
+            rows.push((
+                "Busiest day",
+                format!("{} ({})", date.format(date_format), hours(length)),
+            ));
+        }
+        rows
+    }
+
+    pub fn markdown(&self, date_format: &str) -> String {
+        let mut text = format!("# {}\n\n", self.title(date_format));
+        text.push_str("| | |\n|---|---|\n");
+        for (label, value) in self.rows(date_format) {
+            text.push_str(&format!("| {label} | {value} |\n"));
+        }
+        if !self.attendees.is_empty() {
+            text.push_str("\n## Most frequent attendees\n\n");
+            for (name, count) in &self.attendees {
+                text.push_str(&format!("- {name}: {count}\n"));
+            }
+        }
+        text
+    }
+}
+
+// `calpersonal report [--month] [--date YYYY-MM-DD]`: the report as Markdown, from the
+// events cache the TUI keeps
+pub fn run(args: &[String]) -> io::Result<()> {
+    let period = if args.iter().any(|a| a == "--month") {
+        Period::Month
+    } else {
+        Period::Week
+    };
+    let date = match args.iter().position(|a| a == "--date") {
+        Some(i) => {
+            let value = args.get(i + 1).map(String::as_str).unwrap_or_default();
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Expected --date YYYY-MM-DD, got {value:?}"),
+                )
+            })?
+        }
+        None => Local::now().date_naive(),
+    };
+    let config = config::parse_config();
+    let cache = file_writing::load_events_cache();
+    let report = build(
+        period,
+        date,
+        cache.values().flatten().map(|(ev, _)| ev),
+        &Local,
+    );
+    print!("{}", report.markdown(&config.display.date_format));
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use chrono::{Datelike, Days, NaiveDate};
+use google_calendar3::api;
+use google_tasks1::api::Task;
+use std::collections::HashMap;
+
+// The weekly review walks these in order; decisions are only sent after the last one
+#[derive(Clone, Copy, PartialEq)]
+pub enum Step {
+    Overdue,
+    NextWeek,
+    Unscheduled,
+}
+
+impl Step {
+    pub fn title(self) -> &'static str {
+        match self {
+            Step::Overdue => "Overdue tasks",
+            Step::NextWeek => "Next week",
+            Step::Unscheduled => "Unscheduled tasks",
+        }
+    }
+
+    fn next(self) -> Option<Step> {
+        match self {
+            Step::Overdue => Some(Step::NextWeek),
+            Step::NextWeek => Some(Step::Unscheduled),
+            Step::Unscheduled => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Decision {
+    Keep,
+    Today,
+    Tomorrow,
+    NextMonday,
+    Complete,
+}
+
+impl Decision {
+    pub fn label(self) -> &'static str {
+        match self {
+            Decision::Keep => "keep",
+            Decision::Today => "today",
+            Decision::Tomorrow => "tomorrow",
+            Decision::NextMonday => "next Monday",
+            Decision::Complete => "done",
+        }
+    }
+
+    fn next(self) -> Decision {
+        match self {
+            Decision::Keep => Decision::Today,
+            Decision::Today => Decision::Tomorrow,
+            Decision::Tomorrow => Decision::NextMonday,
+            Decision::NextMonday => Decision::Complete,
+            Decision::Complete => Decision::Keep,
+        }
+    }
+
+    // The patch to send, None when the task is left alone
+    fn patch(self, today: NaiveDate) -> Option<Task> {
+        let due = |date: NaiveDate| Task {
+            due: Some(date.format("%Y-%m-%dT00:00:00.000Z").to_string()),
+            ..Task::default()
+        };
+        match self {
+            Decision::Keep => None,
+            Decision::Today => Some(due(today)),
+            Decision::Tomorrow => Some(due(today + Days::new(1))),
+            Decision::NextMonday => Some(due(next_monday(today))),
+            Decision::Complete => Some(Task {
+                status: Some("completed".to_string()),
+                ..Task::default()
+            }),
+        }
+    }
+}
+
+fn next_monday(today: NaiveDate) -> NaiveDate {
+    today + Days::new(7 - today.weekday().num_days_from_monday() as u64)
+}
+
+fn due_date(task: &Task) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(task.due.as_deref()?.get(..10)?, "%Y-%m-%d").ok()
+}
+
+pub struct Review {
+    pub step: Step,
+    pub overdue: Vec<(Task, String)>,
+    pub next_week: Vec<(NaiveDate, api::Event)>,
+    pub unscheduled: Vec<(Task, String)>,
+    decisions: HashMap<String, Decision>, // task id → decision
+    today: NaiveDate,
+}
+
+impl Review {
+    pub fn new(
+        tasks: &[(Task, String)],
+        events: &HashMap<NaiveDate, Vec<(api::Event, String)>>,
+        today: NaiveDate,
+    ) -> Review {
+        let open: Vec<&(Task, String)> = tasks
+            .iter()
+            .filter(|(task, _)| task.status.as_deref() != Some("completed") && task.id.is_some())
+            .collect();
+        let monday = next_monday(today);
+        let next_week = (0..7)
+            .map(|d| monday + Days::new(d))
+            .flat_map(|date| {
+                events
+                    .get(&date)
+                    .into_iter()
+                    .flatten()
+                    .map(move |(ev, _)| (date, ev.clone()))
+            })
+            .collect();
+        Review {
+            step: Step::Overdue,
+            overdue: open
+                .iter()
+                .filter(|(task, _)| due_date(task).is_some_and(|due| due < today))
+                .map(|t| (*t).clone())
+                .collect(),
+            next_week,
+            unscheduled: open
+                .iter()
+                .filter(|(task, _)| task.due.is_none())
+                .map(|t| (*t).clone())
+                .collect(),
+            decisions: HashMap::new(),
+            today,
+        }
+    }
+
+    // Tasks the current step makes decisions on; the events step has none
+    pub fn tasks(&self) -> &[(Task, String)] {
+        match self.step {
+            Step::Overdue => &self.overdue,
+            Step::NextWeek => &[],
+            Step::Unscheduled => &self.unscheduled,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self.step {
+            Step::NextWeek => self.next_week.len(),
+            _ => self.tasks().len(),
+        }
+    }
+
+    pub fn decision(&self, task: &Task) -> Decision {
+        task.id
+            .as_ref()
+            .and_then(|id| self.decisions.get(id))
+            .copied()
+            .unwrap_or(Decision::Keep)
+    }
+
+    pub fn cycle(&mut self, index: usize) {
+        let Some(id) = self
+            .tasks()
+            .get(index)
+            .and_then(|(task, _)| task.id.clone())
+        else {
+            return;
+        };
+        let next = self
+            .decisions
+            .get(&id)
+            .copied()
+            .unwrap_or(Decision::Keep)
+            .next();
+        self.decisions.insert(id, next);
+    }
+
+    // Moves to the next step; false once the last one is done
+    pub fn advance(&mut self) -> bool {
+        match self.step.next() {
+            Some(step) => {
+                self.step = step;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // (tasklist id, task id, patch) for every task that was given a decision
+    pub fn changes(&self) -> Vec<(String, String, Task)> {
+        self.overdue
+            .iter()
+            .chain(&self.unscheduled)
+            .filter_map(|(task, tasklist_id)| {
+                let id = task.id.clone()?;
+                let patch = self.decision(task).patch(self.today)?;
+                Some((tasklist_id.clone(), id, patch))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,205 @@
+use crate::config::ProtectedBlock;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+    Weekday,
+};
+use google_calendar3::api;
+
+pub type Slot = (DateTime<FixedOffset>, DateTime<FixedOffset>);
+pub type Busy = (DateTime<Utc>, DateTime<Utc>);
+
+const WORKDAY_START: u32 = 9;
+const WORKDAY_END: u32 = 18;
+const STEP_MINUTES: i64 = 30;
+const MAX_SLOTS: usize = 20;
+
+fn overlaps(
+    busy: &[(DateTime<Utc>, DateTime<Utc>)],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> bool {
+    busy.iter()
+        .any(|(b_start, b_end)| *b_start < end && *b_end > start)
+}
+
+// Busy periods from a free/busy answer, plus the calendars Google couldn't answer for
+// as `id (reason)`. Those have no busy periods, so they must not be read as free.
+pub fn busy_periods(response: api::FreeBusyResponse) -> (Vec<Busy>, Vec<String>) {
+    let mut busy = Vec::new();
+    let mut unavailable = Vec::new();
+    for (id, calendar) in response.calendars.unwrap_or_default() {
+        let errors = calendar.errors.unwrap_or_default();
+        if errors.is_empty() {
+            busy.extend(
+                calendar
+                    .busy
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|period| Some((period.start?, period.end?))),
+            );
+        } else {
+            let reasons: Vec<_> = errors.into_iter().filter_map(|e| e.reason).collect();
+            unavailable.push(format!("{id} ({})", reasons.join(", ")));
+        }
+    }
+    unavailable.sort();
+    (busy, unavailable)
+}
+
+// The protected blocks falling on `date`, in local time
+pub fn protected_on(
+    blocks: &[ProtectedBlock],
+    date: NaiveDate,
+) -> impl Iterator<Item = (&str, NaiveDateTime, NaiveDateTime)> {
+    blocks
+        .iter()
+        .filter(move |b| b.days.is_empty() || b.days.contains(&date.weekday()))
+        .map(move |b| {
+            (
+                b.name.as_str(),
+                date.and_time(b.start),
+                date.and_time(b.end),
+            )
+        })
+}
+
+// Protected time over `days` days from `from`, as busy periods for the slot finder
+pub fn protected_busy(
+    blocks: &[ProtectedBlock],
+    from: NaiveDate,
+    days: u64,
+    tz: FixedOffset,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    from.iter_days()
+        .take(days as usize)
+        .flat_map(|date| protected_on(blocks, date))
+        .filter_map(|(_, start, end)| {
+            Some((
+                start.and_local_timezone(tz).single()?.to_utc(),
+                end.and_local_timezone(tz).single()?.to_utc(),
+            ))
+        })
+        .collect()
+}
+
+// The working hours of `date`
+pub fn workday(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    (
+        date.and_hms_opt(WORKDAY_START, 0, 0).unwrap(),
+        date.and_hms_opt(WORKDAY_END, 0, 0).unwrap(),
+    )
+}
+
+// Gaps of at least `min` between `from` and `to` that none of `busy` touches
+pub fn free_between(
+    busy: &[(NaiveDateTime, NaiveDateTime)],
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    min: Duration,
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut busy: Vec<_> = busy
+        .iter()
+        .filter(|(start, end)| *start < to && *end > from)
+        .collect();
+    busy.sort();
+    let mut free = Vec::new();
+    let mut at = from;
+    for (start, end) in busy {
+        if *start - at >= min {
+            free.push((at, *start));
+        }
+        at = at.max(*end);
+    }
+    if to - at >= min {
+        free.push((at, to));
+    }
+    free
+}
+
+// Rounds up to the next half hour so suggestions start on tidy times, never in the past
+fn round_up(time: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    let exact = time.second() == 0 && time.nanosecond() == 0;
+    let time = time.with_second(0).unwrap().with_nanosecond(0).unwrap();
+    let past = time.minute() as i64 % STEP_MINUTES;
+    if past == 0 && exact {
+        time
+    } else {
+        time + Duration::minutes(STEP_MINUTES - past)
+    }
+}
+
+// Weekday slots within working hours where nobody in `busy` is booked
+pub fn free_slots(
+    busy: &[(DateTime<Utc>, DateTime<Utc>)],
+    from: DateTime<FixedOffset>,
+    days: u64,
+    duration: Duration,
+) -> Vec<Slot> {
+    let tz = *from.offset();
+    let mut slots = Vec::new();
+
+    for date in from.date_naive().iter_days().take(days as usize) {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            continue;
+        }
+        let Some(day_start) = date
+            .and_time(NaiveTime::from_hms_opt(WORKDAY_START, 0, 0).unwrap())
+            .and_local_timezone(tz)
+            .latest()
+        else {
+            continue;
+        };
+        let Some(day_end) = date
+            .and_time(NaiveTime::from_hms_opt(WORKDAY_END, 0, 0).unwrap())
+            .and_local_timezone(tz)
+            .latest()
+        else {
+            continue;
+        };
+
+        let mut start = round_up(day_start.max(from));
+        while start + duration <= day_end {
+            let end = start + duration;
+            if !overlaps(busy, start.to_utc(), end.to_utc()) {
+                slots.push((start, end));
+                if slots.len() == MAX_SLOTS {
+                    return slots;
+                }
+            }
+            start += Duration::minutes(STEP_MINUTES);
+        }
+    }
+    slots
+}
+
+// Earliest-deadline-first: each task gets the first free block before the end of its due day,
+// and placed blocks count as busy for the tasks after it. Returns the blocks and how many tasks
+// did not fit.
+pub fn plan(
+    mut tasks: Vec<(String, Duration, NaiveDate)>,
+    busy: &[(DateTime<Utc>, DateTime<Utc>)],
+    from: DateTime<FixedOffset>,
+) -> (Vec<(String, Slot)>, usize) {
+    tasks.sort_by_key(|(_, _, due)| *due);
+    let mut busy = busy.to_vec();
+    let mut blocks = Vec::new();
+    let mut unplaced = 0;
+    for (title, effort, due) in tasks {
+        let days = (due - from.date_naive()).num_days() + 1;
+        let slot = if days > 0 {
+            free_slots(&busy, from, days as u64, effort)
+                .first()
+                .copied()
+        } else {
+            None
+        };
+        match slot {
+            Some(slot) => {
+                busy.push((slot.0.to_utc(), slot.1.to_utc()));
+                blocks.push((title, slot));
+            }
+            None => unplaced += 1,
+        }
+    }
+    (blocks, unplaced)
+}
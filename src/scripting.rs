@@ -0,0 +1,202 @@
+// Rhai scripts from ~/.config/calpersonal/scripts/*.rhai, compiled in with `--features scripting`.
+// A script can define any of:
+//   fn cmd_<name>(args)                 run with `:<name> args` from the command prompt
+//   fn display_title(title, calendar)   return a replacement title for display
+//   fn on_<hook>(arg)                   startup, refresh, create, delete, complete
+// and call `notify(text)` or `queue_input(text)` to set the status or add an event/task.
+
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub enum Effect {
+    Status(String),
+    Input(String), // handled as if typed at the event/task prompt
+}
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use super::Effect;
+    use crate::platform;
+    use rhai::{AST, Dynamic, Engine, Scope};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    const SCRIPTS_DIR: &str = "scripts";
+    // display_title runs while drawing, so a runaway script must fail instead of freezing the UI
+    const MAX_OPERATIONS: u64 = 100_000;
+    const MAX_CALL_LEVELS: usize = 32;
+    const MAX_EXPR_DEPTH: usize = 64;
+
+    pub struct Scripts {
+        engine: Engine,
+        scripts: Vec<AST>,
+        effects: Arc<Mutex<Vec<Effect>>>,
+        // (title, calendar) -> display title, cleared on refresh
+        titles: Mutex<HashMap<(String, String), Option<String>>>,
+    }
+
+    fn defines(ast: &AST, name: &str, arity: usize) -> bool {
+        ast.iter_functions()
+            .any(|f| f.name == name && f.params.len() == arity)
+    }
+
+    impl Scripts {
+        pub fn load() -> Scripts {
+            let effects = Arc::new(Mutex::new(Vec::new()));
+            let mut engine = Engine::new();
+            engine.set_max_operations(MAX_OPERATIONS);
+            engine.set_max_call_levels(MAX_CALL_LEVELS);
+            engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+            // Scripts must not write over the terminal UI
+            let out = effects.clone();
+            engine.on_print(move |text| {
+                out.lock().unwrap().push(Effect::Status(text.to_string()));
+            });
+            let out = effects.clone();
+            engine.register_fn("notify", move |text: &str| {
+                out.lock().unwrap().push(Effect::Status(text.to_string()));
+            });
+            let out = effects.clone();
+            engine.register_fn("queue_input", move |text: &str| {
+                out.lock().unwrap().push(Effect::Input(text.to_string()));
+            });
+
+            let dir = platform::config_dir().join(SCRIPTS_DIR);
+            let mut paths: Vec<_> = std::fs::read_dir(dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|e| e.path())
+                        .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            paths.sort();
+
+            let mut scripts = Vec::new();
+            for path in paths {
+                match engine.compile_file(path.clone()) {
+                    Ok(ast) => scripts.push(ast),
+                    Err(e) => effects.lock().unwrap().push(Effect::Status(format!(
+                        "Script {} failed to load: {e}",
+                        path.display()
+                    ))),
+                }
+            }
+            Scripts {
+                engine,
+                scripts,
+                effects,
+                titles: Mutex::new(HashMap::new()),
+            }
+        }
+
+        pub fn command(&self, line: &str) -> Result<(), String> {
+            let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+            let function = format!("cmd_{name}");
+            let ast = self
+                .scripts
+                .iter()
+                .find(|ast| defines(ast, &function, 1))
+                .ok_or(format!("Unknown command {name}"))?;
+            let result = self
+                .engine
+                .call_fn::<Dynamic>(
+                    &mut Scope::new(),
+                    ast,
+                    &function,
+                    (args.trim().to_string(),),
+                )
+                .map_err(|e| e.to_string())?;
+            // A returned string is shorthand for notify()
+            if let Ok(text) = result.into_string() {
+                self.effects.lock().unwrap().push(Effect::Status(text));
+            }
+            Ok(())
+        }
+
+        // The last script defining display_title wins; errors are reported once per title
+        pub fn display_title(&self, title: &str, calendar: &str) -> Option<String> {
+            let ast = self
+                .scripts
+                .iter()
+                .rev()
+                .find(|ast| defines(ast, "display_title", 2))?;
+            let key = (title.to_string(), calendar.to_string());
+            if let Some(cached) = self.titles.lock().unwrap().get(&key) {
+                return cached.clone();
+            }
+            let result = match self.engine.call_fn::<String>(
+                &mut Scope::new(),
+                ast,
+                "display_title",
+                key.clone(),
+            ) {
+                Ok(text) => Some(text),
+                Err(e) => {
+                    self.effects
+                        .lock()
+                        .unwrap()
+                        .push(Effect::Status(format!("display_title: {e}")));
+                    None
+                }
+            };
+            self.titles.lock().unwrap().insert(key, result.clone());
+            result
+        }
+
+        pub fn clear_titles(&self) {
+            self.titles.lock().unwrap().clear();
+        }
+
+        pub fn hook(&self, hook: &str, arg: &str) {
+            let function = format!("on_{hook}");
+            for ast in self.scripts.iter().filter(|ast| defines(ast, &function, 1)) {
+                if let Err(e) = self.engine.call_fn::<Dynamic>(
+                    &mut Scope::new(),
+                    ast,
+                    &function,
+                    (arg.to_string(),),
+                ) {
+                    self.effects
+                        .lock()
+                        .unwrap()
+                        .push(Effect::Status(format!("{function}: {e}")));
+                }
+            }
+        }
+
+        pub fn take_effects(&self) -> Vec<Effect> {
+            std::mem::take(&mut *self.effects.lock().unwrap())
+        }
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+mod engine {
+    use super::Effect;
+
+    pub struct Scripts;
+
+    impl Scripts {
+        pub fn load() -> Scripts {
+            Scripts
+        }
+
+        pub fn command(&self, _line: &str) -> Result<(), String> {
+            Err("Built without scripting support".to_string())
+        }
+
+        pub fn display_title(&self, _title: &str, _calendar: &str) -> Option<String> {
+            None
+        }
+
+        pub fn clear_titles(&self) {}
+
+        pub fn hook(&self, _hook: &str, _arg: &str) {}
+
+        pub fn take_effects(&self) -> Vec<Effect> {
+            Vec::new()
+        }
+    }
+}
+
+pub use engine::Scripts;
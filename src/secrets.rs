@@ -0,0 +1,26 @@
+use crate::platform;
+
+// Config values that may be references instead of plaintext:
+// "env:NAME" reads an environment variable, "file:PATH" runs the decrypt command on PATH
+// (gpg by default, `age -d -i key.txt` works the same way). Anything else is used as is.
+pub fn resolve(value: &str, decrypt_command: &str) -> Result<String, String> {
+    if let Some(name) = value.strip_prefix("env:") {
+        return std::env::var(name).map_err(|_| format!("{name} is not set"));
+    }
+    let Some(path) = value.strip_prefix("file:") else {
+        return Ok(value.to_string());
+    };
+    let path = platform::expand_home(path);
+    // Through the shell like the note editor, so the command can carry its own arguments
+    let output = platform::shell_with_path(decrypt_command, &path)
+        .output()
+        .map_err(|e| format!("{decrypt_command}: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Could not decrypt {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,75 @@
+use google_calendar3::api;
+use google_tasks1::api::Task;
+use serde::Serialize;
+use serde_json::Value;
+
+// List responses carry a few dozen fields per event, most of them empty or never read here.
+// The cache keeps what the app shows, matches on or sends back: i_cal_uid ties copies of one
+// meeting on several calendars together, etag tells webhooks what changed, and the
+// organizer is who an invitation is from. Patches are
+// sparse, so nothing dropped here is cleared on the server.
+pub fn event(event: api::Event) -> api::Event {
+    api::Event {
+        id: event.id,
+        i_cal_uid: event.i_cal_uid,
+        etag: event.etag,
+        summary: event.summary,
+        description: event.description,
+        location: event.location,
+        start: event.start,
+        end: event.end,
+        status: event.status,
+        event_type: event.event_type,
+        transparency: event.transparency,
+        html_link: event.html_link,
+        hangout_link: event.hangout_link,
+        conference_data: event.conference_data,
+        recurring_event_id: event.recurring_event_id,
+        recurrence: event.recurrence,
+        original_start_time: event.original_start_time,
+        updated: event.updated,
+        reminders: event.reminders,
+        attendees: event.attendees,
+        organizer: event.organizer,
+        color_id: event.color_id,
+        focus_time_properties: event.focus_time_properties,
+        out_of_office_properties: event.out_of_office_properties,
+        working_location_properties: event.working_location_properties,
+        ..api::Event::default()
+    }
+}
+
+pub fn task(task: Task) -> Task {
+    Task {
+        id: task.id,
+        etag: task.etag,
+        title: task.title,
+        notes: task.notes,
+        due: task.due,
+        status: task.status,
+        completed: task.completed,
+        parent: task.parent,
+        position: task.position,
+        updated: task.updated,
+        web_view_link: task.web_view_link,
+        ..Task::default()
+    }
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+// The API types write every unset field as null; leaving them out reads back the same
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    let mut value = serde_json::to_value(value)?;
+    strip_nulls(&mut value);
+    serde_json::to_string(&value)
+}
@@ -0,0 +1,186 @@
+use crate::panels::Panel;
+use crate::platform;
+use crate::report::Period;
+use chrono::{DateTime, Days, Local, NaiveDate};
+use google_tasks1::api::Task;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Paragraph, Widget},
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// Task id → the day it was completed. Kept on this machine, so clearing completed tasks
+// from Google doesn't lose the history.
+pub type Completions = HashMap<String, NaiveDate>;
+
+const RELOAD: Duration = Duration::from_secs(10);
+
+fn path() -> PathBuf {
+    platform::data_dir().join("completions.json")
+}
+
+pub fn load() -> Completions {
+    match read_to_string(path()) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn save(completions: &Completions) {
+    let path = path();
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(completions) {
+        let _ = write(path, json);
+    }
+}
+
+// Folds a fetched task list into the history: new completions are added and tasks marked
+// not done again are dropped. Tasks missing from the list (cleared or deleted) stay.
+pub fn record(tasks: &[(Task, String)]) {
+    let mut completions = load();
+    let mut changed = false;
+    for (task, _) in tasks {
+        let Some(id) = task.id.as_ref() else {
+            continue;
+        };
+        let done = task.status.as_deref() == Some("completed");
+        let day = task
+            .completed
+            .as_deref()
+            .and_then(|c| DateTime::parse_from_rfc3339(c).ok())
+            .map(|c| c.with_timezone(&Local).date_naive());
+        match day {
+            Some(day) if done => changed |= completions.insert(id.clone(), day) != Some(day),
+            _ if !done => changed |= completions.remove(id).is_some(),
+            _ => {}
+        }
+    }
+    if changed {
+        save(&completions);
+    }
+}
+
+pub struct Stats {
+    pub current: usize, // days in a row up to today, or up to yesterday if none yet today
+    pub best: usize,
+    pub weeks: Vec<(NaiveDate, u64)>, // week start and completions, oldest first
+}
+
+pub fn stats(completions: &Completions, today: NaiveDate, weeks: usize) -> Stats {
+    let mut per_day: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for day in completions.values() {
+        *per_day.entry(*day).or_default() += 1;
+    }
+
+    let mut current = 0;
+    let mut day = if per_day.contains_key(&today) {
+        today
+    } else {
+        today - Days::new(1)
+    };
+    while per_day.contains_key(&day) {
+        current += 1;
+        day = day - Days::new(1);
+    }
+
+    let mut best = 0;
+    let mut run = 0;
+    let mut last: Option<NaiveDate> = None;
+    for day in per_day.keys() {
+        run = match last {
+            Some(last) if *day == last + Days::new(1) => run + 1,
+            _ => 1,
+        };
+        best = best.max(run);
+        last = Some(*day);
+    }
+
+    let this_week = *Period::Week.range(today).start();
+    let weeks = (0..weeks as u64)
+        .rev()
+        .map(|back| {
+            let start = this_week - Days::new(7 * back);
+            let count = per_day
+                .range(start..start + Days::new(7))
+                .map(|(_, count)| count)
+                .sum();
+            (start, count)
+        })
+        .collect();
+    Stats {
+        current,
+        best,
+        weeks,
+    }
+}
+
+// Streaks and a chart of weekly totals, added by `streaks = true` under [panels]
+#[derive(Default)]
+pub struct StreaksPanel {
+    completions: Completions,
+    loaded: Option<Instant>,
+}
+
+impl Panel for StreaksPanel {
+    fn title(&self) -> &str {
+        "Streaks"
+    }
+
+    // Reread now and then, as the app records completions after each task sync
+    fn update(&mut self) {
+        if self.loaded.is_some_and(|t| t.elapsed() < RELOAD) {
+            return;
+        }
+        self.loaded = Some(Instant::now());
+        self.completions = load();
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        const BAR_WIDTH: u16 = 5;
+        let weeks = (area.width / (BAR_WIDTH + 1)).max(1) as usize;
+        let today = Local::now().date_naive();
+        let stats = stats(&self.completions, today, weeks);
+        let this_week = stats.weeks.last().map(|(_, count)| *count).unwrap_or(0);
+        let last_week = match stats.weeks.len() {
+            n if n >= 2 => stats.weeks[n - 2].1,
+            _ => 0,
+        };
+        let [text_area, chart_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+        let days = |n: usize| if n == 1 { "day" } else { "days" };
+        let streak = Line::from(format!("Streak: {} {}", stats.current, days(stats.current)));
+        Paragraph::new(vec![
+            if stats.current > 0 {
+                streak.bold()
+            } else {
+                streak.dim()
+            },
+            Line::from(format!("Best: {} {}", stats.best, days(stats.best))),
+            Line::from(format!("This week: {this_week} (last {last_week})")),
+        ])
+        .render(text_area, buf);
+
+        let bars: Vec<Bar> = stats
+            .weeks
+            .iter()
+            .map(|(start, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(start.format("%-m/%-d").to_string()))
+            })
+            .collect();
+        BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(BAR_WIDTH)
+            .bar_gap(1)
+            .render(chart_area, buf);
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+
+const J2000: f64 = 2451545.0;
+
+fn from_julian(j: f64) -> DateTime<Utc> {
+    let epoch = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+    epoch + Duration::seconds(((j - J2000) * 86400.0) as i64)
+}
+
+// Solar transit and hour angle cosine for `date` (sunrise equation, NOAA approximation)
+fn solar(date: NaiveDate, lat: f64, lon: f64) -> (f64, f64) {
+    let days = (date - NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()).num_days() as f64;
+    let mean_noon = days + 0.0008 - lon / 360.0;
+    let anomaly = (357.5291 + 0.98560028 * mean_noon)
+        .rem_euclid(360.0)
+        .to_radians();
+    let center =
+        1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic = (anomaly.to_degrees() + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+    let transit = J2000 + mean_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+    let declination = (ecliptic.sin() * 23.4397_f64.to_radians().sin()).asin();
+    let lat = lat.to_radians();
+    // -0.833° accounts for refraction and the sun's radius
+    let cos_hour = ((-0.833_f64).to_radians().sin() - lat.sin() * declination.sin())
+        / (lat.cos() * declination.cos());
+    (transit, cos_hour)
+}
+
+// None during polar day or night
+pub fn sun_times(date: NaiveDate, lat: f64, lon: f64) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let (transit, cos_hour) = solar(date, lat, lon);
+    if !(-1.0..=1.0).contains(&cos_hour) {
+        return None;
+    }
+    let half_day = cos_hour.acos().to_degrees() / 360.0;
+    Some((
+        from_julian(transit - half_day),
+        from_julian(transit + half_day),
+    ))
+}
+
+// Fraction of the day the sun is up, 0.0 in polar night and 1.0 in polar day
+pub fn daylight_fraction(date: NaiveDate, lat: f64, lon: f64) -> f64 {
+    let (_, cos_hour) = solar(date, lat, lon);
+    cos_hour.clamp(-1.0, 1.0).acos().to_degrees() / 180.0
+}
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Job control. Raw mode turns Ctrl-Z into a key press, so the app stops itself; a SIGTSTP sent
+// with `kill` is caught the same way. SIGCONT after an outside SIGSTOP asks for a redraw.
+static SUSPEND: AtomicBool = AtomicBool::new(false);
+static RESUMED: AtomicBool = AtomicBool::new(false);
+
+pub fn request() {
+    SUSPEND.store(true, Ordering::Relaxed);
+}
+
+pub fn take_request() -> bool {
+    SUSPEND.swap(false, Ordering::Relaxed)
+}
+
+pub fn take_resumed() -> bool {
+    RESUMED.swap(false, Ordering::Relaxed)
+}
+
+#[cfg(unix)]
+pub fn listen() {
+    use tokio::signal::unix::{SignalKind, signal};
+    tokio::spawn(async {
+        let (Ok(mut tstp), Ok(mut cont)) = (
+            signal(SignalKind::from_raw(libc::SIGTSTP)),
+            signal(SignalKind::from_raw(libc::SIGCONT)),
+        ) else {
+            return;
+        };
+        loop {
+            tokio::select! {
+                _ = tstp.recv() => request(),
+                _ = cont.recv() => RESUMED.store(true, Ordering::Relaxed),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn listen() {}
+
+// Blocks until the shell continues the job. SIGTSTP is handled above, so stop with SIGSTOP.
+#[cfg(unix)]
+pub fn stop() {
+    tracing::info!("suspending");
+    unsafe {
+        libc::raise(libc::SIGSTOP);
+    }
+    // The SIGCONT that woke us is already handled by the caller's redraw
+    RESUMED.store(false, Ordering::Relaxed);
+}
+
+#[cfg(not(unix))]
+pub fn stop() {}
@@ -1,50 +1,102 @@
-use dirs::home_dir;
-use google_tasks1::{TasksHub, yup_oauth2};
-use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
-use hyper_util::{client::legacy::Client, client::legacy::connect, rt::TokioExecutor};
-use std::error::Error;
-
-pub async fn get_tasks_hub()
--> Result<TasksHub<HttpsConnector<connect::HttpConnector>>, Box<dyn Error>> {
-    let secret_path = home_dir()
-        .expect("Could not find home directory")
-        .join(".config/calpersonal/clientsecret.json");
-
-    let token_path = home_dir()
-        .expect("Could not find home directory")
-        .join(".cache/calpersonal/task_tokens/tokencache.json");
-
-    let secret: yup_oauth2::ApplicationSecret = yup_oauth2::read_application_secret(secret_path)
-        .await
-        .map_err(|e| format!("clientsecret.json not found: {}", e))?;
-
-    // 2. Authenticator (opens browser first time, reuses tokencache.json)
-    let auth = yup_oauth2::InstalledFlowAuthenticator::builder(
-        secret,
-        yup_oauth2::InstalledFlowReturnMethod::HTTPRedirect,
-    )
-    .persist_tokens_to_disk(token_path)
-    .build()
-    .await
-    .map_err(|e| format!("Failed to create authenticator: {}", e))?;
-
-    let scopes = &["https://www.googleapis.com/auth/tasks"];
-    auth.token(scopes)
-        .await
-        .map_err(|e| format!("Failed to get token: {}", e))?;
-
-    let https = HttpsConnectorBuilder::new()
-        .with_native_roots()
-        .map_err(|e| format!("Failed to load native roots: {}", e))?
-        .https_or_http()
-        .enable_http1()
-        .build();
+use crate::calendar_auth;
+use crate::config::{Access, AuthConfig, NetworkConfig};
+use crate::error::{Error, Result};
+use crate::net;
+use crate::platform;
+use google_tasks1::{TasksHub, api::Scope, yup_oauth2};
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use std::path::PathBuf;
+
+fn token_path() -> PathBuf {
+    platform::cache_dir().join("task_tokens/tokencache.json")
+}
+
+// Read-only access also reads tasks only
+pub fn read_scope() -> Scope {
+    match calendar_auth::access() {
+        Access::ReadOnly => Scope::Readonly,
+        Access::Full | Access::Events => Scope::Full,
+    }
+}
+
+pub fn write_scope() -> Scope {
+    Scope::Full
+}
+
+fn service_token_path() -> PathBuf {
+    platform::cache_dir().join("task_tokens/service_account.json")
+}
+
+// Drops the stored token so the next sign-in goes through the browser again
+pub fn forget_token() {
+    let _ = std::fs::remove_file(token_path());
+    let _ = std::fs::remove_file(service_token_path());
+}
+
+pub fn signed_in(auth_config: &AuthConfig) -> bool {
+    auth_config.service_account.is_some() || token_path().exists()
+}
+
+pub async fn get_tasks_hub(
+    network: NetworkConfig,
+    client_secret: Option<String>,
+    auth_config: AuthConfig,
+) -> Result<TasksHub<net::Connector>> {
+    // Sign-in and refreshes go through the proxy and CA bundle like every other call
+    let token_client = net::token_client(&network).map_err(|e| Error::Network(e.to_string()))?;
+    let auth = match &auth_config.service_account {
+        Some(key_path) => {
+            tracing::info!(api = "tasks", "signing in with a service account");
+            let key = yup_oauth2::read_service_account_key(platform::expand_home(key_path))
+                .await
+                .map_err(|e| Error::Auth(format!("service account key {key_path}: {e}")))?;
+            let builder =
+                yup_oauth2::ServiceAccountAuthenticator::builder(key).hyper_client(token_client);
+            let builder = match &auth_config.subject {
+                Some(subject) => builder.subject(subject.clone()),
+                None => builder,
+            };
+            builder
+                .persist_tokens_to_disk(service_token_path())
+                .build()
+                .await?
+        }
+        None => {
+            let secret_path = platform::config_dir().join("clientsecret.json");
+
+            tracing::info!(api = "tasks", "signing in");
+            // Decrypted at startup when configured; otherwise the plain file
+            let secret: yup_oauth2::ApplicationSecret = match client_secret {
+                Some(json) => yup_oauth2::parse_application_secret(json)
+                    .map_err(|e| Error::Auth(format!("invalid client secret: {e}")))?,
+                None => yup_oauth2::read_application_secret(secret_path)
+                    .await
+                    .map_err(|e| Error::Auth(format!("clientsecret.json not found: {e}")))?,
+            };
+
+            // 2. Authenticator (opens browser first time, reuses tokencache.json)
+            yup_oauth2::InstalledFlowAuthenticator::builder(
+                secret,
+                yup_oauth2::InstalledFlowReturnMethod::HTTPRedirect,
+            )
+            .hyper_client(token_client)
+            .persist_tokens_to_disk(token_path())
+            .build()
+            .await?
+        }
+    };
+
+    // Token requests go out through token_client, so each is timed like an API call; only the
+    // wait for the browser on first sign-in is not
+    auth.token(&[read_scope()]).await?;
+
+    let https = net::build_connector(&network).map_err(|e| Error::Network(e.to_string()))?;
 
     let client = Client::builder(TokioExecutor::new()).build(https);
     Ok(TasksHub::new(client, auth))
 }
 
-// pub async fn test_connection(hub: &TasksHub<HttpsConnector<connect::HttpConnector>>) -> bool {
+// pub async fn test_connection(hub: &TasksHub<net::Connector>) -> bool {
 //     match hub.tasks().get("primary").doit().await {
 //         Ok((_, calendar)) => {
 //             println!(
@@ -0,0 +1,109 @@
+use crate::config::ThemeConfig;
+use ratatui::style::Color;
+
+// What the UI draws with, by role rather than by color
+#[derive(Clone)]
+pub struct Theme {
+    pub today: Color,
+    pub sunday: Color,
+    pub saturday: Color,
+    pub dimmed: Color,   // other months' days, finished tasks, what's already over
+    pub selected: Color, // background of the selected day or row
+    pub selected_text: Color,
+    pub range: Color, // background of the days in an `x` range
+    pub overdue: Color,
+    pub ok: Color, // status colors
+    pub warning: Color,
+    pub error: Color,
+}
+
+const DEFAULT: Theme = Theme {
+    today: Color::Green,
+    sunday: Color::Red,
+    saturday: Color::Blue,
+    dimmed: Color::DarkGray,
+    selected: Color::DarkGray,
+    selected_text: Color::White,
+    range: Color::Blue,
+    overdue: Color::Red,
+    ok: Color::Green,
+    warning: Color::Yellow,
+    error: Color::Red,
+};
+
+// For terminals with a light background, where dark gray and yellow wash out
+const LIGHT: Theme = Theme {
+    today: Color::Rgb(0x1a, 0x7f, 0x37),
+    sunday: Color::Rgb(0xcf, 0x22, 0x2e),
+    saturday: Color::Rgb(0x09, 0x69, 0xda),
+    dimmed: Color::Gray,
+    selected: Color::Rgb(0xd0, 0xd7, 0xde),
+    selected_text: Color::Black,
+    range: Color::LightBlue,
+    overdue: Color::Rgb(0xcf, 0x22, 0x2e),
+    ok: Color::Rgb(0x1a, 0x7f, 0x37),
+    warning: Color::Rgb(0x9a, 0x67, 0x00),
+    error: Color::Rgb(0xcf, 0x22, 0x2e),
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    today: Color::LightGreen,
+    sunday: Color::LightRed,
+    saturday: Color::LightCyan,
+    dimmed: Color::Gray,
+    selected: Color::White,
+    selected_text: Color::Black,
+    range: Color::LightBlue,
+    overdue: Color::LightRed,
+    ok: Color::LightGreen,
+    warning: Color::LightYellow,
+    error: Color::LightRed,
+};
+
+pub const PRESETS: [(&str, Theme); 3] = [
+    ("default", DEFAULT),
+    ("light", LIGHT),
+    ("high-contrast", HIGH_CONTRAST),
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        DEFAULT
+    }
+}
+
+impl Theme {
+    // The preset with the config's colors over it; anything unreadable is logged and skipped
+    pub fn from_config(config: &ThemeConfig) -> Theme {
+        let mut theme = match PRESETS.iter().find(|(name, _)| *name == config.preset) {
+            Some((_, theme)) => theme.clone(),
+            None => {
+                tracing::warn!("unknown theme preset {:?}", config.preset);
+                DEFAULT
+            }
+        };
+        let roles = [
+            (&config.today, &mut theme.today),
+            (&config.sunday, &mut theme.sunday),
+            (&config.saturday, &mut theme.saturday),
+            (&config.dimmed, &mut theme.dimmed),
+            (&config.selected, &mut theme.selected),
+            (&config.selected_text, &mut theme.selected_text),
+            (&config.range, &mut theme.range),
+            (&config.overdue, &mut theme.overdue),
+            (&config.ok, &mut theme.ok),
+            (&config.warning, &mut theme.warning),
+            (&config.error, &mut theme.error),
+        ];
+        for (value, color) in roles {
+            let Some(value) = value else {
+                continue;
+            };
+            match value.parse() {
+                Ok(parsed) => *color = parsed,
+                Err(_) => tracing::warn!("unknown theme color {value:?}"),
+            }
+        }
+        theme
+    }
+}
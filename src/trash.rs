@@ -0,0 +1,117 @@
+use crate::platform;
+use chrono::{DateTime, Duration, Local};
+use google_calendar3::api;
+use google_tasks1::api::Task;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, write};
+use tokio::sync::Mutex;
+
+const TRASH_FILE: &str = "trash.json";
+const RETENTION_DAYS: i64 = 30;
+// Deletes and restores finish on their own tasks; one load/modify/save at a time
+static TRASH: Mutex<()> = Mutex::const_new(());
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Trashed {
+    Event(Box<api::Event>, String), // event, calendar id
+    Task(Box<Task>, String),        // task, tasklist id
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrashItem {
+    pub deleted_at: DateTime<Local>,
+    pub item: Trashed,
+}
+
+impl TrashItem {
+    pub fn title(&self) -> &str {
+        match &self.item {
+            Trashed::Event(event, _) => event.summary.as_deref(),
+            Trashed::Task(task, _) => task.title.as_deref(),
+        }
+        .unwrap_or("Untitled")
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        match &self.item {
+            Trashed::Event(event, _) => event.id.as_deref(),
+            Trashed::Task(task, _) => task.id.as_deref(),
+        }
+    }
+}
+
+// Newest first, with anything past the retention window dropped
+pub fn load() -> Vec<TrashItem> {
+    let path = platform::cache_dir().join(TRASH_FILE);
+    let mut items: Vec<TrashItem> = match read_to_string(path) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    let cutoff = Local::now() - Duration::days(RETENTION_DAYS);
+    items.retain(|i| i.deleted_at > cutoff);
+    items.sort_by_key(|i| std::cmp::Reverse(i.deleted_at));
+    items
+}
+
+fn save(items: &[TrashItem]) {
+    let path = platform::cache_dir().join(TRASH_FILE);
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(items) {
+        let _ = write(path, json);
+    }
+}
+
+pub async fn push(trashed: Vec<Trashed>) {
+    let _trash = TRASH.lock().await;
+    let mut items = load();
+    let deleted_at = Local::now();
+    items.splice(
+        0..0,
+        trashed
+            .into_iter()
+            .map(|item| TrashItem { deleted_at, item }),
+    );
+    save(&items);
+}
+
+// Items trashed together share deleted_at, so the id tells them apart
+pub async fn remove(deleted_at: DateTime<Local>, id: Option<String>) {
+    let _trash = TRASH.lock().await;
+    let mut items = load();
+    items.retain(|i| i.deleted_at != deleted_at || i.id() != id.as_deref());
+    save(&items);
+}
+
+// Server-assigned fields have to go or the insert is rejected as a duplicate
+pub fn fresh_event(event: &api::Event) -> api::Event {
+    api::Event {
+        id: None,
+        etag: None,
+        i_cal_uid: None,
+        html_link: None,
+        created: None,
+        updated: None,
+        creator: None,
+        organizer: None,
+        hangout_link: None,
+        conference_data: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        status: None,
+        sequence: None,
+        ..event.clone()
+    }
+}
+
+pub fn fresh_task(task: &Task) -> Task {
+    Task {
+        title: task.title.clone(),
+        notes: task.notes.clone(),
+        due: task.due.clone(),
+        status: task.status.clone(),
+        completed: task.completed.clone(),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,199 @@
+use crate::error::{Error, Result};
+use crate::{config, net, platform};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs::{create_dir_all, read_to_string, rename, write};
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+const RELEASES_URL: &str = "https://api.github.com/repos/Crdr0122/calpersonal/releases";
+// The version that last ran, to tell a first launch after an upgrade
+const LAST_VERSION_FILE: &str = "last_version";
+
+// What the startup check found, for the UI
+pub enum News {
+    Available(String),         // a newer release's tag
+    Changelog(String, String), // this version's tag and notes, after an upgrade
+}
+
+#[derive(Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>, // the release notes, Markdown
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+// GitHub turns away requests without a User-Agent
+fn client(timeout: Duration) -> Result<reqwest::Client> {
+    Ok(net::http_client()
+        .timeout(timeout)
+        .user_agent(concat!("calpersonal/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+async fn get_release(url: &str, timeout: Duration) -> Result<Release> {
+    Ok(client(timeout)?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+pub async fn latest(timeout: Duration) -> Result<Release> {
+    get_release(&format!("{RELEASES_URL}/latest"), timeout).await
+}
+
+// This version's own release, for its notes
+pub async fn current(timeout: Duration) -> Result<Release> {
+    get_release(&format!("{RELEASES_URL}/tags/v{VERSION}"), timeout).await
+}
+
+// "v1.10.0" → [1, 10, 0]; pre-release suffixes are ignored
+fn parts(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+pub fn is_newer(tag: &str) -> bool {
+    parts(tag) > parts(VERSION)
+}
+
+// Release binaries are named by target, e.g. calpersonal-x86_64-linux or
+// calpersonal-aarch64-macos; Windows ones end in .exe
+fn asset_name() -> String {
+    format!(
+        "calpersonal-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+async fn download(release: &Release, name: &str) -> Result<Vec<u8>> {
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == name)
+        .ok_or_else(|| Error::Parse(format!("{} has no {name}", release.tag_name)))?;
+    Ok(client(Duration::from_secs(300))?
+        .get(&asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?
+        .to_vec())
+}
+
+// The release publishes `<asset>.sha256` (`sha256sum` output) beside every binary
+fn verify(bytes: &[u8], checksum_file: &[u8]) -> Result<()> {
+    let expected = String::from_utf8_lossy(checksum_file)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual: String = Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    if expected.len() != 64 || expected != actual {
+        return Err(Error::Parse(format!(
+            "checksum mismatch: expected {expected:?}, downloaded {actual}"
+        )));
+    }
+    Ok(())
+}
+
+// Downloads the release's binary for this platform, checks it against the published
+// SHA-256 and only then swaps it in for the running one
+pub async fn install(release: &Release) -> Result<PathBuf> {
+    let name = asset_name();
+    let bytes = download(release, &name).await?;
+    verify(&bytes, &download(release, &format!("{name}.sha256")).await?)?;
+    let exe = std::env::current_exe()?;
+    // Written next to the binary so the rename stays on one filesystem
+    let staged = exe.with_extension("new");
+    write(&staged, &bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    // Windows won't replace a running executable but lets it be moved aside
+    #[cfg(windows)]
+    rename(&exe, exe.with_extension("old"))?;
+    rename(&staged, &exe)?;
+    Ok(exe)
+}
+
+// The version that ran before this one, if it was a different one; records this one
+pub fn upgraded_from() -> Option<String> {
+    let path = platform::cache_dir().join(LAST_VERSION_FILE);
+    let last = read_to_string(&path).ok().map(|v| v.trim().to_string());
+    if last.as_deref() != Some(VERSION) {
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+        let _ = write(&path, VERSION);
+    }
+    last.filter(|v| v != VERSION)
+}
+
+// Notes for this version if it is new since the last launch, then whether a newer one is out
+pub async fn check(news: tokio::sync::mpsc::Sender<News>) {
+    if upgraded_from().is_some() {
+        match current(net::timeout()).await {
+            Ok(release) => {
+                let notes = release.body.unwrap_or_default();
+                let _ = news.send(News::Changelog(release.tag_name, notes)).await;
+            }
+            Err(e) => tracing::warn!("release notes: {e}"),
+        }
+    }
+    match latest(net::timeout()).await {
+        Ok(release) if is_newer(&release.tag_name) => {
+            let _ = news.send(News::Available(release.tag_name)).await;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("update check: {e}"),
+    }
+}
+
+// `calpersonal update [--check]`
+pub async fn run(args: &[String]) -> io::Result<()> {
+    net::configure(&config::parse_config().network);
+    let release = latest(net::timeout())
+        .await
+        .map_err(|e| io::Error::other(format!("Could not check for updates: {e}")))?;
+    if !is_newer(&release.tag_name) {
+        println!("calpersonal {VERSION} is the latest version");
+        return Ok(());
+    }
+    println!(
+        "calpersonal {} is available (this is {VERSION})",
+        release.tag_name
+    );
+    if args.iter().any(|a| a == "--check") {
+        return Ok(());
+    }
+    let path = install(&release)
+        .await
+        .map_err(|e| io::Error::other(format!("Update failed: {e}")))?;
+    println!("Installed {} to {}", release.tag_name, path.display());
+    Ok(())
+}
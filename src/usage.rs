@@ -0,0 +1,55 @@
+use crate::config::QuotaConfig;
+use chrono::{DateTime, Duration, Local};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// Every Google API request made this session, oldest first; pruned to today
+static CALLS: Mutex<VecDeque<DateTime<Local>>> = Mutex::new(VecDeque::new());
+
+pub fn record_call() {
+    let now = Local::now();
+    if let Ok(mut calls) = CALLS.lock() {
+        while calls
+            .front()
+            .is_some_and(|t| t.date_naive() != now.date_naive())
+        {
+            calls.pop_front();
+        }
+        calls.push_back(now);
+    }
+}
+
+// (calls in the last minute, calls today)
+pub fn counts() -> (usize, usize) {
+    let now = Local::now();
+    let Ok(calls) = CALLS.lock() else {
+        return (0, 0);
+    };
+    let minute_ago = now - Duration::minutes(1);
+    let last_minute = calls.iter().rev().take_while(|t| **t > minute_ago).count();
+    let today = calls
+        .iter()
+        .filter(|t| t.date_naive() == now.date_naive())
+        .count();
+    (last_minute, today)
+}
+
+// Share of the tighter of the two quotas already used, 0.0 to 1.0+
+pub fn load(quota: &QuotaConfig) -> f64 {
+    let (minute, day) = counts();
+    (minute as f64 / quota.per_minute.max(1) as f64).max(day as f64 / quota.per_day.max(1) as f64)
+}
+
+pub fn near_limit(quota: &QuotaConfig) -> bool {
+    load(quota) >= quota.warn_at
+}
+
+// Automatic refreshes back off as usage climbs past half the quota
+pub fn refresh_delay(quota: &QuotaConfig) -> std::time::Duration {
+    let load = load(quota);
+    if load < 0.5 {
+        std::time::Duration::ZERO
+    } else {
+        std::time::Duration::from_secs_f64(((load - 0.5) * 2.0).min(1.0) * 60.0)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::error::{Error, Result};
+use crate::platform;
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use google_calendar3::api;
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+// Calendar id given to private events so they are never sent to Google
+pub const PRIVATE_CALENDAR: &str = "private:";
+
+pub fn is_private(calendar_id: &str) -> bool {
+    calendar_id == PRIVATE_CALENDAR
+}
+
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    data: Vec<u8>,
+}
+
+// Key for the private events file, derived once per session from the passphrase
+pub struct Vault {
+    key: Key,
+    salt: Vec<u8>,
+}
+
+fn path() -> PathBuf {
+    platform::data_dir().join("private_events.json")
+}
+
+fn derive(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| Error::Cache(e.to_string()))?;
+    Ok(key)
+}
+
+impl Vault {
+    // Decrypts the stored events; the first unlock picks the passphrase
+    pub fn unlock(passphrase: &str) -> Result<(Vault, Vec<api::Event>)> {
+        let Ok(data) = read_to_string(path()) else {
+            let mut salt = vec![0; 16];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive(passphrase, &salt)?;
+            return Ok((Vault { key, salt }, Vec::new()));
+        };
+        let sealed: Sealed =
+            serde_json::from_str(&data).map_err(|e| Error::Cache(e.to_string()))?;
+        let key = derive(passphrase, &sealed.salt)?;
+        let plain = XChaCha20Poly1305::new(&key)
+            .decrypt(XNonce::from_slice(&sealed.nonce), sealed.data.as_slice())
+            .map_err(|_| Error::Cache("wrong passphrase".to_string()))?;
+        let events = serde_json::from_slice(&plain).map_err(|e| Error::Cache(e.to_string()))?;
+        Ok((
+            Vault {
+                key,
+                salt: sealed.salt,
+            },
+            events,
+        ))
+    }
+
+    pub fn save(&self, events: &[api::Event]) -> Result<()> {
+        let plain = serde_json::to_vec(events)?;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let data = XChaCha20Poly1305::new(&self.key)
+            .encrypt(&nonce, plain.as_slice())
+            .map_err(|e| Error::Cache(e.to_string()))?;
+        let sealed = Sealed {
+            salt: self.salt.clone(),
+            nonce: nonce.to_vec(),
+            data,
+        };
+        let path = path();
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, serde_json::to_string(&sealed)?)?;
+        Ok(())
+    }
+}
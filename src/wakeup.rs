@@ -0,0 +1,24 @@
+use std::future::Future;
+use tokio::sync::Notify;
+
+// Lets background work cut the event loop's wait short once it has something to show
+static WAKE: Notify = Notify::const_new();
+
+pub fn wake() {
+    WAKE.notify_one();
+}
+
+pub async fn woken() {
+    WAKE.notified().await;
+}
+
+// tokio::spawn that wakes the event loop when the work is done
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        future.await;
+        wake();
+    });
+}
@@ -1,13 +1,25 @@
+use crate::error::{Error, Result};
+use crate::{net, platform};
+use chrono::{DateTime, FixedOffset, Local};
+use ratatui::style::Color;
 use reqwest;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fs::{read_to_string, write};
+use std::time::Duration;
 
-#[derive(Deserialize)]
+const WEATHER_CACHE_FILE: &str = "weather.json";
+
+#[derive(Serialize, Deserialize)]
 pub struct OneCallResponse {
+    pub lat: f64,
+    pub lon: f64,
     pub current: WeatherData,
     pub daily: Vec<DailyWeather>,
+    #[serde(default)] // not in caches written before hourly data was requested
+    pub hourly: Vec<HourlyWeather>,
     pub alerts: Option<Vec<Alert>>,
 }
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct WeatherData {
     pub temp: f64,
     pub feels_like: f64,
@@ -20,17 +32,17 @@ pub struct WeatherData {
     pub snow: Option<Snow>,
     pub weather: Vec<Weather>,
 }
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Weather {
     pub main: String,
     pub icon: String,
 }
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Rain {
     #[serde(rename = "1h")]
     pub one_hour: Option<f64>,
 }
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Snow {
     #[serde(rename = "1h")]
     pub one_hour: Option<f64>,
@@ -41,8 +53,10 @@ pub struct Geocode {
     lon: f64,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct DailyWeather {
+    #[serde(default)]
+    pub dt: i64, // unix time, midday of the day
     pub temp: DailyTemp,
     pub humidity: f64,
     pub wind_speed: f64,
@@ -53,45 +67,204 @@ pub struct DailyWeather {
     pub weather: Vec<Weather>,
     pub pop: f64,
 }
-#[derive(Deserialize)]
+// The next 48 hours
+#[derive(Serialize, Deserialize)]
+pub struct HourlyWeather {
+    pub dt: i64, // unix time
+    pub temp: f64,
+    pub pop: f64,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct DailyTemp {
     pub max: f64,
     pub min: f64,
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Alert {
     pub sender_name: String,
     pub event: String,
     pub description: String,
 }
 
-pub async fn fetch_weather(
+// Open-Meteo's current air quality
+#[derive(Serialize, Deserialize)]
+pub struct AirQuality {
+    pub us_aqi: f64,
+    pub pm2_5: f64,
+}
+#[derive(Deserialize)]
+struct AirQualityResponse {
+    current: AirQuality,
+}
+
+// The last successful response, shown at startup and while offline
+#[derive(Serialize, Deserialize)]
+pub struct CachedWeather {
+    pub fetched_at: DateTime<Local>,
+    pub response: OneCallResponse,
+    #[serde(default)]
+    pub air: Option<AirQuality>,
+}
+
+pub fn load_cached() -> Option<CachedWeather> {
+    let path = platform::cache_dir().join(WEATHER_CACHE_FILE);
+    serde_json::from_str(&read_to_string(path).ok()?).ok()
+}
+
+pub fn save_cached(weather: &CachedWeather) {
+    let path = platform::cache_dir().join(WEATHER_CACHE_FILE);
+    if let Ok(json) = serde_json::to_string(weather) {
+        let _ = write(path, json);
+    }
+}
+
+async fn geocode(
+    client: &reqwest::Client,
     api_key: &str,
-    city: String,
-    country: String,
-) -> Option<OneCallResponse> {
+    city: &str,
+    country: &str,
+) -> Result<Geocode> {
+    if city.is_empty() {
+        return Err(Error::Config("set city or latitude/longitude".to_string()));
+    }
     let geo_url = format!(
         "http://api.openweathermap.org/geo/1.0/direct?q={},{}&limit=1&appid={}",
-        city, country, api_key
+        urlencoding::encode(city),
+        urlencoding::encode(country),
+        api_key
     );
-    if let Some(geocode_response) = reqwest::get(&geo_url).await.ok() {
-        if geocode_response.status().is_success() {
-            let geo: Vec<Geocode> = geocode_response.json().await.ok()?;
-            let onecall_url = format!(
-                "https://api.openweathermap.org/data/3.0/onecall?lat={}&lon={}&exclude=minutely,hourly&units=metric&appid={}",
-                geo[0].lat, geo[0].lon, api_key
-            );
-            if let Some(onecall_response) = reqwest::get(&onecall_url).await.ok() {
-                if onecall_response.status().is_success() {
-                    let res: OneCallResponse =
-                        onecall_response.json().await.expect("Could not decode");
-                    return Some(res);
-                }
-            }
+    let geo: Vec<Geocode> = client
+        .get(&geo_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    geo.into_iter()
+        .next()
+        .ok_or_else(|| Error::Config(format!("no place called {city}, {country}")))
+}
+
+// Configured coordinates skip the geocoding request
+pub async fn fetch_weather(
+    api_key: &str,
+    city: String,
+    country: String,
+    coords: Option<(f64, f64)>,
+    timeout: Duration,
+) -> Result<OneCallResponse> {
+    let client = net::http_client().timeout(timeout).build()?;
+    let (lat, lon) = match coords {
+        Some(coords) => coords,
+        None => {
+            let geo = geocode(&client, api_key, &city, &country).await?;
+            (geo.lat, geo.lon)
         }
+    };
+    let onecall_url = format!(
+        "https://api.openweathermap.org/data/3.0/onecall?lat={lat}&lon={lon}&exclude=minutely&units=metric&appid={api_key}"
+    );
+    Ok(client
+        .get(&onecall_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+pub async fn fetch_air_quality(lat: f64, lon: f64, timeout: Duration) -> Result<AirQuality> {
+    let client = net::http_client().timeout(timeout).build()?;
+    let url = format!(
+        "https://air-quality-api.open-meteo.com/v1/air-quality?latitude={lat}&longitude={lon}&current=us_aqi,pm2_5"
+    );
+    let response: AirQualityResponse = client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response.current)
+}
+
+// The EPA's bands, named short enough for the weather table
+pub fn aqi_level(aqi: f64) -> (&'static str, Color) {
+    match aqi {
+        ..=50.0 => ("good", Color::Green),
+        ..=100.0 => ("moderate", Color::Yellow),
+        ..=150.0 => ("poor", Color::LightRed),
+        ..=200.0 => ("unhealthy", Color::Red),
+        ..=300.0 => ("very poor", Color::Magenta),
+        _ => ("hazardous", Color::Rgb(126, 0, 35)),
+    }
+}
+
+// Location words that suggest the event is outside; `#outdoor` in the title or notes also counts
+const OUTDOOR_WORDS: [&str; 12] = [
+    "park", "beach", "trail", "garden", "field", "stadium", "lake", "camp", "pitch", "court",
+    "outdoor", "outdoors",
+];
+
+pub fn looks_outdoor(summary: &str, location: &str, description: &str) -> bool {
+    summary.contains("#outdoor")
+        || description.contains("#outdoor")
+        || location
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| OUTDOOR_WORDS.contains(&word))
+}
+
+// Chance of rain for a start time: the hour it falls in within the next 48 hours, otherwise
+// (and for all-day events) that day's figure. None past the 8-day forecast.
+pub fn pop_at(
+    weather: &OneCallResponse,
+    start: DateTime<FixedOffset>,
+    all_day: bool,
+) -> Option<f64> {
+    let ts = start.timestamp();
+    let hourly = weather
+        .hourly
+        .iter()
+        .find(|h| (h.dt..h.dt + 3600).contains(&ts))
+        .filter(|_| !all_day);
+    match hourly {
+        Some(h) => Some(h.pop),
+        None => weather
+            .daily
+            .iter()
+            .find(|d| {
+                DateTime::from_timestamp(d.dt, 0).is_some_and(|t| {
+                    t.with_timezone(start.offset()).date_naive() == start.date_naive()
+                })
+            })
+            .map(|d| d.pop),
+    }
+}
+
+// One block glyph per value, scaled between the lowest and highest
+pub fn sparkline(values: &[f64], low: f64, high: f64) -> Vec<char> {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let span = (high - low).max(f64::EPSILON);
+    values
+        .iter()
+        .map(|v| BARS[(((v - low) / span) * 7.0).round().clamp(0.0, 7.0) as usize])
+        .collect()
+}
+
+// A single narrow glyph for an icon code, for the header; the night codes end in 'n'
+pub fn glyph(icon: &str) -> &'static str {
+    match icon.get(..2).unwrap_or_default() {
+        "01" if icon.ends_with('n') => "☾",
+        "01" => "☀",
+        "02" | "03" | "04" => "☁",
+        "09" | "10" => "☂",
+        "11" => "ϟ",
+        "13" => "❄",
+        _ => "≡",
     }
-    None
 }
 
 pub fn get_weather_icon(icon: String) -> String {
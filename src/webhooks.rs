@@ -0,0 +1,200 @@
+use crate::config::WebhooksConfig;
+use crate::merge::EventsCache;
+use crate::{birthdays, ics, issues, net, platform, vault};
+use chrono::Utc;
+use google_tasks1::api::Task;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+// Deliveries that ran out of retries, sent again before the next batch
+const OUTBOX_MAX: usize = 500;
+static OUTBOX: Mutex<()> = Mutex::const_new(());
+
+// An event or task as the last sync saw it, keyed by (calendar or task list, id)
+#[derive(Serialize, Clone)]
+pub struct Item {
+    pub kind: &'static str, // "event" or "task"
+    pub id: String,
+    pub source: String, // calendar or task list id
+    pub title: Option<String>,
+    pub when: Option<String>, // event start or task due date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done: Option<bool>, // tasks only
+    #[serde(skip)]
+    version: Option<String>, // etag, or when it was last updated
+}
+
+pub type Items = HashMap<(String, String), Item>;
+
+#[derive(Serialize)]
+pub struct Change {
+    pub action: &'static str, // created, updated, completed or deleted
+    #[serde(flatten)]
+    pub item: Item,
+}
+
+// Only what Google holds: feeds, private events and birthdays are this client's own
+pub fn events(cache: &EventsCache) -> Items {
+    cache
+        .values()
+        .flatten()
+        .filter(|(_, calendar)| {
+            !ics::is_feed(calendar)
+                && !vault::is_private(calendar)
+                && !birthdays::is_birthday(calendar)
+        })
+        .filter_map(|(event, calendar)| {
+            let id = event.id.clone()?;
+            let start = event.start.as_ref();
+            let item = Item {
+                kind: "event",
+                id: id.clone(),
+                source: calendar.clone(),
+                title: event.summary.clone(),
+                when: start
+                    .and_then(|s| s.date_time.map(|dt| dt.to_rfc3339()))
+                    .or_else(|| start.and_then(|s| s.date.map(|d| d.to_string()))),
+                done: None,
+                version: event
+                    .etag
+                    .clone()
+                    .or_else(|| event.updated.map(|u| u.to_rfc3339())),
+            };
+            Some(((calendar.clone(), id), item))
+        })
+        .collect()
+}
+
+pub fn tasks(tasks: &[(Task, String)]) -> Items {
+    tasks
+        .iter()
+        .filter(|(_, list)| !issues::is_issue(list))
+        .filter_map(|(task, list)| {
+            let id = task.id.clone()?;
+            let item = Item {
+                kind: "task",
+                id: id.clone(),
+                source: list.clone(),
+                title: task.title.clone(),
+                when: task.due.clone(),
+                done: Some(task.status.as_deref() == Some("completed")),
+                version: task.etag.clone().or_else(|| task.updated.clone()),
+            };
+            Some(((list.clone(), id), item))
+        })
+        .collect()
+}
+
+// What happened between two syncs; a task ticked off counts as completed, not updated
+pub fn changes(old: &Items, new: &Items) -> Vec<Change> {
+    let mut changes: Vec<Change> = new
+        .iter()
+        .filter_map(|(key, item)| {
+            let action = match old.get(key) {
+                None => "created",
+                Some(before) if before.done != Some(true) && item.done == Some(true) => "completed",
+                Some(before) if before.version != item.version => "updated",
+                Some(_) => return None,
+            };
+            Some(Change {
+                action,
+                item: item.clone(),
+            })
+        })
+        .collect();
+    changes.extend(
+        old.iter()
+            .filter(|(key, _)| !new.contains_key(*key))
+            .map(|(_, item)| Change {
+                action: "deleted",
+                item: item.clone(),
+            }),
+    );
+    changes
+}
+
+fn outbox_path() -> PathBuf {
+    platform::cache_dir().join("webhook_outbox.json")
+}
+
+fn load_outbox() -> Vec<(String, Value)> {
+    match read_to_string(outbox_path()) {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn save_outbox(outbox: &[(String, Value)]) {
+    let path = outbox_path();
+    if let Some(parent) = path.parent() {
+        let _ = create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(outbox) {
+        let _ = write(path, json);
+    }
+}
+
+// Tries again after 1s, 2s, 4s...
+async fn post(client: &reqwest::Client, url: &str, payload: &Value, retries: u32) -> bool {
+    for attempt in 0..=retries {
+        let result = client
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+        match result {
+            Ok(_) => return true,
+            Err(e) => {
+                tracing::warn!(url, attempt, "webhook: {e}");
+                if attempt < retries {
+                    tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+                }
+            }
+        }
+    }
+    false
+}
+
+async fn deliver(urls: Vec<String>, payload: Value, retries: u32) {
+    // One batch at a time, so the outbox file isn't written from two places
+    let _outbox = OUTBOX.lock().await;
+    let mut queue = load_outbox();
+    queue.extend(urls.into_iter().map(|url| (url, payload.clone())));
+    let client = match net::http_client().build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("webhook client: {e}");
+            save_outbox(&queue);
+            return;
+        }
+    };
+    let mut failed = Vec::new();
+    for (url, payload) in queue {
+        if !post(&client, &url, &payload, retries).await {
+            failed.push((url, payload));
+        }
+    }
+    if failed.len() > OUTBOX_MAX {
+        failed.drain(..failed.len() - OUTBOX_MAX);
+    }
+    save_outbox(&failed);
+}
+
+// POSTs the changes to every configured URL in the background
+pub fn send(config: &WebhooksConfig, changes: Vec<Change>) {
+    if config.urls.is_empty() || changes.is_empty() {
+        return;
+    }
+    let payload = json!({
+        "source": "calpersonal",
+        "sent_at": Utc::now().to_rfc3339(),
+        "changes": changes,
+    });
+    tokio::spawn(deliver(config.urls.clone(), payload, config.retries));
+}
@@ -0,0 +1,136 @@
+use crate::{config, events, file_writing};
+use chrono::{Duration, Local, Utc};
+use google_calendar3::api;
+use google_tasks1::api::Task;
+use std::io;
+
+// `calpersonal widget --format waybar|polybar|tmux`: one line for a status bar,
+// built from the caches the TUI keeps up to date so no OAuth round trip is needed.
+
+#[derive(Clone, Copy)]
+enum Format {
+    Waybar,
+    Polybar,
+    Tmux,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Format> {
+        match name {
+            "waybar" => Some(Format::Waybar),
+            "polybar" => Some(Format::Polybar),
+            "tmux" => Some(Format::Tmux),
+            _ => None,
+        }
+    }
+}
+
+fn countdown(until: Duration) -> String {
+    let minutes = until.num_minutes().max(0);
+    match minutes {
+        0 => "now".to_string(),
+        m if m < 60 => format!("in {m}m"),
+        m if m < 24 * 60 => format!("in {}h{:02}m", m / 60, m % 60),
+        m => format!("in {}d", m / (24 * 60)),
+    }
+}
+
+// The next timed event within the coming week, skipping working locations and focus blocks
+fn next_event() -> Option<(api::Event, Duration)> {
+    let cache = file_writing::load_events_cache();
+    let now = Utc::now();
+    let today = Local::now().date_naive();
+    (0..7)
+        .filter_map(|d| cache.get(&(today + Duration::days(d))))
+        .flatten()
+        .filter(|(ev, _)| !events::EventKind::of(ev).is_background())
+        .filter_map(|(ev, _)| {
+            let start = ev.start.as_ref()?.date_time?;
+            let end = ev.end.as_ref().and_then(|e| e.date_time).unwrap_or(start);
+            (end > now).then(|| (ev.clone(), start - now))
+        })
+        .min_by_key(|(_, until)| *until)
+}
+
+// Open tasks and how many of them are due today or earlier
+pub fn task_counts(tasks: &[(Task, String)]) -> (usize, usize) {
+    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+    let open: Vec<_> = tasks
+        .iter()
+        .filter(|(task, _)| task.status.as_deref() != Some("completed"))
+        .collect();
+    let due = open
+        .iter()
+        .filter(|(task, _)| {
+            task.due
+                .as_deref()
+                .and_then(|d| d.get(..10))
+                .is_some_and(|d| d <= today.as_str())
+        })
+        .count();
+    (open.len(), due)
+}
+
+pub fn run(args: &[String]) -> io::Result<()> {
+    let name = args
+        .iter()
+        .position(|a| a == "--format")
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+        .unwrap_or("polybar");
+    let format = Format::parse(name).ok_or(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Unknown widget format {name}, expected waybar, polybar or tmux"),
+    ))?;
+    let config = config::parse_config();
+
+    let event = next_event().map(|(ev, until)| {
+        let time = ev
+            .start
+            .as_ref()
+            .and_then(|s| s.date_time)
+            .map(|dt| {
+                dt.with_timezone(&Local)
+                    .format(config.display.time_format())
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let title = ev.summary.unwrap_or("(no title)".to_string());
+        (format!("{title} {}", countdown(until)), time, until)
+    });
+    let (open, due) = task_counts(&file_writing::load_tasks_cache());
+    let tasks = format!("☑ {due}/{open}");
+    let text = match &event {
+        Some((next, _, _)) => format!("{next} · {tasks}"),
+        None => tasks.clone(),
+    };
+
+    match format {
+        Format::Waybar => {
+            let tooltip = match &event {
+                Some((next, time, _)) => format!("{time} {next}\n{due} due, {open} open tasks"),
+                None => format!("No upcoming events\n{due} due, {open} open tasks"),
+            };
+            // Waybar styles on `class`; "soon" is anything starting within 15 minutes
+            let class = match &event {
+                Some((_, _, until)) if *until <= Duration::minutes(15) => "soon",
+                Some(_) => "upcoming",
+                None => "free",
+            };
+            let json = serde_json::json!({ "text": text, "tooltip": tooltip, "class": class });
+            println!("{json}");
+        }
+        Format::Polybar => println!("{text}"),
+        Format::Tmux => {
+            // tmux interprets #[...] as style, so literal '#' must be doubled
+            let text = text.replace('#', "##");
+            match &event {
+                Some((_, _, until)) if *until <= Duration::minutes(15) => {
+                    println!("#[fg=yellow]{text}#[default]")
+                }
+                _ => println!("{text}"),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use crate::error::{Error, Result};
+use crate::net;
+use crate::weather::{
+    DailyTemp, DailyWeather, HourlyWeather, OneCallResponse, Rain, Snow, Weather, WeatherData,
+};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde::Deserialize;
+use std::time::Duration;
+
+// wttr.in's JSON (`?format=j1`); every number comes as a string
+#[derive(Deserialize)]
+struct Response {
+    current_condition: Vec<Current>,
+    nearest_area: Vec<Area>,
+    weather: Vec<Day>,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Current {
+    #[serde(rename = "temp_C")]
+    temp_c: String,
+    #[serde(rename = "FeelsLikeC")]
+    feels_like_c: String,
+    humidity: String,
+    windspeed_kmph: String,
+    pressure: String,
+    uv_index: String,
+    cloudcover: String,
+    #[serde(rename = "precipMM")]
+    precip_mm: String,
+    weather_code: String,
+    local_obs_date_time: String, // "2025-06-01 10:15 AM"
+    #[serde(rename = "observation_time")]
+    observation_time: String, // the same moment in UTC, "08:15 AM"
+}
+#[derive(Deserialize)]
+struct Area {
+    latitude: String,
+    longitude: String,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Day {
+    date: String,
+    #[serde(rename = "maxtempC")]
+    max_temp_c: String,
+    #[serde(rename = "mintempC")]
+    min_temp_c: String,
+    uv_index: String,
+    hourly: Vec<Hour>,
+}
+// Every three hours, `time` is local: "0", "300" … "2100"
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Hour {
+    time: String,
+    #[serde(rename = "tempC")]
+    temp_c: String,
+    humidity: String,
+    windspeed_kmph: String,
+    pressure: String,
+    chanceofrain: String,
+    chanceofsnow: String,
+    #[serde(rename = "precipMM")]
+    precip_mm: String,
+    weather_code: String,
+}
+
+fn num(value: &str) -> f64 {
+    value.trim().parse().unwrap_or_default()
+}
+
+// OpenWeatherMap reports m/s
+fn wind(kmph: f64) -> f64 {
+    (kmph / 3.6 * 10.0).round() / 10.0
+}
+
+// World Weather Online condition codes to OpenWeatherMap's main/icon, which the strip draws
+fn condition(code: &str) -> Weather {
+    let (main, icon) = match code {
+        "113" => ("Clear", "01d"),
+        "116" => ("Clouds", "02d"),
+        "119" | "122" => ("Clouds", "04d"),
+        "143" | "248" | "260" => ("Mist", "50d"),
+        "176" | "263" | "266" | "281" | "284" | "293" | "296" | "353" => ("Drizzle", "09d"),
+        "200" | "386" | "389" | "392" | "395" => ("Thunderstorm", "11d"),
+        "299" | "302" | "305" | "308" | "311" | "314" | "356" | "359" => ("Rain", "10d"),
+        "179" | "182" | "185" | "227" | "230" | "317" | "320" | "323" | "326" | "329" | "332"
+        | "335" | "338" | "350" | "362" | "365" | "368" | "371" | "374" | "377" => ("Snow", "13d"),
+        _ => ("Clouds", "03d"),
+    };
+    Weather {
+        main: main.to_string(),
+        icon: icon.to_string(),
+    }
+}
+
+// The place's UTC offset from the observation time given both ways, to the quarter hour
+fn offset(current: &Current) -> Option<FixedOffset> {
+    let local =
+        NaiveDateTime::parse_from_str(&current.local_obs_date_time, "%Y-%m-%d %I:%M %p").ok()?;
+    let utc_time = NaiveTime::parse_from_str(&current.observation_time, "%I:%M %p").ok()?;
+    // The UTC date is the local one or a day either side, whichever is closest
+    let minutes = [-1, 0, 1]
+        .into_iter()
+        .map(|d| {
+            (local - (local.date() + chrono::Duration::days(d)).and_time(utc_time)).num_minutes()
+        })
+        .min_by_key(|m| m.abs())?;
+    FixedOffset::east_opt(((minutes as f64 / 15.0).round() * 15.0 * 60.0) as i32)
+}
+
+fn convert(response: Response) -> Result<OneCallResponse> {
+    let missing = || Error::Parse("wttr.in sent no current conditions".to_string());
+    let current = response.current_condition.first().ok_or_else(missing)?;
+    let area = response.nearest_area.first().ok_or_else(missing)?;
+    let offset = offset(current).unwrap_or_else(|| *chrono::Local::now().offset());
+
+    let mut hourly = Vec::new();
+    let mut daily = Vec::new();
+    for day in &response.weather {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let at = |hours: u32| {
+            offset
+                .from_local_datetime(&date.and_hms_opt(hours, 0, 0).unwrap())
+                .single()
+                .map(|t| t.timestamp())
+                .unwrap_or_default()
+        };
+        // Each three-hour reading stands for the hours it covers
+        for hour in &day.hourly {
+            let start = num(&hour.time) as u32 / 100;
+            for h in start..(start + 3).min(24) {
+                hourly.push(HourlyWeather {
+                    dt: at(h),
+                    temp: num(&hour.temp_c),
+                    pop: num(&hour.chanceofrain) / 100.0,
+                });
+            }
+        }
+        let average = |field: fn(&Hour) -> &str| {
+            day.hourly.iter().map(|h| num(field(h))).sum::<f64>() / day.hourly.len().max(1) as f64
+        };
+        let precip: f64 = day.hourly.iter().map(|h| num(&h.precip_mm)).sum();
+        let snowy = day.hourly.iter().any(|h| num(&h.chanceofsnow) > 50.0);
+        let midday = day
+            .hourly
+            .iter()
+            .find(|h| num(&h.time) as u32 == 1200)
+            .or(day.hourly.first());
+        daily.push(DailyWeather {
+            dt: at(12),
+            temp: DailyTemp {
+                max: num(&day.max_temp_c),
+                min: num(&day.min_temp_c),
+            },
+            humidity: average(|h| &h.humidity).round(),
+            wind_speed: wind(average(|h| &h.windspeed_kmph)),
+            pressure: average(|h| &h.pressure).round(),
+            uvi: num(&day.uv_index),
+            rain: (!snowy && precip > 0.0).then_some(precip),
+            snow: (snowy && precip > 0.0).then_some(precip),
+            weather: midday
+                .map(|h| condition(&h.weather_code))
+                .into_iter()
+                .collect(),
+            pop: day
+                .hourly
+                .iter()
+                .map(|h| num(&h.chanceofrain) / 100.0)
+                .fold(0.0, f64::max),
+        });
+    }
+
+    let precip = num(&current.precip_mm);
+    let weather = condition(&current.weather_code);
+    let snowing = weather.main == "Snow";
+    Ok(OneCallResponse {
+        lat: num(&area.latitude),
+        lon: num(&area.longitude),
+        current: WeatherData {
+            temp: num(&current.temp_c),
+            feels_like: num(&current.feels_like_c),
+            humidity: num(&current.humidity),
+            wind_speed: wind(num(&current.windspeed_kmph)),
+            pressure: num(&current.pressure),
+            uvi: num(&current.uv_index),
+            clouds: num(&current.cloudcover),
+            rain: (!snowing && precip > 0.0).then_some(Rain {
+                one_hour: Some(precip),
+            }),
+            snow: (snowing && precip > 0.0).then_some(Snow {
+                one_hour: Some(precip),
+            }),
+            weather: vec![weather],
+        },
+        daily,
+        hourly,
+        alerts: None,
+    })
+}
+
+// Needs no key; with neither coordinates nor a city wttr.in goes by the caller's IP.
+// Three days of forecast instead of eight, and no alerts.
+pub async fn fetch(
+    city: &str,
+    country: &str,
+    coords: Option<(f64, f64)>,
+    timeout: Duration,
+) -> Result<OneCallResponse> {
+    let location = match coords {
+        Some((lat, lon)) => format!("{lat},{lon}"),
+        None if city.is_empty() => String::new(),
+        None if country.is_empty() => urlencoding::encode(city).into_owned(),
+        None => format!(
+            "{},{}",
+            urlencoding::encode(city),
+            urlencoding::encode(country)
+        ),
+    };
+    let client = net::http_client().timeout(timeout).build()?;
+    let response: Response = client
+        .get(format!("https://wttr.in/{location}?format=j1"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    convert(response)
+}